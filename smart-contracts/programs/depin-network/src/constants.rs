@@ -1,36 +1,121 @@
-use anchor_lang::prelude::*;
-
 // Program seeds
 pub const NETWORK_STATE_SEED: &[u8] = b"network_state";
 pub const MINT_SEED: &[u8] = b"mint";
+pub const TREASURY_SEED: &[u8] = b"treasury";
 pub const WIFI_HOTSPOT_SEED: &[u8] = b"wifi_hotspot";
 pub const LOGISTICS_PARTNER_SEED: &[u8] = b"logistics_partner";
 pub const FARM_SEED: &[u8] = b"farm";
+pub const HEALTHCARE_PROVIDER_SEED: &[u8] = b"healthcare_provider";
+pub const TAX_POINT_SEED: &[u8] = b"tax_point";
 pub const STAKING_POOL_SEED: &[u8] = b"staking_pool";
 pub const USER_STAKE_SEED: &[u8] = b"user_stake";
+pub const POOL_VAULT_SEED: &[u8] = b"pool_vault";
+pub const REWARD_VAULT_SEED: &[u8] = b"reward_vault";
 pub const USER_PROFILE_SEED: &[u8] = b"user_profile";
 pub const PROPOSAL_SEED: &[u8] = b"proposal";
 pub const VOTE_SEED: &[u8] = b"vote";
+pub const EPOCH_SEED: &[u8] = b"reward_epoch";
+pub const EPOCH_CONTRIBUTION_SEED: &[u8] = b"epoch_contribution";
+pub const OPERATOR_REWARDS_SEED: &[u8] = b"operator_rewards";
+pub const POC_CHALLENGE_SEED: &[u8] = b"poc_challenge";
+pub const POC_BOND_SEED: &[u8] = b"poc_bond";
+pub const HEX_CELL_SEED: &[u8] = b"hex_cell";
+pub const DELEGATION_SEED: &[u8] = b"delegation";
+pub const DELEGATE_RECORD_SEED: &[u8] = b"delegate_record";
+pub const PROPOSAL_DEPOSIT_SEED: &[u8] = b"proposal_deposit";
+pub const ASSET_MINT_SEED: &[u8] = b"asset_mint";
+pub const EV_CHARGER_SEED: &[u8] = b"ev_charger";
+pub const CHARGING_SESSION_SEED: &[u8] = b"charging_session";
+pub const PLOT_CITY_SEED: &[u8] = b"plot_city";
+pub const CHARGING_PLOT_SEED: &[u8] = b"charging_plot";
+pub const PLOT_LISTING_SEED: &[u8] = b"plot_listing";
+
+// Token Metadata program PDA seeds
+pub const METADATA_SEED: &[u8] = b"metadata";
+pub const EDITION_SEED: &[u8] = b"edition";
+
+// Asset NFT metadata
+pub const ASSET_NFT_SYMBOL: &str = "PKNA";
+pub const MAX_NFT_URI_LEN: usize = 200;
 
 // Token constants
 pub const PKN_DECIMALS: u8 = 9;
 pub const INITIAL_SUPPLY: u64 = 1_000_000_000 * 10_u64.pow(PKN_DECIMALS as u32); // 1B PKN
+pub const TREASURY_ALLOCATION_BPS: u64 = 2000; // 20% of total supply seeds the treasury
 
 // Reward rates (in smallest unit)
 pub const DEFAULT_WIFI_REWARD_RATE: u64 = 100 * 10_u64.pow(PKN_DECIMALS as u32); // 100 PKN per GB
 pub const DEFAULT_LOGISTICS_REWARD_RATE: u64 = 50 * 10_u64.pow(PKN_DECIMALS as u32); // 50 PKN per delivery
 pub const DEFAULT_AGRICULTURE_REWARD_RATE: u64 = 25 * 10_u64.pow(PKN_DECIMALS as u32); // 25 PKN per submission
+pub const DEFAULT_POWER_REWARD_RATE: u64 = 5 * 10_u64.pow(PKN_DECIMALS as u32); // 5 PKN per kWh delivered
+
+// Epoch emissions
+pub const SECTOR_COUNT: usize = 6;
+pub const DEFAULT_EPOCH_EMISSION: u64 = 100_000 * 10_u64.pow(PKN_DECIMALS as u32); // 100K PKN per epoch
+pub const DEFAULT_SECTOR_EMISSION_BPS: [u16; SECTOR_COUNT] = [2500, 2000, 1500, 1500, 1000, 1500]; // WiFi, logistics, agriculture, healthcare, taxation, power
 
 // Staking parameters
 pub const MIN_STAKE_AMOUNT: u64 = 100 * 10_u64.pow(PKN_DECIMALS as u32); // 100 PKN
 pub const GOVERNANCE_THRESHOLD: u64 = 1000 * 10_u64.pow(PKN_DECIMALS as u32); // 1000 PKN
+pub const REWARD_PRECISION: u128 = 1_000_000_000_000_000_000; // 1e18 fixed-point scale for reward-per-token
+pub const BASIS_POINTS: u64 = 10_000;
+
+// Governance voting rules
+pub const PROPOSAL_TYPE_COUNT: usize = 5;
+pub const DEFAULT_QUORUM_BPS: u16 = 1000; // 10% of total stake must vote (abstains count)
+pub const MIN_APPROVAL_THRESHOLD_BPS: u16 = 5000; // Never less than a simple majority
+// Yes share of yes + no votes that must be exceeded, per proposal type:
+// ParameterChange, TreasurySpend, ProtocolUpgrade, RewardRateChange, NetworkExpansion
+pub const DEFAULT_APPROVAL_THRESHOLD_BPS: [u16; PROPOSAL_TYPE_COUNT] = [5000, 6666, 6666, 5000, 5000];
+
+// Governance timelock
+pub const DEFAULT_TIMELOCK_DELAY: i64 = 2 * 24 * 3600; // 2 days between approval and execution
+pub const DEFAULT_EXECUTION_WINDOW: i64 = 7 * 24 * 3600; // Queued proposals expire 7 days after the delay
+pub const MIN_TIMELOCK_DELAY: i64 = 24 * 3600; // 1 day
+pub const MAX_TIMELOCK_DELAY: i64 = 30 * 24 * 3600; // 30 days
+
+// Proposal deposits
+pub const DEFAULT_PROPOSAL_DEPOSIT: u64 = 100 * 10_u64.pow(PKN_DECIMALS as u32); // 100 PKN, slashed if quorum is missed
+
+// Vote delegation
+pub const MIN_DELEGATION_STAKE: u64 = 100 * 10_u64.pow(PKN_DECIMALS as u32); // 100 PKN staked to delegate
+pub const DELEGATION_COOLDOWN: i64 = 24 * 3600; // 1 day between delegation changes
 
 // Time constants
 pub const SECONDS_PER_DAY: i64 = 86400;
 pub const SECONDS_PER_HOUR: i64 = 3600;
+pub const SECONDS_PER_YEAR: i64 = 365 * SECONDS_PER_DAY;
 pub const VOTING_PERIOD: i64 = 7 * SECONDS_PER_DAY; // 7 days
+pub const EPOCH_DURATION: i64 = SECONDS_PER_DAY; // 24 hours
+pub const TELEMETRY_MAX_AGE: i64 = 5 * 60; // Device reports must be signed within 5 minutes
+pub const MIN_CHARGING_SESSION_INTERVAL: i64 = 15 * 60; // Idle time between sessions on a charger
+pub const MAX_CHARGING_SESSION_DURATION: i64 = 12 * SECONDS_PER_HOUR; // Operator may cancel a session after this
+
+// Proof of coverage
+pub const POC_CHALLENGE_WINDOW: i64 = SECONDS_PER_HOUR; // Witnesses have 1 hour to respond
+pub const MIN_POC_WITNESSES: u8 = 2; // Valid witnesses needed to verify coverage
+pub const POC_REPUTATION_REWARD: u8 = 5;
+pub const POC_REPUTATION_PENALTY: u8 = 10;
+pub const POC_CHALLENGE_BOND: u64 = 10 * 10_u64.pow(PKN_DECIMALS as u32); // 10 PKN, forfeited if the hotspot proves coverage
+pub const POC_CHALLENGE_FEE: u64 = 2 * 10_u64.pow(PKN_DECIMALS as u32); // 2 PKN of the bond kept by the treasury whatever the outcome
+pub const MIN_POC_RECEIPTS: u8 = 3; // Receipts needed before a hotspot that fails can be penalised
+pub const POC_WITNESS_REWARD: u64 = 5 * 10_u64.pow(PKN_DECIMALS as u32); // 5 PKN of WiFi epoch contribution per valid receipt
+
+// Hotspot density
+pub const DEFAULT_H3_RESOLUTION: u8 = 8; // ~0.7 km² hexes
+pub const MAX_H3_RESOLUTION: u8 = 15;
+pub const DEFAULT_HEX_DENSITY_TARGET: u32 = 3; // Hotspots per hex that earn the full reward
+
+// Device telemetry
+pub const TELEMETRY_DOMAIN: &[u8] = b"pkn-telemetry-v1";
 
 // Network limits
 pub const MAX_HOTSPOTS_PER_USER: u8 = 10;
 pub const MAX_LOGISTICS_PARTNERS_PER_USER: u8 = 5;
-pub const MAX_FARMS_PER_USER: u8 = 20;
\ No newline at end of file
+pub const MAX_FARMS_PER_USER: u8 = 20;
+pub const MAX_HEALTHCARE_PROVIDERS_PER_USER: u8 = 5;
+pub const MAX_TAX_POINTS_PER_USER: u8 = 5;
+pub const MAX_EV_CHARGERS_PER_USER: u8 = 10;
+
+// Charging plot grids
+pub const MAX_PLOT_GRID_SIZE: u16 = 100; // Rows and columns per city
\ No newline at end of file
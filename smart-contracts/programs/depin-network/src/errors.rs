@@ -88,4 +88,124 @@ pub enum DePINError {
     ArithmeticOverflow,
     #[msg("Invalid program state")]
     InvalidProgramState,
+    #[msg("Reward would exceed total token supply")]
+    SupplyCapExceeded,
+    #[msg("Insufficient treasury funds")]
+    InsufficientTreasuryFunds,
+    #[msg("Invalid amount")]
+    InvalidAmount,
+    #[msg("Reward epoch already finalized")]
+    EpochAlreadyFinalized,
+    #[msg("Reward epoch not finalized yet")]
+    EpochNotFinalized,
+    #[msg("Sector emission split must total 10000 basis points")]
+    InvalidEmissionSplit,
+    #[msg("Staking reward vault has insufficient funds")]
+    InsufficientRewardVault,
+    #[msg("Invalid staking pool configuration")]
+    InvalidPoolConfig,
+    #[msg("Token account is not the staking pool's vault")]
+    InvalidPoolVault,
+    #[msg("Asset index is out of range")]
+    InvalidAssetIndex,
+    #[msg("Maximum number of assets of this type reached")]
+    TooManyAssets,
+    #[msg("Device signature instruction missing or malformed")]
+    MissingDeviceSignature,
+    #[msg("Telemetry is not signed by the registered device")]
+    InvalidDeviceSignature,
+    #[msg("Device nonce must increase with every report")]
+    InvalidDeviceNonce,
+    #[msg("Telemetry timestamp is too old or in the future")]
+    StaleTelemetry,
+    #[msg("Invalid coordinates")]
+    InvalidCoordinates,
+    #[msg("Asset is inactive")]
+    AssetInactive,
+    #[msg("Coverage challenge has expired")]
+    ChallengeExpired,
+    #[msg("Coverage challenge is still open")]
+    ChallengeStillOpen,
+    #[msg("Coverage challenge already resolved")]
+    ChallengeAlreadyResolved,
+    #[msg("Hotspot has already witnessed this challenge")]
+    DuplicateWitness,
+    #[msg("Coverage challenge has the maximum number of witnesses")]
+    TooManyWitnesses,
+    #[msg("Hotspots cannot witness challenges against their own owner")]
+    SelfWitness,
+    #[msg("H3 cell index does not contain the coordinates")]
+    InvalidH3Index,
+    #[msg("Invalid hex density configuration")]
+    InvalidHexConfig,
+    #[msg("Proposal action does not match the proposal type or is invalid")]
+    InvalidProposalAction,
+    #[msg("An account required to execute this proposal is missing or wrong")]
+    InvalidProposalAccounts,
+    #[msg("Invalid quorum or approval threshold")]
+    InvalidVotingRules,
+    #[msg("Proposal is not active")]
+    ProposalNotActive,
+    #[msg("Proposal is not queued for execution")]
+    ProposalNotQueued,
+    #[msg("Timelock delay has not elapsed")]
+    TimelockNotElapsed,
+    #[msg("Queued proposal has expired")]
+    ProposalExpired,
+    #[msg("Invalid timelock configuration")]
+    InvalidTimelockConfig,
+    #[msg("Invalid delegate")]
+    InvalidDelegate,
+    #[msg("Delegation changed after the proposal opened")]
+    DelegationChanged,
+    #[msg("Proposal already has votes")]
+    ProposalHasVotes,
+    #[msg("Proposal outcome is not final")]
+    ProposalNotFinal,
+    #[msg("Asset is already active")]
+    AssetAlreadyActive,
+    #[msg("New owner must differ from the current owner")]
+    InvalidNewOwner,
+    #[msg("Signer does not hold the asset's NFT")]
+    NotAssetHolder,
+    #[msg("Metadata URI too long")]
+    UriTooLong,
+    #[msg("Invalid charger power output")]
+    InvalidPowerOutput,
+    #[msg("Charger already has a session in progress")]
+    ChargerBusy,
+    #[msg("Charging session is not in progress on this charger")]
+    SessionNotActive,
+    #[msg("Reported energy exceeds what the charger can deliver in the session")]
+    EnergyExceedsCapacity,
+    #[msg("Invalid plot grid configuration")]
+    InvalidPlotGrid,
+    #[msg("Plot is outside the city grid")]
+    PlotOutOfBounds,
+    #[msg("Signer does not own the plot")]
+    NotPlotOwner,
+    #[msg("Plot already has a charger installed")]
+    PlotOccupied,
+    #[msg("Charger coordinates are outside its plot")]
+    ChargerOutsidePlot,
+    #[msg("Invalid plot price")]
+    InvalidPlotPrice,
+    #[msg("A charger's operator cannot drive its charging sessions")]
+    DriverIsOperator,
+    #[msg("Charger must rest before a new session")]
+    ChargerCoolingDown,
+    #[msg("Charging session has not run long enough to be cancelled")]
+    SessionNotExpired,
+    #[msg("Not enough stake to delegate")]
+    InsufficientDelegationStake,
+    #[msg("Delegation changed too recently")]
+    DelegationCooldown,
+    #[msg("Listing price is above the buyer's maximum")]
+    PriceAboveMax,
+    #[msg("Timelock has elapsed; the proposal can no longer be vetoed")]
+    TimelockElapsed,
+    #[msg("PKN mint must use 9 decimals")]
+    InvalidMintDecimals,
+    #[msg("Hotspot has coverage challenges that are not resolved")]
+    ChallengesOpen,
 }
\ No newline at end of file
@@ -0,0 +1,409 @@
+use anchor_lang::prelude::*;
+use crate::constants::{PROPOSAL_TYPE_COUNT, SECTOR_COUNT};
+use crate::state::*;
+
+// Events are emitted through `emit_cpi!`, so they are recorded as self-CPI
+// instruction data and survive log truncation. Amounts are in base units.
+
+#[event]
+pub struct NetworkInitialized {
+    pub authority: Pubkey,
+    pub mint: Pubkey,
+    pub treasury: Pubkey,
+    pub total_supply: u64,
+    pub treasury_allocation: u64,
+}
+
+#[event]
+pub struct HotspotRegistered {
+    pub owner: Pubkey,
+    pub hotspot: Pubkey,
+    pub asset_id: u64,
+    pub nft_mint: Pubkey,
+    pub device_pubkey: Pubkey,
+    pub latitude: i32,
+    pub longitude: i32,
+    pub h3_index: u64,
+    pub coverage_radius: u32,
+    pub bandwidth_mbps: u32,
+    pub reward_multiplier_bps: u16,
+}
+
+/// Registration of any non-WiFi asset
+#[event]
+pub struct InfrastructureRegistered {
+    pub owner: Pubkey,
+    pub asset: Pubkey,
+    pub sector: Sector,
+    pub asset_id: u64,
+    pub nft_mint: Pubkey,
+    pub device_pubkey: Pubkey,
+}
+
+#[event]
+pub struct ChargingSessionStarted {
+    pub charger: Pubkey,
+    pub session: Pubkey,
+    pub driver: Pubkey,
+    pub session_id: u64,
+    pub start_time: i64,
+}
+
+#[event]
+pub struct ChargingSessionEnded {
+    pub charger: Pubkey,
+    pub session: Pubkey,
+    pub driver: Pubkey,
+    pub energy_delivered_wh: u64,
+    pub duration: i64,
+    pub reward_amount: u64,
+}
+
+#[event]
+pub struct ChargingSessionCancelled {
+    pub charger: Pubkey,
+    pub session: Pubkey,
+    pub driver: Pubkey,
+    pub cancelled_by: Pubkey,
+}
+
+#[event]
+pub struct PlotCityCreated {
+    pub city: Pubkey,
+    pub name: String,
+    pub rows: u16,
+    pub cols: u16,
+    pub plot_price: u64,
+}
+
+#[event]
+pub struct PlotPriceUpdated {
+    pub city: Pubkey,
+    pub plot_price: u64,
+}
+
+#[event]
+pub struct PlotPurchased {
+    pub city: Pubkey,
+    pub plot: Pubkey,
+    pub buyer: Pubkey,
+    pub row: u16,
+    pub col: u16,
+    pub price: u64,
+}
+
+#[event]
+pub struct PlotListed {
+    pub plot: Pubkey,
+    pub seller: Pubkey,
+    pub price: u64,
+}
+
+#[event]
+pub struct PlotListingCancelled {
+    pub plot: Pubkey,
+    pub seller: Pubkey,
+}
+
+#[event]
+pub struct PlotSold {
+    pub plot: Pubkey,
+    pub seller: Pubkey,
+    pub buyer: Pubkey,
+    pub price: u64,
+}
+
+/// An owner changed an asset's registration details
+#[event]
+pub struct AssetUpdated {
+    pub owner: Pubkey,
+    pub asset: Pubkey,
+    pub sector: Sector,
+}
+
+#[event]
+pub struct AssetStatusChanged {
+    pub owner: Pubkey,
+    pub asset: Pubkey,
+    pub sector: Sector,
+    pub is_active: bool,
+}
+
+#[event]
+pub struct AssetClosed {
+    pub owner: Pubkey,
+    pub asset: Pubkey,
+    pub sector: Sector,
+}
+
+/// The holder of an asset's NFT claimed its owner record
+#[event]
+pub struct AssetTransferred {
+    pub previous_owner: Pubkey,
+    pub new_owner: Pubkey,
+    pub asset: Pubkey,
+    pub sector: Sector,
+}
+
+#[event]
+pub struct DataSubmitted {
+    pub owner: Pubkey,
+    pub asset: Pubkey,
+    pub sector: Sector,
+    pub epoch: u64,
+    pub nonce: u64,
+    pub reward_amount: u64,
+    pub timestamp: i64,
+}
+
+/// An operator reward paid out, either minted or transferred from the treasury
+#[event]
+pub struct RewardMinted {
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub source: RewardSource,
+}
+
+#[event]
+pub struct PocChallengeIssued {
+    pub challenge: Pubkey,
+    pub hotspot: Pubkey,
+    pub challenger: Pubkey,
+    pub epoch: u64,
+    pub expires_at: i64,
+    pub bond: u64,
+}
+
+#[event]
+pub struct WitnessReceiptSubmitted {
+    pub challenge: Pubkey,
+    pub witness: Pubkey,
+    pub distance_m: u64,
+    pub signal_strength_dbm: i16,
+    pub valid: bool,
+    pub reward: u64,
+}
+
+#[event]
+pub struct PocChallengeResolved {
+    pub challenge: Pubkey,
+    pub hotspot: Pubkey,
+    pub valid_witnesses: u8,
+    pub verified: bool,
+    pub penalized: bool,
+    pub bond_to_treasury: u64,
+    pub bond_refunded: u64,
+    pub reputation_score: u8,
+}
+
+#[event]
+pub struct EpochFinalized {
+    pub epoch: u64,
+    pub emission_budget: u64,
+    pub sector_budgets: [u64; SECTOR_COUNT],
+    pub sector_contributions: [u64; SECTOR_COUNT],
+    pub end_time: i64,
+}
+
+#[event]
+pub struct EpochRewardsSettled {
+    pub owner: Pubkey,
+    pub epoch: u64,
+    pub share: u64,
+    pub claimable: u64,
+}
+
+#[event]
+pub struct TreasuryDeposited {
+    pub from: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct TreasuryWithdrawn {
+    pub recipient: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct Staked {
+    pub user: Pubkey,
+    pub pool: Pubkey,
+    pub pool_type: PoolType,
+    pub amount: u64,
+    pub total_staked: u64,
+}
+
+#[event]
+pub struct Unstaked {
+    pub user: Pubkey,
+    pub pool: Pubkey,
+    pub pool_type: PoolType,
+    pub amount: u64,
+    pub remaining_stake: u64,
+}
+
+#[event]
+pub struct RewardsClaimed {
+    pub user: Pubkey,
+    pub pool: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct StakingRewardsFunded {
+    pub pool: Pubkey,
+    pub amount: u64,
+}
+
+/// Emitted on pool creation and on every pool update, with the resulting settings
+#[event]
+pub struct StakingPoolConfigured {
+    pub pool: Pubkey,
+    pub pool_type: PoolType,
+    pub reward_rate: u64,
+    pub min_stake_amount: u64,
+    pub max_capacity: u64,
+    pub lock_period: i64,
+    pub is_active: bool,
+}
+
+#[event]
+pub struct DelegationsAccepted {
+    pub delegate: Pubkey,
+}
+
+#[event]
+pub struct VotesDelegated {
+    pub delegator: Pubkey,
+    pub delegate: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct VotesUndelegated {
+    pub delegator: Pubkey,
+    pub delegate: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct ProposalCreated {
+    pub proposal_id: u64,
+    pub proposer: Pubkey,
+    pub proposal_type: ProposalType,
+    pub action: ProposalAction,
+    pub start_time: i64,
+    pub end_time: i64,
+}
+
+#[event]
+pub struct VoteCast {
+    pub proposal_id: u64,
+    pub voter: Pubkey,
+    pub vote: Vote,
+    pub voting_power: u64,
+}
+
+/// A delegator voted directly, taking `voting_power` back from their delegate on this proposal
+#[event]
+pub struct DelegateVoteOverridden {
+    pub proposal_id: u64,
+    pub delegator: Pubkey,
+    pub delegate: Pubkey,
+    pub voting_power: u64,
+}
+
+/// Voting closed and tallied; `eta` and `expires_at` are set only when queued
+#[event]
+pub struct ProposalFinalized {
+    pub proposal_id: u64,
+    pub status: ProposalStatus,
+    pub quorum_reached: bool,
+    pub yes_votes: u64,
+    pub no_votes: u64,
+    pub abstain_votes: u64,
+    pub eta: i64,
+    pub expires_at: i64,
+}
+
+/// A proposal's deposit left escrow: back to the proposer, or to the treasury if slashed
+#[event]
+pub struct ProposalDepositSettled {
+    pub proposal_id: u64,
+    pub amount: u64,
+    pub slashed: bool,
+}
+
+#[event]
+pub struct ProposalCancelled {
+    pub proposal_id: u64,
+    pub deposit_refunded: u64,
+}
+
+#[event]
+pub struct ProposalClosed {
+    pub proposal_id: u64,
+}
+
+#[event]
+pub struct ProposalExecuted {
+    pub proposal_id: u64,
+    pub action: ProposalAction,
+}
+
+#[event]
+pub struct ProposalVetoed {
+    pub proposal_id: u64,
+    pub security_council: Pubkey,
+}
+
+/// Snapshot of the admin-tunable network parameters after any of them changes
+#[event]
+pub struct ParamsUpdated {
+    pub authority: Pubkey,
+    pub governance_threshold: u64,
+    pub wifi_reward_rate: u64,
+    pub logistics_reward_rate: u64,
+    pub agriculture_reward_rate: u64,
+    pub healthcare_reward_rate: u64,
+    pub taxation_reward_rate: u64,
+    pub power_reward_rate: u64,
+    pub epoch_emission: u64,
+    pub sector_emission_bps: [u16; SECTOR_COUNT],
+    pub reward_source: RewardSource,
+    pub h3_resolution: u8,
+    pub hex_density_target: u32,
+    pub quorum_bps: u16,
+    pub approval_threshold_bps: [u16; PROPOSAL_TYPE_COUNT],
+    pub security_council: Pubkey,
+    pub timelock_delay: i64,
+    pub execution_window: i64,
+    pub proposal_deposit: u64,
+}
+
+impl ParamsUpdated {
+    pub fn from_network_state(network_state: &NetworkState) -> Self {
+        Self {
+            authority: network_state.authority,
+            governance_threshold: network_state.governance_threshold,
+            wifi_reward_rate: network_state.wifi_reward_rate,
+            logistics_reward_rate: network_state.logistics_reward_rate,
+            agriculture_reward_rate: network_state.agriculture_reward_rate,
+            healthcare_reward_rate: network_state.healthcare_reward_rate,
+            taxation_reward_rate: network_state.taxation_reward_rate,
+            power_reward_rate: network_state.power_reward_rate,
+            epoch_emission: network_state.epoch_emission,
+            sector_emission_bps: network_state.sector_emission_bps,
+            reward_source: network_state.reward_source,
+            h3_resolution: network_state.h3_resolution,
+            hex_density_target: network_state.hex_density_target,
+            quorum_bps: network_state.quorum_bps,
+            approval_threshold_bps: network_state.approval_threshold_bps,
+            security_council: network_state.security_council,
+            timelock_delay: network_state.timelock_delay,
+            execution_window: network_state.execution_window,
+            proposal_deposit: network_state.proposal_deposit,
+        }
+    }
+}
@@ -0,0 +1,429 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions::ID as INSTRUCTIONS_SYSVAR_ID;
+use anchor_spl::token::{self, CloseAccount, Mint, Token, TokenAccount, Transfer};
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::*;
+use crate::events::*;
+use crate::utils::{
+    hotspot_distance_meters, poc_outcome, record_epoch_contribution, telemetry_message,
+    validate_telemetry_freshness, verify_device_signature, SafeMath,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct IssuePocChallenge<'info> {
+    #[account(
+        init,
+        payer = challenger,
+        space = PocChallenge::LEN,
+        seeds = [POC_CHALLENGE_SEED, wifi_hotspot.key().as_ref(), &network_state.current_epoch.to_le_bytes()],
+        bump
+    )]
+    pub poc_challenge: Account<'info, PocChallenge>,
+    
+    // Holds the challenger's bond until the challenge is resolved
+    #[account(
+        init,
+        payer = challenger,
+        token::mint = mint,
+        token::authority = poc_challenge,
+        seeds = [POC_BOND_SEED, poc_challenge.key().as_ref()],
+        bump
+    )]
+    pub bond_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = challenger
+    )]
+    pub challenger_token_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        seeds = [MINT_SEED],
+        bump
+    )]
+    pub mint: Account<'info, Mint>,
+    
+    #[account(
+        mut,
+        seeds = [WIFI_HOTSPOT_SEED, &wifi_hotspot.asset_id.to_le_bytes()],
+        bump = wifi_hotspot.bump
+    )]
+    pub wifi_hotspot: Account<'info, WiFiHotspot>,
+    
+    #[account(
+        seeds = [NETWORK_STATE_SEED],
+        bump = network_state.bump
+    )]
+    pub network_state: Account<'info, NetworkState>,
+    
+    #[account(mut)]
+    pub challenger: Signer<'info>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+}
+
+/// Challenge a hotspot to prove its coverage. Permissionless, once per hotspot per epoch.
+/// The challenger escrows `POC_CHALLENGE_BOND`, which is forfeited to the treasury if
+/// the hotspot proves its coverage. The hotspot can't be closed while the challenge is open.
+pub fn issue_poc_challenge(ctx: Context<IssuePocChallenge>) -> Result<()> {
+    require!(ctx.accounts.wifi_hotspot.is_active, DePINError::AssetInactive);
+
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.challenger_token_account.to_account_info(),
+        to: ctx.accounts.bond_vault.to_account_info(),
+        authority: ctx.accounts.challenger.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+    token::transfer(cpi_ctx, POC_CHALLENGE_BOND)?;
+
+    let wifi_hotspot = &mut ctx.accounts.wifi_hotspot;
+    wifi_hotspot.open_challenges = wifi_hotspot.open_challenges.safe_add(1)?;
+
+    let poc_challenge = &mut ctx.accounts.poc_challenge;
+    let current_time = Clock::get()?.unix_timestamp;
+
+    poc_challenge.challenger = ctx.accounts.challenger.key();
+    poc_challenge.hotspot = ctx.accounts.wifi_hotspot.key();
+    poc_challenge.epoch = ctx.accounts.network_state.current_epoch;
+    poc_challenge.issued_at = current_time;
+    poc_challenge.expires_at = current_time.safe_add(POC_CHALLENGE_WINDOW)?;
+    poc_challenge.witnesses = Vec::new();
+    poc_challenge.valid_witnesses = 0;
+    poc_challenge.resolved = false;
+    poc_challenge.verified = false;
+    poc_challenge.bump = ctx.bumps.poc_challenge;
+
+    msg!("Coverage challenge issued against hotspot {}", poc_challenge.hotspot);
+    msg!("Epoch: {}, Witness window closes at {}", poc_challenge.epoch, poc_challenge.expires_at);
+    msg!("Bond: {} PKN", POC_CHALLENGE_BOND / 10_u64.pow(9));
+
+    emit_cpi!(PocChallengeIssued {
+        challenge: poc_challenge.key(),
+        hotspot: poc_challenge.hotspot,
+        challenger: poc_challenge.challenger,
+        epoch: poc_challenge.epoch,
+        expires_at: poc_challenge.expires_at,
+        bond: POC_CHALLENGE_BOND,
+    });
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SubmitWitnessReceipt<'info> {
+    #[account(
+        mut,
+        seeds = [POC_CHALLENGE_SEED, challenged_hotspot.key().as_ref(), &poc_challenge.epoch.to_le_bytes()],
+        bump = poc_challenge.bump
+    )]
+    pub poc_challenge: Account<'info, PocChallenge>,
+    
+    #[account(
+        address = poc_challenge.hotspot
+    )]
+    pub challenged_hotspot: Account<'info, WiFiHotspot>,
+    
+    // The account holding the challenged hotspot's NFT, which identifies its current holder
+    #[account(
+        token::mint = challenged_hotspot.nft_mint,
+        constraint = challenged_nft.amount == 1 @ DePINError::NotAssetHolder
+    )]
+    pub challenged_nft: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [WIFI_HOTSPOT_SEED, &witness_hotspot.asset_id.to_le_bytes()],
+        bump = witness_hotspot.bump
+    )]
+    pub witness_hotspot: Account<'info, WiFiHotspot>,
+    
+    #[account(
+        token::mint = witness_hotspot.nft_mint,
+        token::authority = owner,
+        constraint = owner_nft.amount == 1 @ DePINError::NotAssetHolder
+    )]
+    pub owner_nft: Account<'info, TokenAccount>,
+    
+    #[account(
+        seeds = [NETWORK_STATE_SEED],
+        bump = network_state.bump
+    )]
+    pub network_state: Account<'info, NetworkState>,
+    
+    #[account(
+        mut,
+        seeds = [EPOCH_SEED, &network_state.current_epoch.to_le_bytes()],
+        bump = reward_epoch.bump
+    )]
+    pub reward_epoch: Account<'info, RewardEpoch>,
+    
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = EpochContribution::LEN,
+        seeds = [EPOCH_CONTRIBUTION_SEED, reward_epoch.key().as_ref(), owner.key().as_ref()],
+        bump
+    )]
+    pub epoch_contribution: Account<'info, EpochContribution>,
+    
+    /// CHECK: Instructions sysvar, used to inspect the witness device's ed25519 signature
+    #[account(address = INSTRUCTIONS_SYSVAR_ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Record a device-signed receipt from a hotspot that heard the challenged hotspot.
+/// The receipt counts towards verification only if the witness sits within the
+/// challenged hotspot's coverage radius, and only then earns the witness
+/// `POC_WITNESS_REWARD` of WiFi contribution in the current epoch. The holder of the
+/// challenged hotspot can't witness it; holders who split their hotspots across
+/// wallets are not detected.
+pub fn submit_witness_receipt(
+    ctx: Context<SubmitWitnessReceipt>,
+    signal_strength_dbm: i16,
+    nonce: u64,
+    timestamp: i64,
+) -> Result<()> {
+    let poc_challenge = &mut ctx.accounts.poc_challenge;
+    let challenged_hotspot = &ctx.accounts.challenged_hotspot;
+    let witness_hotspot = &mut ctx.accounts.witness_hotspot;
+    let current_time = Clock::get()?.unix_timestamp;
+
+    require!(!poc_challenge.resolved, DePINError::ChallengeAlreadyResolved);
+    require!(current_time <= poc_challenge.expires_at, DePINError::ChallengeExpired);
+    require!(witness_hotspot.is_active, DePINError::AssetInactive);
+    require!(
+        ctx.accounts.owner.key() != ctx.accounts.challenged_nft.owner,
+        DePINError::SelfWitness
+    );
+    require!(
+        !poc_challenge.witnesses.contains(&witness_hotspot.key()),
+        DePINError::DuplicateWitness
+    );
+    require!(
+        poc_challenge.witnesses.len() < PocChallenge::MAX_WITNESSES,
+        DePINError::TooManyWitnesses
+    );
+
+    // Verify the receipt was signed by the witness hotspot's registered device
+    validate_telemetry_freshness(nonce, witness_hotspot.device_nonce, timestamp, current_time)?;
+    let receipt = (poc_challenge.key(), signal_strength_dbm).try_to_vec()?;
+    let message = telemetry_message(&witness_hotspot.key(), nonce, timestamp, &receipt);
+    verify_device_signature(&ctx.accounts.instructions_sysvar, &witness_hotspot.device_pubkey, &message)?;
+    witness_hotspot.device_nonce = nonce;
+
+    let distance_m = hotspot_distance_meters(challenged_hotspot, witness_hotspot);
+    let in_range = distance_m <= challenged_hotspot.coverage_radius as f64;
+
+    poc_challenge.witnesses.push(witness_hotspot.key());
+    let reward = if in_range {
+        poc_challenge.valid_witnesses = poc_challenge.valid_witnesses.safe_add(1)?;
+        POC_WITNESS_REWARD
+    } else {
+        0
+    };
+
+    if reward > 0 {
+        record_epoch_contribution(
+            &mut ctx.accounts.reward_epoch,
+            &mut ctx.accounts.epoch_contribution,
+            ctx.accounts.owner.key(),
+            ctx.bumps.epoch_contribution,
+            Sector::WiFi,
+            reward,
+        )?;
+    }
+
+    msg!("Witness receipt from {} recorded", witness_hotspot.key());
+    msg!("Distance: {} m, Signal: {} dBm, Within coverage: {}", distance_m as u64, signal_strength_dbm, in_range);
+
+    emit_cpi!(WitnessReceiptSubmitted {
+        challenge: poc_challenge.key(),
+        witness: witness_hotspot.key(),
+        distance_m: distance_m as u64,
+        signal_strength_dbm,
+        valid: in_range,
+        reward,
+    });
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ResolvePocChallenge<'info> {
+    #[account(
+        mut,
+        seeds = [POC_CHALLENGE_SEED, wifi_hotspot.key().as_ref(), &poc_challenge.epoch.to_le_bytes()],
+        bump = poc_challenge.bump,
+        has_one = challenger,
+        close = challenger
+    )]
+    pub poc_challenge: Account<'info, PocChallenge>,
+    
+    #[account(
+        mut,
+        seeds = [POC_BOND_SEED, poc_challenge.key().as_ref()],
+        bump
+    )]
+    pub bond_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        address = poc_challenge.hotspot
+    )]
+    pub wifi_hotspot: Account<'info, WiFiHotspot>,
+    
+    #[account(
+        seeds = [NETWORK_STATE_SEED],
+        bump = network_state.bump
+    )]
+    pub network_state: Account<'info, NetworkState>,
+    
+    #[account(
+        mut,
+        seeds = [NETWORK_STATE_SEED, TREASURY_SEED],
+        bump = network_state.treasury_bump
+    )]
+    pub treasury: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        token::mint = network_state.mint,
+        token::authority = poc_challenge.challenger
+    )]
+    pub challenger_token_account: Account<'info, TokenAccount>,
+    
+    /// CHECK: Receives the challenge and bond vault rent; must match `poc_challenge.challenger`
+    #[account(mut)]
+    pub challenger: UncheckedAccount<'info>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+/// Close a challenge's witness window and apply the result to the hotspot's
+/// reputation, which weights its data rewards. A hotspot that fails is only
+/// penalised if at least `MIN_POC_RECEIPTS` hotspots heard it. The bond goes to the
+/// treasury if the hotspot proved its coverage; otherwise the treasury keeps
+/// `POC_CHALLENGE_FEE` and the rest goes back to the challenger. The challenge's rent
+/// is returned to the challenger. Permissionless.
+pub fn resolve_poc_challenge(ctx: Context<ResolvePocChallenge>) -> Result<()> {
+    let current_time = Clock::get()?.unix_timestamp;
+
+    require!(!ctx.accounts.poc_challenge.resolved, DePINError::ChallengeAlreadyResolved);
+    require!(current_time > ctx.accounts.poc_challenge.expires_at, DePINError::ChallengeStillOpen);
+
+    let bond = ctx.accounts.bond_vault.amount;
+    let (verified, penalized, bond_to_treasury) = poc_outcome(
+        ctx.accounts.poc_challenge.valid_witnesses,
+        ctx.accounts.poc_challenge.witnesses.len(),
+        bond,
+    );
+    let bond_refunded = bond.safe_sub(bond_to_treasury)?;
+
+    // Split the bond, then close its vault, signed by the challenge PDA
+    let poc_challenge = &ctx.accounts.poc_challenge;
+    let epoch_bytes = poc_challenge.epoch.to_le_bytes();
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        POC_CHALLENGE_SEED,
+        poc_challenge.hotspot.as_ref(),
+        &epoch_bytes,
+        &[poc_challenge.bump],
+    ]];
+    for (destination, amount) in [
+        (&ctx.accounts.treasury, bond_to_treasury),
+        (&ctx.accounts.challenger_token_account, bond_refunded),
+    ] {
+        if amount > 0 {
+            let cpi_accounts = Transfer {
+                from: ctx.accounts.bond_vault.to_account_info(),
+                to: destination.to_account_info(),
+                authority: poc_challenge.to_account_info(),
+            };
+            let cpi_ctx = CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, signer_seeds);
+            token::transfer(cpi_ctx, amount)?;
+        }
+    }
+
+    let cpi_accounts = CloseAccount {
+        account: ctx.accounts.bond_vault.to_account_info(),
+        destination: ctx.accounts.challenger.to_account_info(),
+        authority: poc_challenge.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, signer_seeds);
+    token::close_account(cpi_ctx)?;
+
+    let poc_challenge = &mut ctx.accounts.poc_challenge;
+    let wifi_hotspot = &mut ctx.accounts.wifi_hotspot;
+    poc_challenge.resolved = true;
+    poc_challenge.verified = verified;
+    wifi_hotspot.open_challenges = wifi_hotspot.open_challenges.safe_sub(1)?;
+
+    if verified {
+        wifi_hotspot.successful_challenges = wifi_hotspot.successful_challenges.safe_add(1)?;
+        wifi_hotspot.reputation_score = std::cmp::min(100, wifi_hotspot.reputation_score.saturating_add(POC_REPUTATION_REWARD));
+    } else if penalized {
+        wifi_hotspot.failed_challenges = wifi_hotspot.failed_challenges.safe_add(1)?;
+        wifi_hotspot.reputation_score = wifi_hotspot.reputation_score.saturating_sub(POC_REPUTATION_PENALTY);
+    }
+
+    msg!("Coverage challenge for hotspot {} resolved", poc_challenge.hotspot);
+    msg!("Valid witnesses: {}/{}, Verified: {}, Penalized: {}, Reputation: {}",
+         poc_challenge.valid_witnesses, poc_challenge.witnesses.len(), verified, penalized, wifi_hotspot.reputation_score);
+
+    emit_cpi!(PocChallengeResolved {
+        challenge: poc_challenge.key(),
+        hotspot: poc_challenge.hotspot,
+        valid_witnesses: poc_challenge.valid_witnesses,
+        verified,
+        penalized,
+        bond_to_treasury,
+        bond_refunded,
+        reputation_score: wifi_hotspot.reputation_score,
+    });
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateHexConfig<'info> {
+    #[account(
+        mut,
+        seeds = [NETWORK_STATE_SEED],
+        bump = network_state.bump,
+        has_one = authority
+    )]
+    pub network_state: Account<'info, NetworkState>,
+    
+    pub authority: Signer<'info>,
+}
+
+/// Change how hotspots are indexed and how many share a hex at full rewards. Applies to
+/// hotspots registered from now on; existing hotspots keep their cell and multiplier.
+pub fn update_hex_config(
+    ctx: Context<UpdateHexConfig>,
+    h3_resolution: u8,
+    hex_density_target: u32,
+) -> Result<()> {
+    require!(h3_resolution <= MAX_H3_RESOLUTION, DePINError::InvalidHexConfig);
+    require!(hex_density_target > 0, DePINError::InvalidHexConfig);
+
+    let network_state = &mut ctx.accounts.network_state;
+    network_state.h3_resolution = h3_resolution;
+    network_state.hex_density_target = hex_density_target;
+
+    msg!("Hotspots are now indexed at H3 resolution {}", h3_resolution);
+    msg!("Full rewards for up to {} hotspots per hex", hex_density_target);
+
+    emit_cpi!(ParamsUpdated::from_network_state(network_state));
+
+    Ok(())
+}
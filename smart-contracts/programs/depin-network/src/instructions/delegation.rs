@@ -0,0 +1,187 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::*;
+use crate::events::*;
+use crate::utils::{write_delegate_checkpoint, write_vote_checkpoint, SafeMath};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct AcceptDelegations<'info> {
+    #[account(
+        init,
+        payer = delegate,
+        space = DelegateRecord::LEN,
+        seeds = [DELEGATE_RECORD_SEED, delegate.key().as_ref()],
+        bump
+    )]
+    pub delegate_record: Account<'info, DelegateRecord>,
+    
+    #[account(mut)]
+    pub delegate: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Opt in to receiving delegated voting power. Users can only delegate to someone
+/// who has done this.
+pub fn accept_delegations(ctx: Context<AcceptDelegations>) -> Result<()> {
+    let delegate_record = &mut ctx.accounts.delegate_record;
+    delegate_record.delegate = ctx.accounts.delegate.key();
+    delegate_record.delegated_power = 0;
+    delegate_record.checkpoints = Vec::new();
+    delegate_record.bump = ctx.bumps.delegate_record;
+
+    msg!("{} now accepts delegated votes", delegate_record.delegate);
+
+    emit_cpi!(DelegationsAccepted {
+        delegate: delegate_record.delegate,
+    });
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct DelegateVotes<'info> {
+    #[account(
+        init,
+        payer = delegator,
+        space = Delegation::LEN,
+        seeds = [DELEGATION_SEED, delegator.key().as_ref()],
+        bump
+    )]
+    pub delegation: Account<'info, Delegation>,
+    
+    #[account(
+        mut,
+        seeds = [USER_PROFILE_SEED, delegator.key().as_ref()],
+        bump = delegator_profile.bump,
+        constraint = delegator_profile.owner == delegator.key()
+    )]
+    pub delegator_profile: Account<'info, UserProfile>,
+    
+    #[account(
+        mut,
+        seeds = [DELEGATE_RECORD_SEED, delegate_record.delegate.as_ref()],
+        bump = delegate_record.bump,
+        constraint = delegate_record.delegate != delegator.key() @ DePINError::InvalidDelegate
+    )]
+    pub delegate_record: Account<'info, DelegateRecord>,
+    
+    #[account(
+        seeds = [NETWORK_STATE_SEED],
+        bump = network_state.bump
+    )]
+    pub network_state: Account<'info, NetworkState>,
+    
+    #[account(mut)]
+    pub delegator: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Let another user vote with all of the delegator's stake, including stake added later.
+/// Delegation is not transitive: a delegate cannot pass delegated stake on. To switch
+/// delegates, undelegate first; a new delegation needs a day since the last change.
+pub fn delegate_votes(ctx: Context<DelegateVotes>) -> Result<()> {
+    let delegation = &mut ctx.accounts.delegation;
+    let delegator_profile = &mut ctx.accounts.delegator_profile;
+    let delegate_record = &mut ctx.accounts.delegate_record;
+
+    let current_time = Clock::get()?.unix_timestamp;
+    let last_proposal_time = ctx.accounts.network_state.last_proposal_time;
+    let amount = delegator_profile.total_staked;
+
+    require!(amount >= MIN_DELEGATION_STAKE, DePINError::InsufficientDelegationStake);
+    require!(
+        current_time.safe_sub(delegator_profile.delegation_changed_at)? >= DELEGATION_COOLDOWN,
+        DePINError::DelegationCooldown
+    );
+
+    delegation.delegator = ctx.accounts.delegator.key();
+    delegation.delegate = delegate_record.delegate;
+    delegation.delegated_at = current_time;
+    delegation.bump = ctx.bumps.delegation;
+
+    delegator_profile.delegate = delegate_record.delegate;
+    delegator_profile.delegation_changed_at = current_time;
+    delegate_record.delegated_power = delegate_record.delegated_power.safe_add(amount)?;
+
+    write_vote_checkpoint(delegator_profile, current_time, last_proposal_time)?;
+    write_delegate_checkpoint(delegate_record, current_time, last_proposal_time)?;
+
+    msg!("Votes delegated to {}", delegation.delegate);
+    msg!("Delegated stake: {} PKN", amount / 10_u64.pow(9));
+
+    emit_cpi!(VotesDelegated {
+        delegator: delegation.delegator,
+        delegate: delegation.delegate,
+        amount,
+    });
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UndelegateVotes<'info> {
+    #[account(
+        mut,
+        seeds = [DELEGATION_SEED, delegator.key().as_ref()],
+        bump = delegation.bump,
+        close = delegator
+    )]
+    pub delegation: Account<'info, Delegation>,
+    
+    #[account(
+        mut,
+        seeds = [USER_PROFILE_SEED, delegator.key().as_ref()],
+        bump = delegator_profile.bump,
+        constraint = delegator_profile.owner == delegator.key()
+    )]
+    pub delegator_profile: Account<'info, UserProfile>,
+    
+    #[account(
+        mut,
+        seeds = [DELEGATE_RECORD_SEED, delegation.delegate.as_ref()],
+        bump = delegate_record.bump
+    )]
+    pub delegate_record: Account<'info, DelegateRecord>,
+    
+    #[account(
+        seeds = [NETWORK_STATE_SEED],
+        bump = network_state.bump
+    )]
+    pub network_state: Account<'info, NetworkState>,
+    
+    #[account(mut)]
+    pub delegator: Signer<'info>,
+}
+
+/// Take voting power back from the delegate. Proposals that opened while the delegation
+/// was in place still count the stake for the delegate.
+pub fn undelegate_votes(ctx: Context<UndelegateVotes>) -> Result<()> {
+    let delegation = &ctx.accounts.delegation;
+    let delegator_profile = &mut ctx.accounts.delegator_profile;
+    let delegate_record = &mut ctx.accounts.delegate_record;
+
+    let current_time = Clock::get()?.unix_timestamp;
+    let last_proposal_time = ctx.accounts.network_state.last_proposal_time;
+    let amount = delegator_profile.total_staked;
+
+    delegator_profile.delegate = Pubkey::default();
+    delegator_profile.delegation_changed_at = current_time;
+    delegate_record.delegated_power = delegate_record.delegated_power.safe_sub(amount)?;
+
+    write_vote_checkpoint(delegator_profile, current_time, last_proposal_time)?;
+    write_delegate_checkpoint(delegate_record, current_time, last_proposal_time)?;
+
+    msg!("Votes undelegated from {}", delegation.delegate);
+
+    emit_cpi!(VotesUndelegated {
+        delegator: delegation.delegator,
+        delegate: delegation.delegate,
+        amount,
+    });
+
+    Ok(())
+}
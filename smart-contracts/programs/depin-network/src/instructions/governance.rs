@@ -1,8 +1,15 @@
 use anchor_lang::prelude::*;
+use anchor_spl::token::{self, CloseAccount, Mint, Token, TokenAccount, Transfer};
 use crate::state::*;
 use crate::constants::*;
 use crate::errors::*;
+use crate::events::*;
+use crate::utils::{
+    delegated_power_at, delegated_stake_at, tally_proposal, transfer_from_proposal_escrow, transfer_from_treasury,
+    update_pool_rewards, validate_voting_rules, voting_power_at, SafeMath,
+};
 
+#[event_cpi]
 #[derive(Accounts)]
 #[instruction(title: String)]
 pub struct CreateProposal<'info> {
@@ -15,6 +22,30 @@ pub struct CreateProposal<'info> {
     )]
     pub proposal: Account<'info, Proposal>,
     
+    // Holds the proposal deposit until the proposal is finalized or cancelled
+    #[account(
+        init,
+        payer = proposer,
+        token::mint = mint,
+        token::authority = proposal,
+        seeds = [PROPOSAL_DEPOSIT_SEED, proposal.key().as_ref()],
+        bump
+    )]
+    pub deposit_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = proposer
+    )]
+    pub proposer_token_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        seeds = [MINT_SEED],
+        bump
+    )]
+    pub mint: Account<'info, Mint>,
+    
     #[account(
         mut,
         seeds = [NETWORK_STATE_SEED],
@@ -25,13 +56,14 @@ pub struct CreateProposal<'info> {
     #[account(
         seeds = [USER_PROFILE_SEED, proposer.key().as_ref()],
         bump = user_profile.bump,
-        has_one = proposer
+        constraint = user_profile.owner == proposer.key()
     )]
     pub user_profile: Account<'info, UserProfile>,
     
     #[account(mut)]
     pub proposer: Signer<'info>,
     pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
 }
 
 pub fn create_proposal(
@@ -39,9 +71,11 @@ pub fn create_proposal(
     title: String,
     description: String,
     proposal_type: ProposalType,
+    action: ProposalAction,
 ) -> Result<()> {
     require!(title.len() <= Proposal::MAX_TITLE_LEN, DePINError::TitleTooLong);
     require!(description.len() <= Proposal::MAX_DESCRIPTION_LEN, DePINError::DescriptionTooLong);
+    require!(action.is_allowed_for(proposal_type), DePINError::InvalidProposalAction);
 
     let network_state = &mut ctx.accounts.network_state;
     let user_profile = &ctx.accounts.user_profile;
@@ -52,6 +86,18 @@ pub fn create_proposal(
         DePINError::InsufficientTokensForProposal
     );
 
+    // Escrow the deposit; it is returned unless the proposal misses quorum
+    let deposit = network_state.proposal_deposit;
+    if deposit > 0 {
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.proposer_token_account.to_account_info(),
+            to: ctx.accounts.deposit_vault.to_account_info(),
+            authority: ctx.accounts.proposer.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+        token::transfer(cpi_ctx, deposit)?;
+    }
+
     let proposal = &mut ctx.accounts.proposal;
     let current_time = Clock::get()?.unix_timestamp;
 
@@ -59,25 +105,44 @@ pub fn create_proposal(
     proposal.proposer = ctx.accounts.proposer.key();
     proposal.title = title.clone();
     proposal.description = description.clone();
-    proposal.proposal_type = proposal_type.clone();
+    proposal.proposal_type = proposal_type;
+    proposal.action = action.clone();
     proposal.yes_votes = 0;
     proposal.no_votes = 0;
+    proposal.abstain_votes = 0;
     proposal.total_votes = 0;
+    proposal.total_staked_snapshot = network_state.total_staked;
+    proposal.quorum_bps = network_state.quorum_bps;
+    proposal.approval_threshold_bps = network_state.approval_threshold_bps[proposal_type as usize];
     proposal.start_time = current_time;
-    proposal.end_time = current_time + (7 * 24 * 3600); // 7 days voting period
-    proposal.executed = false;
-    proposal.approved = false;
+    proposal.end_time = current_time.safe_add(VOTING_PERIOD)?;
+    proposal.status = ProposalStatus::Active;
+    proposal.eta = 0;
+    proposal.expires_at = 0;
+    proposal.deposit = deposit;
     proposal.bump = ctx.bumps.proposal;
 
-    network_state.proposals_count += 1;
+    network_state.proposals_count = network_state.proposals_count.safe_add(1)?;
+    network_state.last_proposal_time = current_time;
 
     msg!("Proposal '{}' created successfully", title);
     msg!("Proposal ID: {}, Type: {:?}", proposal.id, proposal_type);
-    msg!("Voting period: {} days", 7);
+    msg!("Action: {:?}", action);
+    msg!("Voting period: {} days, Deposit: {} PKN", VOTING_PERIOD / SECONDS_PER_DAY, deposit / 10_u64.pow(9));
+
+    emit_cpi!(ProposalCreated {
+        proposal_id: proposal.id,
+        proposer: proposal.proposer,
+        proposal_type,
+        action,
+        start_time: proposal.start_time,
+        end_time: proposal.end_time,
+    });
 
     Ok(())
 }
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct VoteProposal<'info> {
     #[account(
@@ -91,14 +156,21 @@ pub struct VoteProposal<'info> {
         mut,
         seeds = [USER_PROFILE_SEED, voter.key().as_ref()],
         bump = user_profile.bump,
-        has_one = voter
+        constraint = user_profile.owner == voter.key()
     )]
     pub user_profile: Account<'info, UserProfile>,
     
+    // Required to vote with stake delegated to the voter
+    #[account(
+        seeds = [DELEGATE_RECORD_SEED, voter.key().as_ref()],
+        bump = delegate_record.bump
+    )]
+    pub delegate_record: Option<Account<'info, DelegateRecord>>,
+    
     #[account(
         init_if_needed,
         payer = voter,
-        space = 8 + 32 + 8 + 1 + 8 + 1, // UserVote account
+        space = UserVote::LEN,
         seeds = [VOTE_SEED, proposal.key().as_ref(), voter.key().as_ref()],
         bump
     )]
@@ -117,6 +189,11 @@ pub struct UserVote {
     pub voting_power: u64,
     pub timestamp: i64,
     pub bump: u8,
+    pub overridden_power: u64,       // Delegated stake whose owners voted directly
+}
+
+impl UserVote {
+    pub const LEN: usize = 8 + 32 + 32 + 1 + 8 + 8 + 1 + 8;
 }
 
 pub fn vote_proposal(
@@ -135,39 +212,311 @@ pub fn vote_proposal(
         DePINError::VotingPeriodEnded
     );
 
-    require!(!proposal.executed, DePINError::ProposalAlreadyExecuted);
+    require!(proposal.status == ProposalStatus::Active, DePINError::ProposalNotActive);
 
     // Check if user has already voted
     require!(user_vote.voter == Pubkey::default(), DePINError::AlreadyVoted);
 
-    // Voting power based on staked tokens
-    let voting_power = user_profile.total_staked;
+    // Voting power is the stake held before the proposal opened, so tokens
+    // moved or restaked during the vote cannot be counted twice. Delegators
+    // who voted directly have already taken their stake back.
+    let received_power = ctx.accounts.delegate_record.as_ref()
+        .map_or(0, |delegate_record| delegated_power_at(delegate_record, proposal.start_time));
+    let voting_power = voting_power_at(user_profile, proposal.start_time)
+        .safe_add(received_power)?
+        .safe_sub(user_vote.overridden_power)?;
     require!(voting_power > 0, DePINError::NoVotingPower);
 
     // Record the vote
     user_vote.voter = ctx.accounts.voter.key();
     user_vote.proposal = proposal.key();
-    user_vote.vote = vote.clone();
+    user_vote.vote = vote;
     user_vote.voting_power = voting_power;
     user_vote.timestamp = current_time;
     user_vote.bump = ctx.bumps.user_vote;
 
     // Update proposal vote counts
-    match vote {
-        Vote::Yes => proposal.yes_votes += voting_power,
-        Vote::No => proposal.no_votes += voting_power,
-        Vote::Abstain => {} // Abstain votes don't count towards yes/no
-    }
+    add_to_tally(proposal, vote, voting_power)?;
 
-    proposal.total_votes += 1;
-    user_profile.governance_votes += 1;
+    proposal.total_votes = proposal.total_votes.safe_add(1)?;
+    user_profile.governance_votes = user_profile.governance_votes.safe_add(1)?;
 
     msg!("Vote cast successfully on proposal {}", proposal.id);
     msg!("Vote: {:?}, Voting power: {} PKN", vote, voting_power / 10_u64.pow(9));
 
+    emit_cpi!(VoteCast {
+        proposal_id: proposal.id,
+        voter: user_vote.voter,
+        vote,
+        voting_power,
+    });
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct OverrideDelegateVote<'info> {
+    #[account(
+        mut,
+        seeds = [PROPOSAL_SEED, &proposal.id.to_le_bytes()],
+        bump = proposal.bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+    
+    #[account(
+        mut,
+        seeds = [USER_PROFILE_SEED, voter.key().as_ref()],
+        bump = user_profile.bump,
+        constraint = user_profile.owner == voter.key()
+    )]
+    pub user_profile: Account<'info, UserProfile>,
+    
+    // Required to vote with stake delegated to the voter
+    #[account(
+        seeds = [DELEGATE_RECORD_SEED, voter.key().as_ref()],
+        bump = delegate_record.bump
+    )]
+    pub delegate_record: Option<Account<'info, DelegateRecord>>,
+    
+    #[account(
+        seeds = [DELEGATION_SEED, voter.key().as_ref()],
+        bump = delegation.bump
+    )]
+    pub delegation: Account<'info, Delegation>,
+    
+    #[account(
+        init_if_needed,
+        payer = voter,
+        space = UserVote::LEN,
+        seeds = [VOTE_SEED, proposal.key().as_ref(), voter.key().as_ref()],
+        bump
+    )]
+    pub user_vote: Account<'info, UserVote>,
+    
+    // The delegate's vote record, created here if they have not voted yet
+    #[account(
+        init_if_needed,
+        payer = voter,
+        space = UserVote::LEN,
+        seeds = [VOTE_SEED, proposal.key().as_ref(), delegation.delegate.as_ref()],
+        bump
+    )]
+    pub delegate_vote: Account<'info, UserVote>,
+    
+    #[account(mut)]
+    pub voter: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Vote directly with stake delegated to someone else. The stake is taken out of the
+/// delegate's vote on this proposal, whether they have voted already or vote later.
+pub fn override_delegate_vote(
+    ctx: Context<OverrideDelegateVote>,
+    vote: Vote,
+) -> Result<()> {
+    let proposal = &mut ctx.accounts.proposal;
+    let user_profile = &mut ctx.accounts.user_profile;
+    let delegation = &ctx.accounts.delegation;
+    let user_vote = &mut ctx.accounts.user_vote;
+    let delegate_vote = &mut ctx.accounts.delegate_vote;
+
+    let current_time = Clock::get()?.unix_timestamp;
+
+    require!(
+        current_time >= proposal.start_time && current_time <= proposal.end_time,
+        DePINError::VotingPeriodEnded
+    );
+    require!(proposal.status == ProposalStatus::Active, DePINError::ProposalNotActive);
+    require!(user_vote.voter == Pubkey::default(), DePINError::AlreadyVoted);
+
+    // Only a delegation in place since before the proposal opened gave this
+    // stake to the current delegate
+    require!(delegation.delegated_at < proposal.start_time, DePINError::DelegationChanged);
+    let delegated_stake = delegated_stake_at(user_profile, proposal.start_time);
+    require!(delegated_stake > 0, DePINError::NoVotingPower);
+
+    let received_power = ctx.accounts.delegate_record.as_ref()
+        .map_or(0, |delegate_record| delegated_power_at(delegate_record, proposal.start_time));
+    let voting_power = voting_power_at(user_profile, proposal.start_time)
+        .safe_add(delegated_stake)?
+        .safe_add(received_power)?
+        .safe_sub(user_vote.overridden_power)?;
+
+    // Take the stake back from the delegate
+    if delegate_vote.voter != Pubkey::default() {
+        remove_from_tally(proposal, delegate_vote.vote, delegated_stake)?;
+        delegate_vote.voting_power = delegate_vote.voting_power.safe_sub(delegated_stake)?;
+    } else {
+        delegate_vote.proposal = proposal.key();
+        delegate_vote.bump = ctx.bumps.delegate_vote;
+    }
+    delegate_vote.overridden_power = delegate_vote.overridden_power.safe_add(delegated_stake)?;
+
+    // Record the vote
+    user_vote.voter = ctx.accounts.voter.key();
+    user_vote.proposal = proposal.key();
+    user_vote.vote = vote;
+    user_vote.voting_power = voting_power;
+    user_vote.timestamp = current_time;
+    user_vote.bump = ctx.bumps.user_vote;
+
+    add_to_tally(proposal, vote, voting_power)?;
+
+    proposal.total_votes = proposal.total_votes.safe_add(1)?;
+    user_profile.governance_votes = user_profile.governance_votes.safe_add(1)?;
+
+    msg!("Vote cast on proposal {} overriding delegate {}", proposal.id, delegation.delegate);
+    msg!("Vote: {:?}, Voting power: {} PKN", vote, voting_power / 10_u64.pow(9));
+
+    emit_cpi!(DelegateVoteOverridden {
+        proposal_id: proposal.id,
+        delegator: user_vote.voter,
+        delegate: delegation.delegate,
+        voting_power: delegated_stake,
+    });
+
+    emit_cpi!(VoteCast {
+        proposal_id: proposal.id,
+        voter: user_vote.voter,
+        vote,
+        voting_power,
+    });
+
+    Ok(())
+}
+
+/// Count voting power towards a proposal's tally. Abstains count towards quorum only.
+fn add_to_tally(proposal: &mut Proposal, vote: Vote, voting_power: u64) -> Result<()> {
+    match vote {
+        Vote::Yes => proposal.yes_votes = proposal.yes_votes.safe_add(voting_power)?,
+        Vote::No => proposal.no_votes = proposal.no_votes.safe_add(voting_power)?,
+        Vote::Abstain => proposal.abstain_votes = proposal.abstain_votes.safe_add(voting_power)?,
+    }
+    Ok(())
+}
+
+fn remove_from_tally(proposal: &mut Proposal, vote: Vote, voting_power: u64) -> Result<()> {
+    match vote {
+        Vote::Yes => proposal.yes_votes = proposal.yes_votes.safe_sub(voting_power)?,
+        Vote::No => proposal.no_votes = proposal.no_votes.safe_sub(voting_power)?,
+        Vote::Abstain => proposal.abstain_votes = proposal.abstain_votes.safe_sub(voting_power)?,
+    }
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct FinalizeProposal<'info> {
+    #[account(
+        mut,
+        seeds = [PROPOSAL_SEED, &proposal.id.to_le_bytes()],
+        bump = proposal.bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+    
+    #[account(
+        seeds = [NETWORK_STATE_SEED],
+        bump = network_state.bump
+    )]
+    pub network_state: Account<'info, NetworkState>,
+    
+    #[account(
+        mut,
+        seeds = [PROPOSAL_DEPOSIT_SEED, proposal.key().as_ref()],
+        bump
+    )]
+    pub deposit_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [NETWORK_STATE_SEED, TREASURY_SEED],
+        bump = network_state.treasury_bump
+    )]
+    pub treasury: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        token::mint = network_state.mint,
+        token::authority = proposal.proposer
+    )]
+    pub proposer_token_account: Account<'info, TokenAccount>,
+    
+    pub finalizer: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+/// Tally a proposal once voting has closed. An approved proposal is queued behind
+/// the timelock; anything else is defeated. The deposit is refunded if the proposal
+/// reached quorum and slashed to the treasury otherwise. Permissionless.
+pub fn finalize_proposal(ctx: Context<FinalizeProposal>) -> Result<()> {
+    let current_time = Clock::get()?.unix_timestamp;
+
+    // Check if voting period has ended
+    require!(current_time > ctx.accounts.proposal.end_time, DePINError::VotingStillActive);
+    require!(ctx.accounts.proposal.status == ProposalStatus::Active, DePINError::ProposalNotActive);
+
+    // Check quorum and the approval threshold fixed when the proposal was created
+    let (quorum_reached, approved) = tally_proposal(&ctx.accounts.proposal)?;
+
+    let deposit = ctx.accounts.deposit_vault.amount;
+    let deposit_to = if quorum_reached {
+        &ctx.accounts.proposer_token_account
+    } else {
+        &ctx.accounts.treasury
+    };
+    transfer_from_proposal_escrow(
+        &ctx.accounts.proposal,
+        &ctx.accounts.deposit_vault,
+        deposit_to,
+        &ctx.accounts.token_program,
+        deposit,
+    )?;
+
+    let proposal = &mut ctx.accounts.proposal;
+    let network_state = &ctx.accounts.network_state;
+
+    if approved {
+        proposal.status = ProposalStatus::Queued;
+        proposal.eta = current_time.safe_add(network_state.timelock_delay)?;
+        proposal.expires_at = proposal.eta.safe_add(network_state.execution_window)?;
+
+        msg!("Proposal {} approved and queued", proposal.id);
+        msg!("Executable from {} until {}", proposal.eta, proposal.expires_at);
+    } else {
+        proposal.status = ProposalStatus::Defeated;
+
+        if !quorum_reached {
+            msg!("Proposal {} failed to reach quorum; deposit slashed", proposal.id);
+        } else {
+            msg!("Proposal {} rejected by voters", proposal.id);
+        }
+    }
+
+    msg!("Yes votes: {} PKN, No votes: {} PKN, Abstain: {} PKN",
+         proposal.yes_votes / 10_u64.pow(9), proposal.no_votes / 10_u64.pow(9), proposal.abstain_votes / 10_u64.pow(9));
+
+    emit_cpi!(ProposalFinalized {
+        proposal_id: proposal.id,
+        status: proposal.status,
+        quorum_reached,
+        yes_votes: proposal.yes_votes,
+        no_votes: proposal.no_votes,
+        abstain_votes: proposal.abstain_votes,
+        eta: proposal.eta,
+        expires_at: proposal.expires_at,
+    });
+
+    emit_cpi!(ProposalDepositSettled {
+        proposal_id: proposal.id,
+        amount: deposit,
+        slashed: !quorum_reached,
+    });
+
     Ok(())
 }
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct ExecuteProposal<'info> {
     #[account(
@@ -184,61 +533,434 @@ pub struct ExecuteProposal<'info> {
     )]
     pub network_state: Account<'info, NetworkState>,
     
+    // Required by UpdatePoolRewardRate
+    #[account(
+        mut,
+        seeds = [STAKING_POOL_SEED, &[staking_pool.pool_type as u8]],
+        bump = staking_pool.bump
+    )]
+    pub staking_pool: Option<Account<'info, StakingPool>>,
+    
+    // Required by TreasuryTransfer
+    #[account(
+        mut,
+        seeds = [NETWORK_STATE_SEED, TREASURY_SEED],
+        bump = network_state.treasury_bump
+    )]
+    pub treasury: Option<Account<'info, TokenAccount>>,
+    
+    #[account(
+        mut,
+        token::mint = network_state.mint
+    )]
+    pub recipient_token_account: Option<Account<'info, TokenAccount>>,
+    
+    pub token_program: Option<Program<'info, Token>>,
+    
     pub executor: Signer<'info>,
 }
 
 pub fn execute_proposal(ctx: Context<ExecuteProposal>) -> Result<()> {
     let proposal = &mut ctx.accounts.proposal;
-    let network_state = &mut ctx.accounts.network_state;
 
     let current_time = Clock::get()?.unix_timestamp;
 
-    // Check if voting period has ended
-    require!(current_time > proposal.end_time, DePINError::VotingStillActive);
-    require!(!proposal.executed, DePINError::ProposalAlreadyExecuted);
+    require!(proposal.status == ProposalStatus::Queued, DePINError::ProposalNotQueued);
+    require!(current_time >= proposal.eta, DePINError::TimelockNotElapsed);
+    require!(current_time <= proposal.expires_at, DePINError::ProposalExpired);
 
-    // Check if proposal passed (simple majority for now)
-    let total_voted = proposal.yes_votes + proposal.no_votes;
-    require!(total_voted > 0, DePINError::NoVotesCast);
+    proposal.status = ProposalStatus::Executed;
 
-    let approval_threshold = total_voted / 2; // 50% threshold
-    let approved = proposal.yes_votes > approval_threshold;
+    let action = proposal.action.clone();
+    let proposal_id = proposal.id;
 
-    proposal.approved = approved;
-    proposal.executed = true;
+    apply_proposal_action(ctx.accounts, &action, current_time)?;
 
-    if approved {
-        // Execute the proposal based on its type
-        match proposal.proposal_type {
-            ProposalType::ParameterChange => {
-                // Implementation would depend on specific parameter changes
-                msg!("Parameter change proposal approved and executed");
-            }
-            ProposalType::TreasurySpend => {
-                // Implementation for treasury spending
-                msg!("Treasury spend proposal approved");
-            }
-            ProposalType::ProtocolUpgrade => {
-                // Implementation for protocol upgrades
-                msg!("Protocol upgrade proposal approved");
-            }
-            ProposalType::RewardRateChange => {
-                // Implementation for reward rate changes
-                msg!("Reward rate change proposal approved");
-            }
-            ProposalType::NetworkExpansion => {
-                // Implementation for network expansion
-                msg!("Network expansion proposal approved");
-            }
+    msg!("Proposal {} executed successfully", proposal_id);
+
+    emit_cpi!(ProposalExecuted {
+        proposal_id,
+        action: action.clone(),
+    });
+
+    if action.updates_network_params() {
+        emit_cpi!(ParamsUpdated::from_network_state(&ctx.accounts.network_state));
+    }
+
+    Ok(())
+}
+
+/// Apply an approved proposal's action to network state, a staking pool or the treasury
+fn apply_proposal_action(
+    accounts: &mut ExecuteProposal,
+    action: &ProposalAction,
+    current_time: i64,
+) -> Result<()> {
+    match action {
+        ProposalAction::Signal => {
+            msg!("Signalling proposal approved; no on-chain action");
         }
+        ProposalAction::UpdateRewardRates {
+            wifi_reward_rate,
+            logistics_reward_rate,
+            agriculture_reward_rate,
+            healthcare_reward_rate,
+            taxation_reward_rate,
+            power_reward_rate,
+        } => {
+            let rates = [
+                *wifi_reward_rate,
+                *logistics_reward_rate,
+                *agriculture_reward_rate,
+                *healthcare_reward_rate,
+                *taxation_reward_rate,
+                *power_reward_rate,
+            ];
+            require!(rates.iter().all(|rate| *rate > 0), DePINError::InvalidRewardRate);
+
+            let network_state = &mut accounts.network_state;
+            network_state.wifi_reward_rate = *wifi_reward_rate;
+            network_state.logistics_reward_rate = *logistics_reward_rate;
+            network_state.agriculture_reward_rate = *agriculture_reward_rate;
+            network_state.healthcare_reward_rate = *healthcare_reward_rate;
+            network_state.taxation_reward_rate = *taxation_reward_rate;
+            network_state.power_reward_rate = *power_reward_rate;
+
+            msg!("Reward rates updated by governance");
+        }
+        ProposalAction::UpdateGovernanceThreshold { governance_threshold } => {
+            accounts.network_state.governance_threshold = *governance_threshold;
 
-        msg!("Proposal {} executed successfully", proposal.id);
-    } else {
-        msg!("Proposal {} rejected by voters", proposal.id);
+            msg!("Governance threshold set to {} PKN", governance_threshold / 10_u64.pow(9));
+        }
+        ProposalAction::UpdatePoolRewardRate { pool_type, reward_rate } => {
+            let staking_pool = accounts
+                .staking_pool
+                .as_mut()
+                .ok_or(DePINError::InvalidProposalAccounts)?;
+            require!(staking_pool.pool_type == *pool_type, DePINError::InvalidProposalAccounts);
+
+            // Accrue at the old rate up to now before the new rate takes effect
+            update_pool_rewards(staking_pool, current_time)?;
+            staking_pool.reward_rate = *reward_rate;
+
+            msg!("{:?} pool APY set to {} bps", pool_type, reward_rate);
+        }
+        ProposalAction::TreasuryTransfer { recipient, amount } => {
+            let treasury = accounts.treasury.as_ref().ok_or(DePINError::InvalidProposalAccounts)?;
+            let recipient_token_account = accounts
+                .recipient_token_account
+                .as_ref()
+                .ok_or(DePINError::InvalidProposalAccounts)?;
+            let token_program = accounts.token_program.as_ref().ok_or(DePINError::InvalidProposalAccounts)?;
+            require!(recipient_token_account.key() == *recipient, DePINError::InvalidProposalAccounts);
+
+            transfer_from_treasury(treasury, recipient_token_account, &accounts.network_state, token_program, *amount)?;
+
+            msg!("Transferred {} PKN from the treasury to {}", amount / 10_u64.pow(9), recipient);
+        }
+        ProposalAction::TransferAuthority { new_authority } => {
+            accounts.network_state.authority = *new_authority;
+
+            msg!("Network authority transferred to {}", new_authority);
+        }
+        ProposalAction::UpdateVotingRules { quorum_bps, approval_threshold_bps } => {
+            require!(validate_voting_rules(*quorum_bps, approval_threshold_bps), DePINError::InvalidVotingRules);
+
+            accounts.network_state.quorum_bps = *quorum_bps;
+            accounts.network_state.approval_threshold_bps = *approval_threshold_bps;
+
+            msg!("Voting rules updated: quorum {} bps, thresholds {:?} bps", quorum_bps, approval_threshold_bps);
+        }
+    }
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateVotingRules<'info> {
+    #[account(
+        mut,
+        seeds = [NETWORK_STATE_SEED],
+        bump = network_state.bump,
+        has_one = authority
+    )]
+    pub network_state: Account<'info, NetworkState>,
+    
+    pub authority: Signer<'info>,
+}
+
+pub fn update_voting_rules(
+    ctx: Context<UpdateVotingRules>,
+    quorum_bps: u16,
+    approval_threshold_bps: [u16; PROPOSAL_TYPE_COUNT],
+) -> Result<()> {
+    require!(validate_voting_rules(quorum_bps, &approval_threshold_bps), DePINError::InvalidVotingRules);
+
+    let network_state = &mut ctx.accounts.network_state;
+    network_state.quorum_bps = quorum_bps;
+    network_state.approval_threshold_bps = approval_threshold_bps;
+
+    msg!("Voting rules updated: quorum {} bps of total stake", quorum_bps);
+    msg!("Approval thresholds (bps): {:?}", approval_threshold_bps);
+
+    emit_cpi!(ParamsUpdated::from_network_state(network_state));
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct VetoProposal<'info> {
+    #[account(
+        mut,
+        seeds = [PROPOSAL_SEED, &proposal.id.to_le_bytes()],
+        bump = proposal.bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+    
+    #[account(
+        seeds = [NETWORK_STATE_SEED],
+        bump = network_state.bump,
+        has_one = security_council @ DePINError::Unauthorized
+    )]
+    pub network_state: Account<'info, NetworkState>,
+    
+    pub security_council: Signer<'info>,
+}
+
+/// Cancel an approved proposal while its timelock is running. Once `eta` passes the
+/// proposal is executable and can no longer be vetoed.
+pub fn veto_proposal(ctx: Context<VetoProposal>) -> Result<()> {
+    let proposal = &mut ctx.accounts.proposal;
+    let current_time = Clock::get()?.unix_timestamp;
+
+    require!(proposal.status == ProposalStatus::Queued, DePINError::ProposalNotQueued);
+    require!(current_time < proposal.eta, DePINError::TimelockElapsed);
+
+    proposal.status = ProposalStatus::Vetoed;
+
+    msg!("Proposal {} vetoed by the security council", proposal.id);
+
+    emit_cpi!(ProposalVetoed {
+        proposal_id: proposal.id,
+        security_council: ctx.accounts.security_council.key(),
+    });
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateTimelockConfig<'info> {
+    #[account(
+        mut,
+        seeds = [NETWORK_STATE_SEED],
+        bump = network_state.bump,
+        has_one = authority
+    )]
+    pub network_state: Account<'info, NetworkState>,
+    
+    pub authority: Signer<'info>,
+}
+
+/// Set the timelock delay, the execution window and the security council. Applies to
+/// proposals finalized from now on; already-queued proposals keep their eta and expiry.
+pub fn update_timelock_config(
+    ctx: Context<UpdateTimelockConfig>,
+    timelock_delay: i64,
+    execution_window: i64,
+    security_council: Pubkey,
+) -> Result<()> {
+    require!(
+        (MIN_TIMELOCK_DELAY..=MAX_TIMELOCK_DELAY).contains(&timelock_delay),
+        DePINError::InvalidTimelockConfig
+    );
+    require!(execution_window > 0, DePINError::InvalidTimelockConfig);
+
+    let network_state = &mut ctx.accounts.network_state;
+    network_state.timelock_delay = timelock_delay;
+    network_state.execution_window = execution_window;
+    network_state.security_council = security_council;
+
+    msg!("Timelock delay: {} s, Execution window: {} s", timelock_delay, execution_window);
+    msg!("Security council: {}", security_council);
+
+    emit_cpi!(ParamsUpdated::from_network_state(network_state));
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CancelProposal<'info> {
+    #[account(
+        mut,
+        seeds = [PROPOSAL_SEED, &proposal.id.to_le_bytes()],
+        bump = proposal.bump,
+        has_one = proposer
+    )]
+    pub proposal: Account<'info, Proposal>,
+    
+    #[account(
+        mut,
+        seeds = [PROPOSAL_DEPOSIT_SEED, proposal.key().as_ref()],
+        bump
+    )]
+    pub deposit_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        token::mint = deposit_vault.mint,
+        token::authority = proposer
+    )]
+    pub proposer_token_account: Account<'info, TokenAccount>,
+    
+    pub proposer: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+/// Withdraw a proposal before anyone has voted on it, refunding the deposit
+pub fn cancel_proposal(ctx: Context<CancelProposal>) -> Result<()> {
+    let current_time = Clock::get()?.unix_timestamp;
+
+    let proposal = &ctx.accounts.proposal;
+    require!(proposal.status == ProposalStatus::Active, DePINError::ProposalNotActive);
+    require!(current_time <= proposal.end_time, DePINError::VotingPeriodEnded);
+    require!(proposal.total_votes == 0, DePINError::ProposalHasVotes);
+
+    let deposit = ctx.accounts.deposit_vault.amount;
+    transfer_from_proposal_escrow(
+        proposal,
+        &ctx.accounts.deposit_vault,
+        &ctx.accounts.proposer_token_account,
+        &ctx.accounts.token_program,
+        deposit,
+    )?;
+
+    let proposal = &mut ctx.accounts.proposal;
+    proposal.status = ProposalStatus::Cancelled;
+
+    msg!("Proposal {} cancelled by its proposer", proposal.id);
+    msg!("Deposit refunded: {} PKN", deposit / 10_u64.pow(9));
+
+    emit_cpi!(ProposalCancelled {
+        proposal_id: proposal.id,
+        deposit_refunded: deposit,
+    });
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CloseProposal<'info> {
+    #[account(
+        mut,
+        seeds = [PROPOSAL_SEED, &proposal.id.to_le_bytes()],
+        bump = proposal.bump,
+        has_one = proposer,
+        close = proposer
+    )]
+    pub proposal: Account<'info, Proposal>,
+    
+    #[account(
+        mut,
+        seeds = [PROPOSAL_DEPOSIT_SEED, proposal.key().as_ref()],
+        bump
+    )]
+    pub deposit_vault: Account<'info, TokenAccount>,
+    
+    /// CHECK: Receives the rent; must match `proposal.proposer`
+    #[account(mut)]
+    pub proposer: UncheckedAccount<'info>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+/// Close a proposal whose outcome is final and its empty deposit escrow, returning the
+/// rent to the proposer. Permissionless.
+pub fn close_proposal(ctx: Context<CloseProposal>) -> Result<()> {
+    let proposal = &ctx.accounts.proposal;
+    let current_time = Clock::get()?.unix_timestamp;
+
+    require!(proposal.is_final(current_time), DePINError::ProposalNotFinal);
+
+    let id_bytes = proposal.id.to_le_bytes();
+    let signer_seeds: &[&[&[u8]]] = &[&[PROPOSAL_SEED, &id_bytes, &[proposal.bump]]];
+    let cpi_accounts = CloseAccount {
+        account: ctx.accounts.deposit_vault.to_account_info(),
+        destination: ctx.accounts.proposer.to_account_info(),
+        authority: proposal.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, signer_seeds);
+    token::close_account(cpi_ctx)?;
+
+    msg!("Proposal {} closed", proposal.id);
+
+    emit_cpi!(ProposalClosed {
+        proposal_id: proposal.id,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CloseVoteRecord<'info> {
+    #[account(
+        mut,
+        seeds = [VOTE_SEED, user_vote.proposal.as_ref(), voter.key().as_ref()],
+        bump = user_vote.bump,
+        close = voter
+    )]
+    pub user_vote: Account<'info, UserVote>,
+    
+    /// CHECK: The voted-on proposal; may already have been closed
+    #[account(address = user_vote.proposal)]
+    pub proposal: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub voter: Signer<'info>,
+}
+
+/// Reclaim the rent of a vote record once its proposal is final or closed
+pub fn close_vote_record(ctx: Context<CloseVoteRecord>) -> Result<()> {
+    let proposal_info = ctx.accounts.proposal.to_account_info();
+
+    if proposal_info.owner == &crate::ID && !proposal_info.data_is_empty() {
+        let proposal = Proposal::try_deserialize(&mut &proposal_info.try_borrow_data()?[..])?;
+        let current_time = Clock::get()?.unix_timestamp;
+        require!(proposal.is_final(current_time), DePINError::ProposalNotFinal);
     }
 
-    msg!("Yes votes: {} PKN, No votes: {} PKN", 
-         proposal.yes_votes / 10_u64.pow(9), proposal.no_votes / 10_u64.pow(9));
+    msg!("Vote record for proposal {} closed", ctx.accounts.user_vote.proposal);
 
     Ok(())
-}
\ No newline at end of file
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateProposalDeposit<'info> {
+    #[account(
+        mut,
+        seeds = [NETWORK_STATE_SEED],
+        bump = network_state.bump,
+        has_one = authority
+    )]
+    pub network_state: Account<'info, NetworkState>,
+    
+    pub authority: Signer<'info>,
+}
+
+/// Set the deposit escrowed by new proposals. Existing proposals keep their deposit.
+pub fn update_proposal_deposit(ctx: Context<UpdateProposalDeposit>, proposal_deposit: u64) -> Result<()> {
+    let network_state = &mut ctx.accounts.network_state;
+    network_state.proposal_deposit = proposal_deposit;
+
+    msg!("Proposal deposit set to {} PKN", proposal_deposit / 10_u64.pow(9));
+
+    emit_cpi!(ParamsUpdated::from_network_state(network_state));
+
+    Ok(())
+}
@@ -1,17 +1,27 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions::ID as INSTRUCTIONS_SYSVAR_ID;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::metadata::Metadata;
+use anchor_spl::token::{Mint, Token, TokenAccount};
 use crate::state::*;
 use crate::constants::*;
 use crate::errors::*;
+use crate::events::*;
+use crate::utils::{
+    calculate_reward, init_user_profile_if_needed, mint_asset_nft, record_epoch_contribution, telemetry_message,
+    validate_micro_degree_coordinates, validate_telemetry_freshness, verify_device_signature,
+    MintAssetNft, SafeMath,
+};
 
 // Healthcare Provider Registration
+#[event_cpi]
 #[derive(Accounts)]
-#[instruction(provider_name: String)]
 pub struct RegisterHealthcareProvider<'info> {
     #[account(
         init,
         payer = owner,
         space = HealthcareProvider::LEN,
-        seeds = [b"healthcare_provider", owner.key().as_ref()],
+        seeds = [HEALTHCARE_PROVIDER_SEED, &network_state.next_asset_id.to_le_bytes()],
         bump
     )]
     pub healthcare_provider: Account<'info, HealthcareProvider>,
@@ -35,29 +45,84 @@ pub struct RegisterHealthcareProvider<'info> {
     #[account(mut)]
     pub owner: Signer<'info>,
     
+    #[account(
+        init,
+        payer = owner,
+        seeds = [ASSET_MINT_SEED, healthcare_provider.key().as_ref()],
+        bump,
+        mint::decimals = 0,
+        mint::authority = network_state,
+        mint::freeze_authority = network_state
+    )]
+    pub nft_mint: Box<Account<'info, Mint>>,
+    
+    #[account(
+        init,
+        payer = owner,
+        associated_token::mint = nft_mint,
+        associated_token::authority = owner
+    )]
+    pub owner_nft: Box<Account<'info, TokenAccount>>,
+    
+    /// CHECK: Metadata PDA, created by the token metadata program
+    #[account(
+        mut,
+        seeds = [METADATA_SEED, token_metadata_program.key().as_ref(), nft_mint.key().as_ref()],
+        bump,
+        seeds::program = token_metadata_program.key()
+    )]
+    pub nft_metadata: UncheckedAccount<'info>,
+    
+    /// CHECK: Master edition PDA, created by the token metadata program
+    #[account(
+        mut,
+        seeds = [METADATA_SEED, token_metadata_program.key().as_ref(), nft_mint.key().as_ref(), EDITION_SEED],
+        bump,
+        seeds::program = token_metadata_program.key()
+    )]
+    pub nft_master_edition: UncheckedAccount<'info>,
+    
     pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_metadata_program: Program<'info, Metadata>,
+    pub rent: Sysvar<'info, Rent>,
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn register_healthcare_provider(
     ctx: Context<RegisterHealthcareProvider>,
+    device_pubkey: Pubkey,
     provider_name: String,
     provider_type: String,
     location: String,
+    latitude: i32,
+    longitude: i32,
     license_number: String,
+    metadata_uri: String,
 ) -> Result<()> {
+    require!(ctx.accounts.user_profile.healthcare_providers < MAX_HEALTHCARE_PROVIDERS_PER_USER, DePINError::TooManyAssets);
     require!(provider_name.len() <= HealthcareProvider::MAX_NAME_LEN, DePINError::NameTooLong);
     require!(provider_type.len() <= HealthcareProvider::MAX_TYPE_LEN, DePINError::NameTooLong);
     require!(location.len() <= HealthcareProvider::MAX_LOCATION_LEN, DePINError::LocationTooLong);
+    require!(validate_micro_degree_coordinates(latitude, longitude), DePINError::InvalidCoordinates);
     require!(license_number.len() <= HealthcareProvider::MAX_LICENSE_LEN, DePINError::NameTooLong);
+    require!(metadata_uri.len() <= MAX_NFT_URI_LEN, DePINError::UriTooLong);
 
     let healthcare_provider = &mut ctx.accounts.healthcare_provider;
     let network_state = &mut ctx.accounts.network_state;
     let user_profile = &mut ctx.accounts.user_profile;
 
     healthcare_provider.owner = ctx.accounts.owner.key();
+    healthcare_provider.asset_id = network_state.next_asset_id;
+    healthcare_provider.nft_mint = ctx.accounts.nft_mint.key();
+    healthcare_provider.device_pubkey = device_pubkey;
+    healthcare_provider.device_nonce = 0;
     healthcare_provider.provider_name = provider_name.clone();
     healthcare_provider.provider_type = provider_type.clone();
     healthcare_provider.location = location.clone();
+    healthcare_provider.latitude = latitude;
+    healthcare_provider.longitude = longitude;
     healthcare_provider.license_number = license_number.clone();
     healthcare_provider.total_data_submissions = 0;
     healthcare_provider.total_records_collected = 0;
@@ -67,39 +132,54 @@ pub fn register_healthcare_provider(
     healthcare_provider.compliance_score = 100;
     healthcare_provider.bump = ctx.bumps.healthcare_provider;
 
-    network_state.healthcare_providers_count += 1;
-
-    // Initialize user profile if needed
-    if user_profile.owner == Pubkey::default() {
-        user_profile.owner = ctx.accounts.owner.key();
-        user_profile.total_earned = 0;
-        user_profile.total_staked = 0;
-        user_profile.reputation_score = 100;
-        user_profile.last_activity = Clock::get()?.unix_timestamp;
-        user_profile.wifi_hotspots = 0;
-        user_profile.logistics_partners = 0;
-        user_profile.farms = 0;
-        user_profile.governance_votes = 0;
-        user_profile.bump = ctx.bumps.user_profile;
-    } else {
-        user_profile.last_activity = Clock::get()?.unix_timestamp;
-    }
-
-    msg!(\"Healthcare provider '{}' registered successfully\", provider_name);
-    msg!(\"Type: {}, License: {}\", provider_type, license_number);
+    network_state.healthcare_providers_count = network_state.healthcare_providers_count.safe_add(1)?;
+    network_state.next_asset_id = network_state.next_asset_id.safe_add(1)?;
+
+    init_user_profile_if_needed(user_profile, ctx.accounts.owner.key(), ctx.bumps.user_profile)?;
+    user_profile.healthcare_providers = user_profile.healthcare_providers.safe_add(1)?;
+
+    mint_asset_nft(
+        MintAssetNft {
+            network_state: &ctx.accounts.network_state,
+            nft_mint: &ctx.accounts.nft_mint,
+            owner_nft: &ctx.accounts.owner_nft,
+            nft_metadata: &ctx.accounts.nft_metadata,
+            nft_master_edition: &ctx.accounts.nft_master_edition,
+            payer: &ctx.accounts.owner,
+            token_program: &ctx.accounts.token_program,
+            token_metadata_program: &ctx.accounts.token_metadata_program,
+            system_program: &ctx.accounts.system_program,
+            rent: &ctx.accounts.rent,
+        },
+        format!("PKN Healthcare #{}", healthcare_provider.asset_id),
+        metadata_uri,
+    )?;
+
+    msg!("Healthcare provider '{}' registered successfully", provider_name);
+    msg!("Type: {}, License: {}", provider_type, license_number);
+    msg!("Location: {} ({}, {}) micro-degrees", location, latitude, longitude);
+
+    emit_cpi!(InfrastructureRegistered {
+        owner: healthcare_provider.owner,
+        asset: healthcare_provider.key(),
+        sector: Sector::Healthcare,
+        asset_id: healthcare_provider.asset_id,
+        nft_mint: healthcare_provider.nft_mint,
+        device_pubkey,
+    });
 
     Ok(())
 }
 
 // Tax Collection Point Registration
+#[event_cpi]
 #[derive(Accounts)]
-#[instruction(point_name: String)]
 pub struct RegisterTaxPoint<'info> {
     #[account(
         init,
         payer = owner,
         space = TaxCollectionPoint::LEN,
-        seeds = [b"tax_point", owner.key().as_ref()],
+        seeds = [TAX_POINT_SEED, &network_state.next_asset_id.to_le_bytes()],
         bump
     )]
     pub tax_point: Account<'info, TaxCollectionPoint>,
@@ -123,29 +203,84 @@ pub struct RegisterTaxPoint<'info> {
     #[account(mut)]
     pub owner: Signer<'info>,
     
+    #[account(
+        init,
+        payer = owner,
+        seeds = [ASSET_MINT_SEED, tax_point.key().as_ref()],
+        bump,
+        mint::decimals = 0,
+        mint::authority = network_state,
+        mint::freeze_authority = network_state
+    )]
+    pub nft_mint: Box<Account<'info, Mint>>,
+    
+    #[account(
+        init,
+        payer = owner,
+        associated_token::mint = nft_mint,
+        associated_token::authority = owner
+    )]
+    pub owner_nft: Box<Account<'info, TokenAccount>>,
+    
+    /// CHECK: Metadata PDA, created by the token metadata program
+    #[account(
+        mut,
+        seeds = [METADATA_SEED, token_metadata_program.key().as_ref(), nft_mint.key().as_ref()],
+        bump,
+        seeds::program = token_metadata_program.key()
+    )]
+    pub nft_metadata: UncheckedAccount<'info>,
+    
+    /// CHECK: Master edition PDA, created by the token metadata program
+    #[account(
+        mut,
+        seeds = [METADATA_SEED, token_metadata_program.key().as_ref(), nft_mint.key().as_ref(), EDITION_SEED],
+        bump,
+        seeds::program = token_metadata_program.key()
+    )]
+    pub nft_master_edition: UncheckedAccount<'info>,
+    
     pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_metadata_program: Program<'info, Metadata>,
+    pub rent: Sysvar<'info, Rent>,
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn register_tax_point(
     ctx: Context<RegisterTaxPoint>,
+    device_pubkey: Pubkey,
     point_name: String,
     authority_type: String,
     location: String,
+    latitude: i32,
+    longitude: i32,
     jurisdiction: String,
+    metadata_uri: String,
 ) -> Result<()> {
+    require!(ctx.accounts.user_profile.tax_points < MAX_TAX_POINTS_PER_USER, DePINError::TooManyAssets);
     require!(point_name.len() <= TaxCollectionPoint::MAX_NAME_LEN, DePINError::NameTooLong);
     require!(authority_type.len() <= TaxCollectionPoint::MAX_TYPE_LEN, DePINError::NameTooLong);
     require!(location.len() <= TaxCollectionPoint::MAX_LOCATION_LEN, DePINError::LocationTooLong);
+    require!(validate_micro_degree_coordinates(latitude, longitude), DePINError::InvalidCoordinates);
     require!(jurisdiction.len() <= TaxCollectionPoint::MAX_JURISDICTION_LEN, DePINError::NameTooLong);
+    require!(metadata_uri.len() <= MAX_NFT_URI_LEN, DePINError::UriTooLong);
 
     let tax_point = &mut ctx.accounts.tax_point;
     let network_state = &mut ctx.accounts.network_state;
     let user_profile = &mut ctx.accounts.user_profile;
 
     tax_point.owner = ctx.accounts.owner.key();
+    tax_point.asset_id = network_state.next_asset_id;
+    tax_point.nft_mint = ctx.accounts.nft_mint.key();
+    tax_point.device_pubkey = device_pubkey;
+    tax_point.device_nonce = 0;
     tax_point.point_name = point_name.clone();
     tax_point.authority_type = authority_type.clone();
     tax_point.location = location.clone();
+    tax_point.latitude = latitude;
+    tax_point.longitude = longitude;
     tax_point.jurisdiction = jurisdiction.clone();
     tax_point.total_records_submitted = 0;
     tax_point.total_amount_processed = 0;
@@ -155,46 +290,69 @@ pub fn register_tax_point(
     tax_point.verification_score = 100;
     tax_point.bump = ctx.bumps.tax_point;
 
-    network_state.tax_points_count += 1;
-
-    // Initialize user profile if needed
-    if user_profile.owner == Pubkey::default() {
-        user_profile.owner = ctx.accounts.owner.key();
-        user_profile.total_earned = 0;
-        user_profile.total_staked = 0;
-        user_profile.reputation_score = 100;
-        user_profile.last_activity = Clock::get()?.unix_timestamp;
-        user_profile.wifi_hotspots = 0;
-        user_profile.logistics_partners = 0;
-        user_profile.farms = 0;
-        user_profile.governance_votes = 0;
-        user_profile.bump = ctx.bumps.user_profile;
-    } else {
-        user_profile.last_activity = Clock::get()?.unix_timestamp;
-    }
-
-    msg!(\"Tax collection point '{}' registered successfully\", point_name);
-    msg!(\"Authority: {}, Jurisdiction: {}\", authority_type, jurisdiction);
+    network_state.tax_points_count = network_state.tax_points_count.safe_add(1)?;
+    network_state.next_asset_id = network_state.next_asset_id.safe_add(1)?;
+
+    init_user_profile_if_needed(user_profile, ctx.accounts.owner.key(), ctx.bumps.user_profile)?;
+    user_profile.tax_points = user_profile.tax_points.safe_add(1)?;
+
+    mint_asset_nft(
+        MintAssetNft {
+            network_state: &ctx.accounts.network_state,
+            nft_mint: &ctx.accounts.nft_mint,
+            owner_nft: &ctx.accounts.owner_nft,
+            nft_metadata: &ctx.accounts.nft_metadata,
+            nft_master_edition: &ctx.accounts.nft_master_edition,
+            payer: &ctx.accounts.owner,
+            token_program: &ctx.accounts.token_program,
+            token_metadata_program: &ctx.accounts.token_metadata_program,
+            system_program: &ctx.accounts.system_program,
+            rent: &ctx.accounts.rent,
+        },
+        format!("PKN Tax Point #{}", tax_point.asset_id),
+        metadata_uri,
+    )?;
+
+    msg!("Tax collection point '{}' registered successfully", point_name);
+    msg!("Authority: {}, Jurisdiction: {}", authority_type, jurisdiction);
+    msg!("Location: {} ({}, {}) micro-degrees", location, latitude, longitude);
+
+    emit_cpi!(InfrastructureRegistered {
+        owner: tax_point.owner,
+        asset: tax_point.key(),
+        sector: Sector::Taxation,
+        asset_id: tax_point.asset_id,
+        nft_mint: tax_point.nft_mint,
+        device_pubkey,
+    });
 
     Ok(())
 }
 
 // Healthcare Data Submission
+#[event_cpi]
 #[derive(Accounts)]
 pub struct SubmitHealthcareData<'info> {
     #[account(
         mut,
-        seeds = [b"healthcare_provider", owner.key().as_ref()],
-        bump = healthcare_provider.bump,
-        has_one = owner
+        seeds = [HEALTHCARE_PROVIDER_SEED, &healthcare_provider.asset_id.to_le_bytes()],
+        bump = healthcare_provider.bump
     )]
     pub healthcare_provider: Account<'info, HealthcareProvider>,
     
     #[account(
-        mut,
+        token::mint = healthcare_provider.nft_mint,
+        token::authority = owner,
+        constraint = owner_nft.amount == 1 @ DePINError::NotAssetHolder
+    )]
+    pub owner_nft: Account<'info, TokenAccount>,
+    
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = UserProfile::LEN,
         seeds = [USER_PROFILE_SEED, owner.key().as_ref()],
-        bump = user_profile.bump,
-        has_one = owner
+        bump
     )]
     pub user_profile: Account<'info, UserProfile>,
     
@@ -204,73 +362,131 @@ pub struct SubmitHealthcareData<'info> {
     )]
     pub network_state: Account<'info, NetworkState>,
     
+    #[account(
+        mut,
+        seeds = [EPOCH_SEED, &network_state.current_epoch.to_le_bytes()],
+        bump = reward_epoch.bump
+    )]
+    pub reward_epoch: Account<'info, RewardEpoch>,
+    
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = EpochContribution::LEN,
+        seeds = [EPOCH_CONTRIBUTION_SEED, reward_epoch.key().as_ref(), owner.key().as_ref()],
+        bump
+    )]
+    pub epoch_contribution: Account<'info, EpochContribution>,
+    
+    /// CHECK: Instructions sysvar, used to inspect the device's ed25519 signature
+    #[account(address = INSTRUCTIONS_SYSVAR_ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+    
+    #[account(mut)]
     pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
 }
 
 pub fn submit_healthcare_data(
     ctx: Context<SubmitHealthcareData>,
     records_count: u32,
     data_quality_score: u8,
+    nonce: u64,
+    timestamp: i64,
 ) -> Result<()> {
     require!(data_quality_score <= 100, DePINError::InvalidScore);
     require!(records_count > 0, DePINError::NoDataTransferred);
 
     let healthcare_provider = &mut ctx.accounts.healthcare_provider;
+    require!(healthcare_provider.is_active, DePINError::AssetInactive);
     let user_profile = &mut ctx.accounts.user_profile;
     let network_state = &ctx.accounts.network_state;
 
     // Check minimum time between submissions (1 hour)
     let current_time = Clock::get()?.unix_timestamp;
     require!(
-        current_time - healthcare_provider.last_data_submission >= 3600,
+        current_time.safe_sub(healthcare_provider.last_data_submission)? >= 3600,
         DePINError::SubmissionTooFrequent
     );
 
+    // Verify the report was signed by the provider's registered device
+    validate_telemetry_freshness(nonce, healthcare_provider.device_nonce, timestamp, current_time)?;
+    let metrics = (records_count, data_quality_score).try_to_vec()?;
+    let message = telemetry_message(&healthcare_provider.key(), nonce, timestamp, &metrics);
+    verify_device_signature(&ctx.accounts.instructions_sysvar, &healthcare_provider.device_pubkey, &message)?;
+    healthcare_provider.device_nonce = nonce;
+
     // Calculate rewards based on records and quality
-    let base_reward = records_count as u64 * network_state.healthcare_reward_rate;
     let quality_multiplier = data_quality_score as u64;
-    let reward_amount = (base_reward * quality_multiplier) / 100;
+    let reward_amount = calculate_reward(records_count as u64, network_state.healthcare_reward_rate, quality_multiplier, 100)?;
 
     // Update healthcare provider data
-    healthcare_provider.total_data_submissions += 1;
-    healthcare_provider.total_records_collected += records_count;
-    healthcare_provider.total_rewards_earned += reward_amount;
+    healthcare_provider.total_data_submissions = healthcare_provider.total_data_submissions.safe_add(1)?;
+    healthcare_provider.total_records_collected = healthcare_provider.total_records_collected.safe_add(records_count)?;
+    healthcare_provider.total_rewards_earned = healthcare_provider.total_rewards_earned.safe_add(reward_amount)?;
     healthcare_provider.last_data_submission = current_time;
 
     // Update compliance score based on quality
     if data_quality_score >= 95 {
-        healthcare_provider.compliance_score = std::cmp::min(100, healthcare_provider.compliance_score + 1);
+        healthcare_provider.compliance_score = std::cmp::min(100, healthcare_provider.compliance_score.saturating_add(1));
     } else if data_quality_score < 80 {
-        healthcare_provider.compliance_score = std::cmp::max(0, healthcare_provider.compliance_score - 1);
+        healthcare_provider.compliance_score = healthcare_provider.compliance_score.saturating_sub(1);
     }
 
-    // Update user profile
-    user_profile.total_earned += reward_amount;
-    user_profile.last_activity = current_time;
-
-    msg!(\"Healthcare data submitted successfully!\");
-    msg!(\"Records: {}, Quality score: {}%\", records_count, data_quality_score);
-    msg!(\"Reward earned: {} BUILD\", reward_amount / 10_u64.pow(9));
+    // Set up the holder's profile on their first report
+    init_user_profile_if_needed(user_profile, ctx.accounts.owner.key(), ctx.bumps.user_profile)?;
+
+    // Accrue contribution towards this epoch's healthcare emission budget
+    record_epoch_contribution(
+        &mut ctx.accounts.reward_epoch,
+        &mut ctx.accounts.epoch_contribution,
+        ctx.accounts.owner.key(),
+        ctx.bumps.epoch_contribution,
+        Sector::Healthcare,
+        reward_amount,
+    )?;
+
+    msg!("Healthcare data submitted successfully!");
+    msg!("Records: {}, Quality score: {}%", records_count, data_quality_score);
+    msg!("Epoch contribution: {} PKN", reward_amount / 10_u64.pow(9));
+
+    emit_cpi!(DataSubmitted {
+        owner: ctx.accounts.owner.key(),
+        asset: healthcare_provider.key(),
+        sector: Sector::Healthcare,
+        epoch: ctx.accounts.reward_epoch.id,
+        nonce,
+        reward_amount,
+        timestamp: current_time,
+    });
 
     Ok(())
 }
 
 // Taxation Data Submission
+#[event_cpi]
 #[derive(Accounts)]
 pub struct SubmitTaxationData<'info> {
     #[account(
         mut,
-        seeds = [b"tax_point", owner.key().as_ref()],
-        bump = tax_point.bump,
-        has_one = owner
+        seeds = [TAX_POINT_SEED, &tax_point.asset_id.to_le_bytes()],
+        bump = tax_point.bump
     )]
     pub tax_point: Account<'info, TaxCollectionPoint>,
     
     #[account(
-        mut,
+        token::mint = tax_point.nft_mint,
+        token::authority = owner,
+        constraint = owner_nft.amount == 1 @ DePINError::NotAssetHolder
+    )]
+    pub owner_nft: Account<'info, TokenAccount>,
+    
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = UserProfile::LEN,
         seeds = [USER_PROFILE_SEED, owner.key().as_ref()],
-        bump = user_profile.bump,
-        has_one = owner
+        bump
     )]
     pub user_profile: Account<'info, UserProfile>,
     
@@ -280,7 +496,29 @@ pub struct SubmitTaxationData<'info> {
     )]
     pub network_state: Account<'info, NetworkState>,
     
+    #[account(
+        mut,
+        seeds = [EPOCH_SEED, &network_state.current_epoch.to_le_bytes()],
+        bump = reward_epoch.bump
+    )]
+    pub reward_epoch: Account<'info, RewardEpoch>,
+    
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = EpochContribution::LEN,
+        seeds = [EPOCH_CONTRIBUTION_SEED, reward_epoch.key().as_ref(), owner.key().as_ref()],
+        bump
+    )]
+    pub epoch_contribution: Account<'info, EpochContribution>,
+    
+    /// CHECK: Instructions sysvar, used to inspect the device's ed25519 signature
+    #[account(address = INSTRUCTIONS_SYSVAR_ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+    
+    #[account(mut)]
     pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
 }
 
 pub fn submit_taxation_data(
@@ -288,47 +526,75 @@ pub fn submit_taxation_data(
     records_count: u32,
     amount_processed: u64,
     verification_score: u8,
+    nonce: u64,
+    timestamp: i64,
 ) -> Result<()> {
     require!(verification_score <= 100, DePINError::InvalidScore);
     require!(records_count > 0, DePINError::NoDataTransferred);
 
     let tax_point = &mut ctx.accounts.tax_point;
+    require!(tax_point.is_active, DePINError::AssetInactive);
     let user_profile = &mut ctx.accounts.user_profile;
     let network_state = &ctx.accounts.network_state;
 
     // Check minimum time between submissions (1 hour)
     let current_time = Clock::get()?.unix_timestamp;
     require!(
-        current_time - tax_point.last_data_submission >= 3600,
+        current_time.safe_sub(tax_point.last_data_submission)? >= 3600,
         DePINError::SubmissionTooFrequent
     );
 
+    // Verify the report was signed by the tax point's registered device
+    validate_telemetry_freshness(nonce, tax_point.device_nonce, timestamp, current_time)?;
+    let metrics = (records_count, amount_processed, verification_score).try_to_vec()?;
+    let message = telemetry_message(&tax_point.key(), nonce, timestamp, &metrics);
+    verify_device_signature(&ctx.accounts.instructions_sysvar, &tax_point.device_pubkey, &message)?;
+    tax_point.device_nonce = nonce;
+
     // Calculate rewards based on records and verification score
-    let base_reward = records_count as u64 * network_state.taxation_reward_rate;
     let verification_multiplier = verification_score as u64;
-    let reward_amount = (base_reward * verification_multiplier) / 100;
+    let reward_amount = calculate_reward(records_count as u64, network_state.taxation_reward_rate, verification_multiplier, 100)?;
 
     // Update tax point data
-    tax_point.total_records_submitted += records_count;
-    tax_point.total_amount_processed += amount_processed;
-    tax_point.total_rewards_earned += reward_amount;
+    tax_point.total_records_submitted = tax_point.total_records_submitted.safe_add(records_count)?;
+    tax_point.total_amount_processed = tax_point.total_amount_processed.safe_add(amount_processed)?;
+    tax_point.total_rewards_earned = tax_point.total_rewards_earned.safe_add(reward_amount)?;
     tax_point.last_data_submission = current_time;
 
     // Update verification score based on performance
     if verification_score >= 95 {
-        tax_point.verification_score = std::cmp::min(100, tax_point.verification_score + 1);
+        tax_point.verification_score = std::cmp::min(100, tax_point.verification_score.saturating_add(1));
     } else if verification_score < 80 {
-        tax_point.verification_score = std::cmp::max(0, tax_point.verification_score - 1);
+        tax_point.verification_score = tax_point.verification_score.saturating_sub(1);
     }
 
-    // Update user profile
-    user_profile.total_earned += reward_amount;
-    user_profile.last_activity = current_time;
-
-    msg!(\"Taxation data submitted successfully!\");
-    msg!(\"Records: {}, Amount processed: {} PKR, Verification score: {}%\", 
+    // Set up the holder's profile on their first report
+    init_user_profile_if_needed(user_profile, ctx.accounts.owner.key(), ctx.bumps.user_profile)?;
+
+    // Accrue contribution towards this epoch's taxation emission budget
+    record_epoch_contribution(
+        &mut ctx.accounts.reward_epoch,
+        &mut ctx.accounts.epoch_contribution,
+        ctx.accounts.owner.key(),
+        ctx.bumps.epoch_contribution,
+        Sector::Taxation,
+        reward_amount,
+    )?;
+
+    msg!("Taxation data submitted successfully!");
+    msg!("Records: {}, Amount processed: {} PKR, Verification score: {}%", 
          records_count, amount_processed, verification_score);
-    msg!(\"Reward earned: {} BUILD\", reward_amount / 10_u64.pow(9));
+    msg!("Epoch contribution: {} PKN", reward_amount / 10_u64.pow(9));
+
+    emit_cpi!(DataSubmitted {
+        owner: ctx.accounts.owner.key(),
+        asset: tax_point.key(),
+        sector: Sector::Taxation,
+        epoch: ctx.accounts.reward_epoch.id,
+        nonce,
+        reward_amount,
+        timestamp: current_time,
+    });
 
     Ok(())
 }
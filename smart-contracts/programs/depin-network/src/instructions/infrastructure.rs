@@ -1,21 +1,40 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Token, TokenAccount, Transfer, transfer};
+use anchor_lang::solana_program::sysvar::instructions::ID as INSTRUCTIONS_SYSVAR_ID;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::metadata::Metadata;
+use anchor_spl::token::{Mint, Token, TokenAccount};
 use crate::state::*;
 use crate::constants::*;
 use crate::errors::*;
-
+use crate::events::*;
+use crate::utils::{
+    calculate_reward, mint_asset_nft, hex_density_multiplier_bps, init_user_profile_if_needed,
+    record_epoch_contribution, telemetry_message, validate_h3_cell, validate_micro_degree_coordinates,
+    validate_telemetry_freshness, verify_device_signature, MintAssetNft, SafeMath,
+};
+
+#[event_cpi]
 #[derive(Accounts)]
-#[instruction(location: String)]
+#[instruction(device_pubkey: Pubkey, location: String, latitude: i32, longitude: i32, h3_index: u64)]
 pub struct RegisterWiFiHotspot<'info> {
     #[account(
         init,
         payer = owner,
         space = WiFiHotspot::LEN,
-        seeds = [WIFI_HOTSPOT_SEED, owner.key().as_ref()],
+        seeds = [WIFI_HOTSPOT_SEED, &network_state.next_asset_id.to_le_bytes()],
         bump
     )]
     pub wifi_hotspot: Account<'info, WiFiHotspot>,
     
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = HexCell::LEN,
+        seeds = [HEX_CELL_SEED, &h3_index.to_le_bytes()],
+        bump
+    )]
+    pub hex_cell: Account<'info, HexCell>,
+    
     #[account(
         mut,
         seeds = [NETWORK_STATE_SEED],
@@ -35,25 +54,98 @@ pub struct RegisterWiFiHotspot<'info> {
     #[account(mut)]
     pub owner: Signer<'info>,
     
+    #[account(
+        init,
+        payer = owner,
+        seeds = [ASSET_MINT_SEED, wifi_hotspot.key().as_ref()],
+        bump,
+        mint::decimals = 0,
+        mint::authority = network_state,
+        mint::freeze_authority = network_state
+    )]
+    pub nft_mint: Box<Account<'info, Mint>>,
+    
+    #[account(
+        init,
+        payer = owner,
+        associated_token::mint = nft_mint,
+        associated_token::authority = owner
+    )]
+    pub owner_nft: Box<Account<'info, TokenAccount>>,
+    
+    /// CHECK: Metadata PDA, created by the token metadata program
+    #[account(
+        mut,
+        seeds = [METADATA_SEED, token_metadata_program.key().as_ref(), nft_mint.key().as_ref()],
+        bump,
+        seeds::program = token_metadata_program.key()
+    )]
+    pub nft_metadata: UncheckedAccount<'info>,
+    
+    /// CHECK: Master edition PDA, created by the token metadata program
+    #[account(
+        mut,
+        seeds = [METADATA_SEED, token_metadata_program.key().as_ref(), nft_mint.key().as_ref(), EDITION_SEED],
+        bump,
+        seeds::program = token_metadata_program.key()
+    )]
+    pub nft_master_edition: UncheckedAccount<'info>,
+    
     pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_metadata_program: Program<'info, Metadata>,
+    pub rent: Sysvar<'info, Rent>,
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn register_wifi_hotspot(
     ctx: Context<RegisterWiFiHotspot>,
+    device_pubkey: Pubkey,
     location: String,
+    latitude: i32,
+    longitude: i32,
+    h3_index: u64,
     coverage_radius: u32,
     bandwidth_mbps: u32,
+    metadata_uri: String,
 ) -> Result<()> {
+    require!(ctx.accounts.user_profile.wifi_hotspots < MAX_HOTSPOTS_PER_USER, DePINError::TooManyAssets);
     require!(location.len() <= WiFiHotspot::MAX_LOCATION_LEN, DePINError::LocationTooLong);
+    require!(validate_micro_degree_coordinates(latitude, longitude), DePINError::InvalidCoordinates);
+    require!(
+        validate_h3_cell(h3_index, latitude, longitude, ctx.accounts.network_state.h3_resolution),
+        DePINError::InvalidH3Index
+    );
     require!(coverage_radius > 0 && coverage_radius <= 5000, DePINError::InvalidCoverageRadius);
-    require!(bandwidth_mbps >= 10 && bandwidth_mbps <= 1000, DePINError::InvalidBandwidth);
+    require!((10..=1000).contains(&bandwidth_mbps), DePINError::InvalidBandwidth);
+    require!(metadata_uri.len() <= MAX_NFT_URI_LEN, DePINError::UriTooLong);
 
     let wifi_hotspot = &mut ctx.accounts.wifi_hotspot;
+    let hex_cell = &mut ctx.accounts.hex_cell;
     let network_state = &mut ctx.accounts.network_state;
     let user_profile = &mut ctx.accounts.user_profile;
 
+    // Track hotspot density per hex; hotspots beyond the target share a diluted reward
+    if hex_cell.h3_index == 0 {
+        hex_cell.h3_index = h3_index;
+        hex_cell.resolution = network_state.h3_resolution;
+        hex_cell.active_hotspots = 0;
+        hex_cell.bump = ctx.bumps.hex_cell;
+    }
+    hex_cell.active_hotspots = hex_cell.active_hotspots.safe_add(1)?;
+    let reward_multiplier_bps = hex_density_multiplier_bps(hex_cell.active_hotspots, network_state.hex_density_target);
+
     wifi_hotspot.owner = ctx.accounts.owner.key();
+    wifi_hotspot.asset_id = network_state.next_asset_id;
+    wifi_hotspot.nft_mint = ctx.accounts.nft_mint.key();
+    wifi_hotspot.device_pubkey = device_pubkey;
+    wifi_hotspot.device_nonce = 0;
     wifi_hotspot.location = location.clone();
+    wifi_hotspot.latitude = latitude;
+    wifi_hotspot.longitude = longitude;
+    wifi_hotspot.h3_index = h3_index;
+    wifi_hotspot.reward_multiplier_bps = reward_multiplier_bps;
     wifi_hotspot.coverage_radius = coverage_radius;
     wifi_hotspot.bandwidth_mbps = bandwidth_mbps;
     wifi_hotspot.total_users_served = 0;
@@ -62,41 +154,65 @@ pub fn register_wifi_hotspot(
     wifi_hotspot.last_data_submission = 0;
     wifi_hotspot.is_active = true;
     wifi_hotspot.reputation_score = 100;
+    wifi_hotspot.successful_challenges = 0;
+    wifi_hotspot.failed_challenges = 0;
+    wifi_hotspot.open_challenges = 0;
     wifi_hotspot.bump = ctx.bumps.wifi_hotspot;
 
-    network_state.wifi_hotspots_count += 1;
-
-    // Initialize user profile if needed
-    if user_profile.owner == Pubkey::default() {
-        user_profile.owner = ctx.accounts.owner.key();
-        user_profile.total_earned = 0;
-        user_profile.total_staked = 0;
-        user_profile.reputation_score = 100;
-        user_profile.last_activity = Clock::get()?.unix_timestamp;
-        user_profile.wifi_hotspots = 1;
-        user_profile.logistics_partners = 0;
-        user_profile.farms = 0;
-        user_profile.governance_votes = 0;
-        user_profile.bump = ctx.bumps.user_profile;
-    } else {
-        user_profile.wifi_hotspots += 1;
-        user_profile.last_activity = Clock::get()?.unix_timestamp;
-    }
+    network_state.wifi_hotspots_count = network_state.wifi_hotspots_count.safe_add(1)?;
+    network_state.next_asset_id = network_state.next_asset_id.safe_add(1)?;
+
+    init_user_profile_if_needed(user_profile, ctx.accounts.owner.key(), ctx.bumps.user_profile)?;
+    user_profile.wifi_hotspots = user_profile.wifi_hotspots.safe_add(1)?;
+
+    mint_asset_nft(
+        MintAssetNft {
+            network_state: &ctx.accounts.network_state,
+            nft_mint: &ctx.accounts.nft_mint,
+            owner_nft: &ctx.accounts.owner_nft,
+            nft_metadata: &ctx.accounts.nft_metadata,
+            nft_master_edition: &ctx.accounts.nft_master_edition,
+            payer: &ctx.accounts.owner,
+            token_program: &ctx.accounts.token_program,
+            token_metadata_program: &ctx.accounts.token_metadata_program,
+            system_program: &ctx.accounts.system_program,
+            rent: &ctx.accounts.rent,
+        },
+        format!("PKN WiFi #{}", wifi_hotspot.asset_id),
+        metadata_uri,
+    )?;
 
     msg!("WiFi hotspot registered successfully at {}", location);
+    msg!("Coordinates: ({}, {}) micro-degrees", latitude, longitude);
+    msg!("H3 cell: {:x}, Hotspots in cell: {}, Reward multiplier: {} bps",
+         h3_index, hex_cell.active_hotspots, reward_multiplier_bps);
     msg!("Coverage radius: {} meters, Bandwidth: {} Mbps", coverage_radius, bandwidth_mbps);
 
+    emit_cpi!(HotspotRegistered {
+        owner: wifi_hotspot.owner,
+        hotspot: wifi_hotspot.key(),
+        asset_id: wifi_hotspot.asset_id,
+        nft_mint: wifi_hotspot.nft_mint,
+        device_pubkey,
+        latitude,
+        longitude,
+        h3_index,
+        coverage_radius,
+        bandwidth_mbps,
+        reward_multiplier_bps,
+    });
+
     Ok(())
 }
 
+#[event_cpi]
 #[derive(Accounts)]
-#[instruction(partner_name: String)]
 pub struct RegisterLogisticsPartner<'info> {
     #[account(
         init,
         payer = owner,
         space = LogisticsPartner::LEN,
-        seeds = [LOGISTICS_PARTNER_SEED, owner.key().as_ref()],
+        seeds = [LOGISTICS_PARTNER_SEED, &network_state.next_asset_id.to_le_bytes()],
         bump
     )]
     pub logistics_partner: Account<'info, LogisticsPartner>,
@@ -120,18 +236,63 @@ pub struct RegisterLogisticsPartner<'info> {
     #[account(mut)]
     pub owner: Signer<'info>,
     
+    #[account(
+        init,
+        payer = owner,
+        seeds = [ASSET_MINT_SEED, logistics_partner.key().as_ref()],
+        bump,
+        mint::decimals = 0,
+        mint::authority = network_state,
+        mint::freeze_authority = network_state
+    )]
+    pub nft_mint: Box<Account<'info, Mint>>,
+    
+    #[account(
+        init,
+        payer = owner,
+        associated_token::mint = nft_mint,
+        associated_token::authority = owner
+    )]
+    pub owner_nft: Box<Account<'info, TokenAccount>>,
+    
+    /// CHECK: Metadata PDA, created by the token metadata program
+    #[account(
+        mut,
+        seeds = [METADATA_SEED, token_metadata_program.key().as_ref(), nft_mint.key().as_ref()],
+        bump,
+        seeds::program = token_metadata_program.key()
+    )]
+    pub nft_metadata: UncheckedAccount<'info>,
+    
+    /// CHECK: Master edition PDA, created by the token metadata program
+    #[account(
+        mut,
+        seeds = [METADATA_SEED, token_metadata_program.key().as_ref(), nft_mint.key().as_ref(), EDITION_SEED],
+        bump,
+        seeds::program = token_metadata_program.key()
+    )]
+    pub nft_master_edition: UncheckedAccount<'info>,
+    
     pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_metadata_program: Program<'info, Metadata>,
+    pub rent: Sysvar<'info, Rent>,
 }
 
 pub fn register_logistics_partner(
     ctx: Context<RegisterLogisticsPartner>,
+    device_pubkey: Pubkey,
     partner_name: String,
     service_areas: Vec<String>,
     vehicle_count: u32,
+    metadata_uri: String,
 ) -> Result<()> {
+    require!(ctx.accounts.user_profile.logistics_partners < MAX_LOGISTICS_PARTNERS_PER_USER, DePINError::TooManyAssets);
     require!(partner_name.len() <= LogisticsPartner::MAX_NAME_LEN, DePINError::NameTooLong);
     require!(service_areas.len() <= LogisticsPartner::MAX_SERVICE_AREAS, DePINError::TooManyServiceAreas);
     require!(vehicle_count > 0 && vehicle_count <= 1000, DePINError::InvalidVehicleCount);
+    require!(metadata_uri.len() <= MAX_NFT_URI_LEN, DePINError::UriTooLong);
 
     for area in &service_areas {
         require!(area.len() <= LogisticsPartner::MAX_AREA_LEN, DePINError::ServiceAreaTooLong);
@@ -142,6 +303,10 @@ pub fn register_logistics_partner(
     let user_profile = &mut ctx.accounts.user_profile;
 
     logistics_partner.owner = ctx.accounts.owner.key();
+    logistics_partner.asset_id = network_state.next_asset_id;
+    logistics_partner.nft_mint = ctx.accounts.nft_mint.key();
+    logistics_partner.device_pubkey = device_pubkey;
+    logistics_partner.device_nonce = 0;
     logistics_partner.partner_name = partner_name.clone();
     logistics_partner.service_areas = service_areas;
     logistics_partner.vehicle_count = vehicle_count;
@@ -153,39 +318,52 @@ pub fn register_logistics_partner(
     logistics_partner.efficiency_score = 100;
     logistics_partner.bump = ctx.bumps.logistics_partner;
 
-    network_state.logistics_partners_count += 1;
-
-    // Initialize user profile if needed
-    if user_profile.owner == Pubkey::default() {
-        user_profile.owner = ctx.accounts.owner.key();
-        user_profile.total_earned = 0;
-        user_profile.total_staked = 0;
-        user_profile.reputation_score = 100;
-        user_profile.last_activity = Clock::get()?.unix_timestamp;
-        user_profile.wifi_hotspots = 0;
-        user_profile.logistics_partners = 1;
-        user_profile.farms = 0;
-        user_profile.governance_votes = 0;
-        user_profile.bump = ctx.bumps.user_profile;
-    } else {
-        user_profile.logistics_partners += 1;
-        user_profile.last_activity = Clock::get()?.unix_timestamp;
-    }
+    network_state.logistics_partners_count = network_state.logistics_partners_count.safe_add(1)?;
+    network_state.next_asset_id = network_state.next_asset_id.safe_add(1)?;
+
+    init_user_profile_if_needed(user_profile, ctx.accounts.owner.key(), ctx.bumps.user_profile)?;
+    user_profile.logistics_partners = user_profile.logistics_partners.safe_add(1)?;
+
+    mint_asset_nft(
+        MintAssetNft {
+            network_state: &ctx.accounts.network_state,
+            nft_mint: &ctx.accounts.nft_mint,
+            owner_nft: &ctx.accounts.owner_nft,
+            nft_metadata: &ctx.accounts.nft_metadata,
+            nft_master_edition: &ctx.accounts.nft_master_edition,
+            payer: &ctx.accounts.owner,
+            token_program: &ctx.accounts.token_program,
+            token_metadata_program: &ctx.accounts.token_metadata_program,
+            system_program: &ctx.accounts.system_program,
+            rent: &ctx.accounts.rent,
+        },
+        format!("PKN Logistics #{}", logistics_partner.asset_id),
+        metadata_uri,
+    )?;
 
     msg!("Logistics partner '{}' registered successfully", partner_name);
     msg!("Service areas: {:?}, Vehicle count: {}", logistics_partner.service_areas, vehicle_count);
 
+    emit_cpi!(InfrastructureRegistered {
+        owner: logistics_partner.owner,
+        asset: logistics_partner.key(),
+        sector: Sector::Logistics,
+        asset_id: logistics_partner.asset_id,
+        nft_mint: logistics_partner.nft_mint,
+        device_pubkey,
+    });
+
     Ok(())
 }
 
+#[event_cpi]
 #[derive(Accounts)]
-#[instruction(farm_name: String)]
 pub struct RegisterFarm<'info> {
     #[account(
         init,
         payer = owner,
         space = Farm::LEN,
-        seeds = [FARM_SEED, owner.key().as_ref()],
+        seeds = [FARM_SEED, &network_state.next_asset_id.to_le_bytes()],
         bump
     )]
     pub farm: Account<'info, Farm>,
@@ -209,20 +387,69 @@ pub struct RegisterFarm<'info> {
     #[account(mut)]
     pub owner: Signer<'info>,
     
+    #[account(
+        init,
+        payer = owner,
+        seeds = [ASSET_MINT_SEED, farm.key().as_ref()],
+        bump,
+        mint::decimals = 0,
+        mint::authority = network_state,
+        mint::freeze_authority = network_state
+    )]
+    pub nft_mint: Box<Account<'info, Mint>>,
+    
+    #[account(
+        init,
+        payer = owner,
+        associated_token::mint = nft_mint,
+        associated_token::authority = owner
+    )]
+    pub owner_nft: Box<Account<'info, TokenAccount>>,
+    
+    /// CHECK: Metadata PDA, created by the token metadata program
+    #[account(
+        mut,
+        seeds = [METADATA_SEED, token_metadata_program.key().as_ref(), nft_mint.key().as_ref()],
+        bump,
+        seeds::program = token_metadata_program.key()
+    )]
+    pub nft_metadata: UncheckedAccount<'info>,
+    
+    /// CHECK: Master edition PDA, created by the token metadata program
+    #[account(
+        mut,
+        seeds = [METADATA_SEED, token_metadata_program.key().as_ref(), nft_mint.key().as_ref(), EDITION_SEED],
+        bump,
+        seeds::program = token_metadata_program.key()
+    )]
+    pub nft_master_edition: UncheckedAccount<'info>,
+    
     pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_metadata_program: Program<'info, Metadata>,
+    pub rent: Sysvar<'info, Rent>,
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn register_farm(
     ctx: Context<RegisterFarm>,
+    device_pubkey: Pubkey,
     farm_name: String,
     location: String,
+    latitude: i32,
+    longitude: i32,
     farm_size_acres: u32,
     crop_types: Vec<String>,
+    metadata_uri: String,
 ) -> Result<()> {
+    require!(ctx.accounts.user_profile.farms < MAX_FARMS_PER_USER, DePINError::TooManyAssets);
     require!(farm_name.len() <= Farm::MAX_NAME_LEN, DePINError::NameTooLong);
     require!(location.len() <= Farm::MAX_LOCATION_LEN, DePINError::LocationTooLong);
+    require!(validate_micro_degree_coordinates(latitude, longitude), DePINError::InvalidCoordinates);
     require!(farm_size_acres > 0 && farm_size_acres <= 10000, DePINError::InvalidFarmSize);
     require!(crop_types.len() <= Farm::MAX_CROP_TYPES, DePINError::TooManyCropTypes);
+    require!(metadata_uri.len() <= MAX_NFT_URI_LEN, DePINError::UriTooLong);
 
     for crop in &crop_types {
         require!(crop.len() <= Farm::MAX_CROP_LEN, DePINError::CropNameTooLong);
@@ -233,8 +460,14 @@ pub fn register_farm(
     let user_profile = &mut ctx.accounts.user_profile;
 
     farm.owner = ctx.accounts.owner.key();
+    farm.asset_id = network_state.next_asset_id;
+    farm.nft_mint = ctx.accounts.nft_mint.key();
+    farm.device_pubkey = device_pubkey;
+    farm.device_nonce = 0;
     farm.farm_name = farm_name.clone();
     farm.location = location.clone();
+    farm.latitude = latitude;
+    farm.longitude = longitude;
     farm.farm_size_acres = farm_size_acres;
     farm.crop_types = crop_types;
     farm.total_data_submissions = 0;
@@ -244,63 +477,77 @@ pub fn register_farm(
     farm.yield_improvement = 0;
     farm.bump = ctx.bumps.farm;
 
-    network_state.farms_count += 1;
-
-    // Initialize user profile if needed
-    if user_profile.owner == Pubkey::default() {
-        user_profile.owner = ctx.accounts.owner.key();
-        user_profile.total_earned = 0;
-        user_profile.total_staked = 0;
-        user_profile.reputation_score = 100;
-        user_profile.last_activity = Clock::get()?.unix_timestamp;
-        user_profile.wifi_hotspots = 0;
-        user_profile.logistics_partners = 0;
-        user_profile.farms = 1;
-        user_profile.governance_votes = 0;
-        user_profile.bump = ctx.bumps.user_profile;
-    } else {
-        user_profile.farms += 1;
-        user_profile.last_activity = Clock::get()?.unix_timestamp;
-    }
+    network_state.farms_count = network_state.farms_count.safe_add(1)?;
+    network_state.next_asset_id = network_state.next_asset_id.safe_add(1)?;
+
+    init_user_profile_if_needed(user_profile, ctx.accounts.owner.key(), ctx.bumps.user_profile)?;
+    user_profile.farms = user_profile.farms.safe_add(1)?;
+
+    mint_asset_nft(
+        MintAssetNft {
+            network_state: &ctx.accounts.network_state,
+            nft_mint: &ctx.accounts.nft_mint,
+            owner_nft: &ctx.accounts.owner_nft,
+            nft_metadata: &ctx.accounts.nft_metadata,
+            nft_master_edition: &ctx.accounts.nft_master_edition,
+            payer: &ctx.accounts.owner,
+            token_program: &ctx.accounts.token_program,
+            token_metadata_program: &ctx.accounts.token_metadata_program,
+            system_program: &ctx.accounts.system_program,
+            rent: &ctx.accounts.rent,
+        },
+        format!("PKN Farm #{}", farm.asset_id),
+        metadata_uri,
+    )?;
 
     msg!("Farm '{}' registered successfully at {}", farm_name, location);
+    msg!("Coordinates: ({}, {}) micro-degrees", latitude, longitude);
     msg!("Size: {} acres, Crop types: {:?}", farm_size_acres, farm.crop_types);
 
+    emit_cpi!(InfrastructureRegistered {
+        owner: farm.owner,
+        asset: farm.key(),
+        sector: Sector::Agriculture,
+        asset_id: farm.asset_id,
+        nft_mint: farm.nft_mint,
+        device_pubkey,
+    });
+
     Ok(())
 }
 
 // Data submission instructions for earning rewards
+#[event_cpi]
 #[derive(Accounts)]
 pub struct SubmitWiFiData<'info> {
     #[account(
         mut,
-        seeds = [WIFI_HOTSPOT_SEED, owner.key().as_ref()],
-        bump = wifi_hotspot.bump,
-        has_one = owner
+        seeds = [WIFI_HOTSPOT_SEED, &wifi_hotspot.asset_id.to_le_bytes()],
+        bump = wifi_hotspot.bump
     )]
     pub wifi_hotspot: Account<'info, WiFiHotspot>,
     
     #[account(
-        mut,
-        seeds = [USER_PROFILE_SEED, owner.key().as_ref()],
-        bump = user_profile.bump,
-        has_one = owner
+        token::mint = wifi_hotspot.nft_mint,
+        token::authority = owner,
+        constraint = owner_nft.amount == 1 @ DePINError::NotAssetHolder
     )]
-    pub user_profile: Account<'info, UserProfile>,
+    pub owner_nft: Account<'info, TokenAccount>,
     
     #[account(
-        mut,
-        associated_token::mint = mint,
-        associated_token::authority = owner
+        seeds = [HEX_CELL_SEED, &wifi_hotspot.h3_index.to_le_bytes()],
+        bump = hex_cell.bump
     )]
-    pub user_token_account: Account<'info, TokenAccount>,
+    pub hex_cell: Account<'info, HexCell>,
     
     #[account(
-        mut,
-        seeds = [MINT_SEED],
+        init_if_needed,
+        payer = owner,
+        space = UserProfile::LEN,
+        seeds = [USER_PROFILE_SEED, owner.key().as_ref()],
         bump
     )]
-    pub mint: Account<'info, anchor_spl::token::Mint>,
+    pub user_profile: Account<'info, UserProfile>,
     
     #[account(
         seeds = [NETWORK_STATE_SEED],
@@ -308,8 +555,29 @@ pub struct SubmitWiFiData<'info> {
     )]
     pub network_state: Account<'info, NetworkState>,
     
+    #[account(
+        mut,
+        seeds = [EPOCH_SEED, &network_state.current_epoch.to_le_bytes()],
+        bump = reward_epoch.bump
+    )]
+    pub reward_epoch: Account<'info, RewardEpoch>,
+    
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = EpochContribution::LEN,
+        seeds = [EPOCH_CONTRIBUTION_SEED, reward_epoch.key().as_ref(), owner.key().as_ref()],
+        bump
+    )]
+    pub epoch_contribution: Account<'info, EpochContribution>,
+    
+    /// CHECK: Instructions sysvar, used to inspect the device's ed25519 signature
+    #[account(address = INSTRUCTIONS_SYSVAR_ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+    
+    #[account(mut)]
     pub owner: Signer<'info>,
-    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
 }
 
 pub fn submit_wifi_data(
@@ -317,68 +585,112 @@ pub fn submit_wifi_data(
     users_connected: u32,
     data_transferred_gb: u64,
     uptime_percentage: u8,
+    nonce: u64,
+    timestamp: i64,
 ) -> Result<()> {
     require!(uptime_percentage <= 100, DePINError::InvalidUptime);
     require!(data_transferred_gb > 0, DePINError::NoDataTransferred);
 
     let wifi_hotspot = &mut ctx.accounts.wifi_hotspot;
+    require!(wifi_hotspot.is_active, DePINError::AssetInactive);
     let user_profile = &mut ctx.accounts.user_profile;
     let network_state = &ctx.accounts.network_state;
 
     // Check minimum time between submissions (1 hour)
     let current_time = Clock::get()?.unix_timestamp;
     require!(
-        current_time - wifi_hotspot.last_data_submission >= 3600,
+        current_time.safe_sub(wifi_hotspot.last_data_submission)? >= 3600,
         DePINError::SubmissionTooFrequent
     );
 
-    // Calculate rewards based on data transferred and uptime
-    let base_reward = data_transferred_gb * network_state.wifi_reward_rate;
+    // Verify the report was signed by the hotspot's registered device
+    validate_telemetry_freshness(nonce, wifi_hotspot.device_nonce, timestamp, current_time)?;
+    let metrics = (users_connected, data_transferred_gb, uptime_percentage).try_to_vec()?;
+    let message = telemetry_message(&wifi_hotspot.key(), nonce, timestamp, &metrics);
+    verify_device_signature(&ctx.accounts.instructions_sysvar, &wifi_hotspot.device_pubkey, &message)?;
+    wifi_hotspot.device_nonce = nonce;
+
+    // Calculate rewards based on data transferred and uptime, weighted by
+    // the reputation earned through proof-of-coverage and the hex's current density
+    wifi_hotspot.reward_multiplier_bps =
+        hex_density_multiplier_bps(ctx.accounts.hex_cell.active_hotspots, network_state.hex_density_target);
     let uptime_multiplier = uptime_percentage as u64;
-    let reward_amount = (base_reward * uptime_multiplier) / 100;
+    let reputation_multiplier = wifi_hotspot.reputation_score as u64;
+    let density_multiplier = wifi_hotspot.reward_multiplier_bps as u64;
+    let reward_amount = calculate_reward(
+        data_transferred_gb,
+        network_state.wifi_reward_rate,
+        uptime_multiplier * reputation_multiplier * density_multiplier,
+        100 * 100 * BASIS_POINTS,
+    )?;
 
     // Update hotspot data
-    wifi_hotspot.total_users_served += users_connected;
-    wifi_hotspot.total_data_transferred += data_transferred_gb;
-    wifi_hotspot.total_rewards_earned += reward_amount;
+    wifi_hotspot.total_users_served = wifi_hotspot.total_users_served.safe_add(users_connected)?;
+    wifi_hotspot.total_data_transferred = wifi_hotspot.total_data_transferred.safe_add(data_transferred_gb)?;
+    wifi_hotspot.total_rewards_earned = wifi_hotspot.total_rewards_earned.safe_add(reward_amount)?;
     wifi_hotspot.last_data_submission = current_time;
 
     // Update reputation score based on performance
     if uptime_percentage >= 95 {
-        wifi_hotspot.reputation_score = std::cmp::min(100, wifi_hotspot.reputation_score + 1);
+        wifi_hotspot.reputation_score = std::cmp::min(100, wifi_hotspot.reputation_score.saturating_add(1));
     } else if uptime_percentage < 80 {
-        wifi_hotspot.reputation_score = std::cmp::max(0, wifi_hotspot.reputation_score - 1);
+        wifi_hotspot.reputation_score = wifi_hotspot.reputation_score.saturating_sub(1);
     }
 
-    // Update user profile
-    user_profile.total_earned += reward_amount;
-    user_profile.last_activity = current_time;
+    // Set up the holder's profile on their first report
+    init_user_profile_if_needed(user_profile, ctx.accounts.owner.key(), ctx.bumps.user_profile)?;
+
+    // Accrue contribution towards this epoch's WiFi emission budget
+    record_epoch_contribution(
+        &mut ctx.accounts.reward_epoch,
+        &mut ctx.accounts.epoch_contribution,
+        ctx.accounts.owner.key(),
+        ctx.bumps.epoch_contribution,
+        Sector::WiFi,
+        reward_amount,
+    )?;
 
-    // Mint rewards to user (in production, this would use a treasury account)
-    // For now, we'll emit an event for the frontend to handle
     msg!("WiFi data submitted successfully!");
     msg!("Users connected: {}, Data transferred: {} GB, Uptime: {}%", 
          users_connected, data_transferred_gb, uptime_percentage);
-    msg!("Reward earned: {} PKN", reward_amount / 10_u64.pow(9));
+    msg!("Epoch contribution: {} PKN", reward_amount / 10_u64.pow(9));
+
+    emit_cpi!(DataSubmitted {
+        owner: ctx.accounts.owner.key(),
+        asset: wifi_hotspot.key(),
+        sector: Sector::WiFi,
+        epoch: ctx.accounts.reward_epoch.id,
+        nonce,
+        reward_amount,
+        timestamp: current_time,
+    });
 
     Ok(())
 }
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct SubmitLogisticsData<'info> {
     #[account(
         mut,
-        seeds = [LOGISTICS_PARTNER_SEED, owner.key().as_ref()],
-        bump = logistics_partner.bump,
-        has_one = owner
+        seeds = [LOGISTICS_PARTNER_SEED, &logistics_partner.asset_id.to_le_bytes()],
+        bump = logistics_partner.bump
     )]
     pub logistics_partner: Account<'info, LogisticsPartner>,
     
     #[account(
-        mut,
+        token::mint = logistics_partner.nft_mint,
+        token::authority = owner,
+        constraint = owner_nft.amount == 1 @ DePINError::NotAssetHolder
+    )]
+    pub owner_nft: Account<'info, TokenAccount>,
+    
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = UserProfile::LEN,
         seeds = [USER_PROFILE_SEED, owner.key().as_ref()],
-        bump = user_profile.bump,
-        has_one = owner
+        bump
     )]
     pub user_profile: Account<'info, UserProfile>,
     
@@ -388,7 +700,29 @@ pub struct SubmitLogisticsData<'info> {
     )]
     pub network_state: Account<'info, NetworkState>,
     
+    #[account(
+        mut,
+        seeds = [EPOCH_SEED, &network_state.current_epoch.to_le_bytes()],
+        bump = reward_epoch.bump
+    )]
+    pub reward_epoch: Account<'info, RewardEpoch>,
+    
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = EpochContribution::LEN,
+        seeds = [EPOCH_CONTRIBUTION_SEED, reward_epoch.key().as_ref(), owner.key().as_ref()],
+        bump
+    )]
+    pub epoch_contribution: Account<'info, EpochContribution>,
+    
+    /// CHECK: Instructions sysvar, used to inspect the device's ed25519 signature
+    #[account(address = INSTRUCTIONS_SYSVAR_ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+    
+    #[account(mut)]
     pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
 }
 
 pub fn submit_logistics_data(
@@ -397,66 +731,102 @@ pub fn submit_logistics_data(
     distance_traveled_km: u32,
     fuel_efficiency: u32,
     route_optimization_score: u8,
+    nonce: u64,
+    timestamp: i64,
 ) -> Result<()> {
     require!(route_optimization_score <= 100, DePINError::InvalidScore);
     require!(deliveries_completed > 0, DePINError::NoDeliveries);
 
     let logistics_partner = &mut ctx.accounts.logistics_partner;
+    require!(logistics_partner.is_active, DePINError::AssetInactive);
     let user_profile = &mut ctx.accounts.user_profile;
     let network_state = &ctx.accounts.network_state;
 
     // Check minimum time between submissions (1 hour)
     let current_time = Clock::get()?.unix_timestamp;
     require!(
-        current_time - logistics_partner.last_data_submission >= 3600,
+        current_time.safe_sub(logistics_partner.last_data_submission)? >= 3600,
         DePINError::SubmissionTooFrequent
     );
 
+    // Verify the report was signed by the partner's registered device
+    validate_telemetry_freshness(nonce, logistics_partner.device_nonce, timestamp, current_time)?;
+    let metrics = (deliveries_completed, distance_traveled_km, fuel_efficiency, route_optimization_score).try_to_vec()?;
+    let message = telemetry_message(&logistics_partner.key(), nonce, timestamp, &metrics);
+    verify_device_signature(&ctx.accounts.instructions_sysvar, &logistics_partner.device_pubkey, &message)?;
+    logistics_partner.device_nonce = nonce;
+
     // Calculate rewards based on deliveries and efficiency
-    let base_reward = deliveries_completed as u64 * network_state.logistics_reward_rate;
     let efficiency_multiplier = route_optimization_score as u64;
-    let reward_amount = (base_reward * efficiency_multiplier) / 100;
+    let reward_amount = calculate_reward(deliveries_completed as u64, network_state.logistics_reward_rate, efficiency_multiplier, 100)?;
 
     // Update logistics partner data
-    logistics_partner.total_deliveries += deliveries_completed;
-    logistics_partner.total_distance_km += distance_traveled_km;
-    logistics_partner.total_rewards_earned += reward_amount;
+    logistics_partner.total_deliveries = logistics_partner.total_deliveries.safe_add(deliveries_completed)?;
+    logistics_partner.total_distance_km = logistics_partner.total_distance_km.safe_add(distance_traveled_km)?;
+    logistics_partner.total_rewards_earned = logistics_partner.total_rewards_earned.safe_add(reward_amount)?;
     logistics_partner.last_data_submission = current_time;
 
     // Update efficiency score
     if route_optimization_score >= 90 {
-        logistics_partner.efficiency_score = std::cmp::min(100, logistics_partner.efficiency_score + 1);
+        logistics_partner.efficiency_score = std::cmp::min(100, logistics_partner.efficiency_score.saturating_add(1));
     } else if route_optimization_score < 70 {
-        logistics_partner.efficiency_score = std::cmp::max(0, logistics_partner.efficiency_score - 1);
+        logistics_partner.efficiency_score = logistics_partner.efficiency_score.saturating_sub(1);
     }
 
-    // Update user profile
-    user_profile.total_earned += reward_amount;
-    user_profile.last_activity = current_time;
+    // Set up the holder's profile on their first report
+    init_user_profile_if_needed(user_profile, ctx.accounts.owner.key(), ctx.bumps.user_profile)?;
+
+    // Accrue contribution towards this epoch's logistics emission budget
+    record_epoch_contribution(
+        &mut ctx.accounts.reward_epoch,
+        &mut ctx.accounts.epoch_contribution,
+        ctx.accounts.owner.key(),
+        ctx.bumps.epoch_contribution,
+        Sector::Logistics,
+        reward_amount,
+    )?;
 
     msg!("Logistics data submitted successfully!");
     msg!("Deliveries: {}, Distance: {} km, Efficiency score: {}%", 
          deliveries_completed, distance_traveled_km, route_optimization_score);
-    msg!("Reward earned: {} PKN", reward_amount / 10_u64.pow(9));
+    msg!("Epoch contribution: {} PKN", reward_amount / 10_u64.pow(9));
+
+    emit_cpi!(DataSubmitted {
+        owner: ctx.accounts.owner.key(),
+        asset: logistics_partner.key(),
+        sector: Sector::Logistics,
+        epoch: ctx.accounts.reward_epoch.id,
+        nonce,
+        reward_amount,
+        timestamp: current_time,
+    });
 
     Ok(())
 }
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct SubmitAgricultureData<'info> {
     #[account(
         mut,
-        seeds = [FARM_SEED, owner.key().as_ref()],
-        bump = farm.bump,
-        has_one = owner
+        seeds = [FARM_SEED, &farm.asset_id.to_le_bytes()],
+        bump = farm.bump
     )]
     pub farm: Account<'info, Farm>,
     
     #[account(
-        mut,
+        token::mint = farm.nft_mint,
+        token::authority = owner,
+        constraint = owner_nft.amount == 1 @ DePINError::NotAssetHolder
+    )]
+    pub owner_nft: Account<'info, TokenAccount>,
+    
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = UserProfile::LEN,
         seeds = [USER_PROFILE_SEED, owner.key().as_ref()],
-        bump = user_profile.bump,
-        has_one = owner
+        bump
     )]
     pub user_profile: Account<'info, UserProfile>,
     
@@ -466,7 +836,29 @@ pub struct SubmitAgricultureData<'info> {
     )]
     pub network_state: Account<'info, NetworkState>,
     
+    #[account(
+        mut,
+        seeds = [EPOCH_SEED, &network_state.current_epoch.to_le_bytes()],
+        bump = reward_epoch.bump
+    )]
+    pub reward_epoch: Account<'info, RewardEpoch>,
+    
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = EpochContribution::LEN,
+        seeds = [EPOCH_CONTRIBUTION_SEED, reward_epoch.key().as_ref(), owner.key().as_ref()],
+        bump
+    )]
+    pub epoch_contribution: Account<'info, EpochContribution>,
+    
+    /// CHECK: Instructions sysvar, used to inspect the device's ed25519 signature
+    #[account(address = INSTRUCTIONS_SYSVAR_ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+    
+    #[account(mut)]
     pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
 }
 
 pub fn submit_agriculture_data(
@@ -475,39 +867,68 @@ pub fn submit_agriculture_data(
     temperature: i16,
     humidity: u8,
     ph_level: u8,
+    nonce: u64,
+    timestamp: i64,
 ) -> Result<()> {
     require!(soil_moisture <= 100, DePINError::InvalidMoisture);
     require!(humidity <= 100, DePINError::InvalidHumidity);
     require!(ph_level <= 14, DePINError::InvalidPH);
-    require!(temperature >= -50 && temperature <= 70, DePINError::InvalidTemperature);
+    require!((-50..=70).contains(&temperature), DePINError::InvalidTemperature);
 
     let farm = &mut ctx.accounts.farm;
+    require!(farm.is_active, DePINError::AssetInactive);
     let user_profile = &mut ctx.accounts.user_profile;
     let network_state = &ctx.accounts.network_state;
 
     // Check minimum time between submissions (1 hour)
     let current_time = Clock::get()?.unix_timestamp;
     require!(
-        current_time - farm.last_data_submission >= 3600,
+        current_time.safe_sub(farm.last_data_submission)? >= 3600,
         DePINError::SubmissionTooFrequent
     );
 
+    // Verify the report was signed by the farm's registered device
+    validate_telemetry_freshness(nonce, farm.device_nonce, timestamp, current_time)?;
+    let metrics = (soil_moisture, temperature, humidity, ph_level).try_to_vec()?;
+    let message = telemetry_message(&farm.key(), nonce, timestamp, &metrics);
+    verify_device_signature(&ctx.accounts.instructions_sysvar, &farm.device_pubkey, &message)?;
+    farm.device_nonce = nonce;
+
     // Calculate rewards (fixed amount per submission for now)
     let reward_amount = network_state.agriculture_reward_rate;
 
     // Update farm data
-    farm.total_data_submissions += 1;
-    farm.total_rewards_earned += reward_amount;
+    farm.total_data_submissions = farm.total_data_submissions.safe_add(1)?;
+    farm.total_rewards_earned = farm.total_rewards_earned.safe_add(reward_amount)?;
     farm.last_data_submission = current_time;
 
-    // Update user profile
-    user_profile.total_earned += reward_amount;
-    user_profile.last_activity = current_time;
+    // Set up the holder's profile on their first report
+    init_user_profile_if_needed(user_profile, ctx.accounts.owner.key(), ctx.bumps.user_profile)?;
+
+    // Accrue contribution towards this epoch's agriculture emission budget
+    record_epoch_contribution(
+        &mut ctx.accounts.reward_epoch,
+        &mut ctx.accounts.epoch_contribution,
+        ctx.accounts.owner.key(),
+        ctx.bumps.epoch_contribution,
+        Sector::Agriculture,
+        reward_amount,
+    )?;
 
     msg!("Agriculture data submitted successfully!");
     msg!("Soil moisture: {}%, Temperature: {}°C, Humidity: {}%, pH: {}", 
          soil_moisture, temperature, humidity, ph_level);
-    msg!("Reward earned: {} PKN", reward_amount / 10_u64.pow(9));
+    msg!("Epoch contribution: {} PKN", reward_amount / 10_u64.pow(9));
+
+    emit_cpi!(DataSubmitted {
+        owner: ctx.accounts.owner.key(),
+        asset: farm.key(),
+        sector: Sector::Agriculture,
+        epoch: ctx.accounts.reward_epoch.id,
+        nonce,
+        reward_amount,
+        timestamp: current_time,
+    });
 
     Ok(())
 }
\ No newline at end of file
@@ -1,8 +1,12 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Mint, Token};
+use anchor_spl::token::{Mint, Token, TokenAccount};
 use crate::state::*;
 use crate::constants::*;
+use crate::errors::*;
+use crate::events::*;
+use crate::utils::{mint_reward, mul_div};
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct Initialize<'info> {
     #[account(
@@ -17,13 +21,32 @@ pub struct Initialize<'info> {
     #[account(
         init,
         payer = authority,
-        mint::decimals = 9,
+        mint::decimals = PKN_DECIMALS,
         mint::authority = network_state,
         seeds = [MINT_SEED],
         bump
     )]
     pub mint: Account<'info, Mint>,
     
+    #[account(
+        init,
+        payer = authority,
+        token::mint = mint,
+        token::authority = network_state,
+        seeds = [NETWORK_STATE_SEED, TREASURY_SEED],
+        bump
+    )]
+    pub treasury: Account<'info, TokenAccount>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = RewardEpoch::LEN,
+        seeds = [EPOCH_SEED, &0u64.to_le_bytes()],
+        bump
+    )]
+    pub reward_epoch: Account<'info, RewardEpoch>,
+    
     #[account(mut)]
     pub authority: Signer<'info>,
     
@@ -37,6 +60,8 @@ pub fn initialize(
     total_supply: u64,
     decimals: u8,
 ) -> Result<()> {
+    require!(decimals == PKN_DECIMALS, DePINError::InvalidMintDecimals);
+
     let network_state = &mut ctx.accounts.network_state;
     
     network_state.authority = ctx.accounts.authority.key();
@@ -48,21 +73,69 @@ pub fn initialize(
     network_state.farms_count = 0;
     network_state.healthcare_providers_count = 0;
     network_state.tax_points_count = 0;
+    network_state.ev_chargers_count = 0;
     network_state.wifi_reward_rate = 100 * 10_u64.pow(9); // 100 PKN per GB
     network_state.logistics_reward_rate = 50 * 10_u64.pow(9); // 50 PKN per delivery
     network_state.agriculture_reward_rate = 25 * 10_u64.pow(9); // 25 PKN per data submission
     network_state.healthcare_reward_rate = 100 * 10_u64.pow(9); // 100 PKN per data submission
     network_state.taxation_reward_rate = 150 * 10_u64.pow(9); // 150 PKN per tax record
+    network_state.power_reward_rate = DEFAULT_POWER_REWARD_RATE;
     network_state.last_reward_distribution = Clock::get()?.unix_timestamp;
     network_state.governance_threshold = 1000 * 10_u64.pow(9); // 1000 PKN to create proposal
     network_state.proposals_count = 0;
+    network_state.last_proposal_time = 0;
+    network_state.treasury = ctx.accounts.treasury.key();
+    network_state.reward_source = RewardSource::Treasury;
+    network_state.current_epoch = 0;
+    network_state.epoch_emission = DEFAULT_EPOCH_EMISSION;
+    network_state.sector_emission_bps = DEFAULT_SECTOR_EMISSION_BPS;
+    network_state.h3_resolution = DEFAULT_H3_RESOLUTION;
+    network_state.hex_density_target = DEFAULT_HEX_DENSITY_TARGET;
+    network_state.quorum_bps = DEFAULT_QUORUM_BPS;
+    network_state.approval_threshold_bps = DEFAULT_APPROVAL_THRESHOLD_BPS;
+    network_state.security_council = ctx.accounts.authority.key();
+    network_state.timelock_delay = DEFAULT_TIMELOCK_DELAY;
+    network_state.execution_window = DEFAULT_EXECUTION_WINDOW;
+    network_state.proposal_deposit = DEFAULT_PROPOSAL_DEPOSIT;
+    network_state.next_asset_id = 0;
     network_state.bump = ctx.bumps.network_state;
+    network_state.treasury_bump = ctx.bumps.treasury;
+
+    // Open the first reward epoch
+    let reward_epoch = &mut ctx.accounts.reward_epoch;
+    reward_epoch.id = 0;
+    reward_epoch.start_time = network_state.last_reward_distribution;
+    reward_epoch.end_time = 0;
+    reward_epoch.emission_budget = 0;
+    reward_epoch.sector_budgets = [0; SECTOR_COUNT];
+    reward_epoch.sector_contributions = [0; SECTOR_COUNT];
+    reward_epoch.finalized = false;
+    reward_epoch.bump = ctx.bumps.reward_epoch;
+
+    // Seed the treasury with its share of the total supply
+    let treasury_allocation = mul_div(total_supply, TREASURY_ALLOCATION_BPS, BASIS_POINTS)?;
+    mint_reward(
+        &ctx.accounts.mint,
+        &ctx.accounts.treasury,
+        &ctx.accounts.network_state,
+        &ctx.accounts.token_program,
+        treasury_allocation,
+    )?;
 
     msg!("Pakistani DePIN Network initialized successfully!");
     msg!("Total supply: {} PKN", total_supply / 10_u64.pow(9));
-    msg!("WiFi reward rate: {} PKN per GB", network_state.wifi_reward_rate / 10_u64.pow(9));
-    msg!("Logistics reward rate: {} PKN per delivery", network_state.logistics_reward_rate / 10_u64.pow(9));
-    msg!("Agriculture reward rate: {} PKN per data submission", network_state.agriculture_reward_rate / 10_u64.pow(9));
+    msg!("Treasury allocation: {} PKN", treasury_allocation / 10_u64.pow(9));
+    msg!("WiFi reward rate: {} PKN per GB", ctx.accounts.network_state.wifi_reward_rate / 10_u64.pow(9));
+    msg!("Logistics reward rate: {} PKN per delivery", ctx.accounts.network_state.logistics_reward_rate / 10_u64.pow(9));
+    msg!("Agriculture reward rate: {} PKN per data submission", ctx.accounts.network_state.agriculture_reward_rate / 10_u64.pow(9));
+
+    emit_cpi!(NetworkInitialized {
+        authority: ctx.accounts.authority.key(),
+        mint: ctx.accounts.mint.key(),
+        treasury: ctx.accounts.treasury.key(),
+        total_supply,
+        treasury_allocation,
+    });
 
     Ok(())
 }
\ No newline at end of file
@@ -0,0 +1,1430 @@
+use anchor_lang::prelude::*;
+use anchor_spl::metadata::Metadata;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::*;
+use crate::events::*;
+use crate::utils::{
+    burn_asset_nft, hex_density_multiplier_bps, plot_contains, validate_h3_cell,
+    validate_micro_degree_coordinates, SafeMath,
+};
+
+// Network and per-owner asset counters track active assets: deactivating or
+// closing an active asset releases its count, reactivating takes it back within
+// the same per-owner limits as registration. The
+// NFT holder signs; the per-owner count stays with the owner of record until the
+// holder claims the asset.
+
+/// Add or remove one active asset from the network and owner counters
+fn update_asset_counts(
+    network_state: &mut NetworkState,
+    user_profile: &mut UserProfile,
+    sector: Sector,
+    active: bool,
+) -> Result<()> {
+    let (network_count, user_count, user_limit) = match sector {
+        Sector::WiFi => (&mut network_state.wifi_hotspots_count, &mut user_profile.wifi_hotspots, MAX_HOTSPOTS_PER_USER),
+        Sector::Logistics => (&mut network_state.logistics_partners_count, &mut user_profile.logistics_partners, MAX_LOGISTICS_PARTNERS_PER_USER),
+        Sector::Agriculture => (&mut network_state.farms_count, &mut user_profile.farms, MAX_FARMS_PER_USER),
+        Sector::Healthcare => (&mut network_state.healthcare_providers_count, &mut user_profile.healthcare_providers, MAX_HEALTHCARE_PROVIDERS_PER_USER),
+        Sector::Taxation => (&mut network_state.tax_points_count, &mut user_profile.tax_points, MAX_TAX_POINTS_PER_USER),
+        Sector::Power => (&mut network_state.ev_chargers_count, &mut user_profile.ev_chargers, MAX_EV_CHARGERS_PER_USER),
+    };
+
+    if active {
+        require!(*user_count < user_limit, DePINError::TooManyAssets);
+        *network_count = network_count.safe_add(1)?;
+        *user_count = user_count.safe_add(1)?;
+    } else {
+        *network_count = network_count.safe_sub(1)?;
+        *user_count = user_count.safe_sub(1)?;
+    }
+    Ok(())
+}
+
+/// Flip an asset's `is_active` flag and update the counters to match
+fn set_asset_active(
+    is_active: &mut bool,
+    network_state: &mut NetworkState,
+    user_profile: &mut UserProfile,
+    sector: Sector,
+    active: bool,
+) -> Result<()> {
+    if active {
+        require!(!*is_active, DePINError::AssetAlreadyActive);
+    } else {
+        require!(*is_active, DePINError::AssetInactive);
+    }
+
+    *is_active = active;
+    update_asset_counts(network_state, user_profile, sector, active)
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(location: String, latitude: i32, longitude: i32, h3_index: u64)]
+pub struct UpdateWiFiHotspot<'info> {
+    #[account(
+        mut,
+        seeds = [WIFI_HOTSPOT_SEED, &wifi_hotspot.asset_id.to_le_bytes()],
+        bump = wifi_hotspot.bump
+    )]
+    pub wifi_hotspot: Account<'info, WiFiHotspot>,
+    
+    #[account(
+        token::mint = wifi_hotspot.nft_mint,
+        token::authority = owner,
+        constraint = owner_nft.amount == 1 @ DePINError::NotAssetHolder
+    )]
+    pub owner_nft: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [HEX_CELL_SEED, &wifi_hotspot.h3_index.to_le_bytes()],
+        bump = current_hex_cell.bump
+    )]
+    pub current_hex_cell: Account<'info, HexCell>,
+    
+    // Same account as `current_hex_cell` if the hotspot stays in its cell
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = HexCell::LEN,
+        seeds = [HEX_CELL_SEED, &h3_index.to_le_bytes()],
+        bump
+    )]
+    pub new_hex_cell: Account<'info, HexCell>,
+    
+    #[account(
+        seeds = [NETWORK_STATE_SEED],
+        bump = network_state.bump
+    )]
+    pub network_state: Account<'info, NetworkState>,
+    
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Move a hotspot or change its radio settings. A hotspot that changes hex takes
+/// the density multiplier of its new cell.
+pub fn update_wifi_hotspot(
+    ctx: Context<UpdateWiFiHotspot>,
+    location: String,
+    latitude: i32,
+    longitude: i32,
+    h3_index: u64,
+    coverage_radius: u32,
+    bandwidth_mbps: u32,
+) -> Result<()> {
+    require!(location.len() <= WiFiHotspot::MAX_LOCATION_LEN, DePINError::LocationTooLong);
+    require!(validate_micro_degree_coordinates(latitude, longitude), DePINError::InvalidCoordinates);
+    require!(
+        validate_h3_cell(h3_index, latitude, longitude, ctx.accounts.network_state.h3_resolution),
+        DePINError::InvalidH3Index
+    );
+    require!(coverage_radius > 0 && coverage_radius <= 5000, DePINError::InvalidCoverageRadius);
+    require!((10..=1000).contains(&bandwidth_mbps), DePINError::InvalidBandwidth);
+
+    let wifi_hotspot = &mut ctx.accounts.wifi_hotspot;
+    let current_hex_cell = &mut ctx.accounts.current_hex_cell;
+    let new_hex_cell = &mut ctx.accounts.new_hex_cell;
+    let network_state = &ctx.accounts.network_state;
+
+    if new_hex_cell.h3_index == 0 {
+        new_hex_cell.h3_index = h3_index;
+        new_hex_cell.resolution = network_state.h3_resolution;
+        new_hex_cell.active_hotspots = 0;
+        new_hex_cell.bump = ctx.bumps.new_hex_cell;
+    }
+
+    // Inactive hotspots are not counted in any cell; they pick up the
+    // multiplier of their cell on reactivation
+    if h3_index != wifi_hotspot.h3_index && wifi_hotspot.is_active {
+        current_hex_cell.active_hotspots = current_hex_cell.active_hotspots.safe_sub(1)?;
+        new_hex_cell.active_hotspots = new_hex_cell.active_hotspots.safe_add(1)?;
+        wifi_hotspot.reward_multiplier_bps =
+            hex_density_multiplier_bps(new_hex_cell.active_hotspots, network_state.hex_density_target);
+    }
+
+    wifi_hotspot.location = location.clone();
+    wifi_hotspot.latitude = latitude;
+    wifi_hotspot.longitude = longitude;
+    wifi_hotspot.h3_index = h3_index;
+    wifi_hotspot.coverage_radius = coverage_radius;
+    wifi_hotspot.bandwidth_mbps = bandwidth_mbps;
+
+    msg!("WiFi hotspot updated at {}", location);
+    msg!("H3 cell: {:x}, Reward multiplier: {} bps", h3_index, wifi_hotspot.reward_multiplier_bps);
+
+    emit_cpi!(AssetUpdated {
+        owner: wifi_hotspot.owner,
+        asset: wifi_hotspot.key(),
+        sector: Sector::WiFi,
+    });
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetWiFiHotspotStatus<'info> {
+    #[account(
+        mut,
+        seeds = [WIFI_HOTSPOT_SEED, &wifi_hotspot.asset_id.to_le_bytes()],
+        bump = wifi_hotspot.bump
+    )]
+    pub wifi_hotspot: Account<'info, WiFiHotspot>,
+    
+    #[account(
+        token::mint = wifi_hotspot.nft_mint,
+        token::authority = owner,
+        constraint = owner_nft.amount == 1 @ DePINError::NotAssetHolder
+    )]
+    pub owner_nft: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [HEX_CELL_SEED, &wifi_hotspot.h3_index.to_le_bytes()],
+        bump = hex_cell.bump
+    )]
+    pub hex_cell: Account<'info, HexCell>,
+    
+    #[account(
+        mut,
+        seeds = [NETWORK_STATE_SEED],
+        bump = network_state.bump
+    )]
+    pub network_state: Account<'info, NetworkState>,
+    
+    #[account(
+        mut,
+        seeds = [USER_PROFILE_SEED, wifi_hotspot.owner.as_ref()],
+        bump = user_profile.bump
+    )]
+    pub user_profile: Account<'info, UserProfile>,
+    
+    pub owner: Signer<'info>,
+}
+
+/// Take a hotspot out of service. It stops earning and no longer counts towards its hex.
+pub fn deactivate_wifi_hotspot(ctx: Context<SetWiFiHotspotStatus>) -> Result<()> {
+    let wifi_hotspot = &mut ctx.accounts.wifi_hotspot;
+    let hex_cell = &mut ctx.accounts.hex_cell;
+
+    set_asset_active(
+        &mut wifi_hotspot.is_active,
+        &mut ctx.accounts.network_state,
+        &mut ctx.accounts.user_profile,
+        Sector::WiFi,
+        false,
+    )?;
+    hex_cell.active_hotspots = hex_cell.active_hotspots.safe_sub(1)?;
+
+    msg!("WiFi hotspot {} deactivated", wifi_hotspot.key());
+
+    emit_cpi!(AssetStatusChanged {
+        owner: wifi_hotspot.owner,
+        asset: wifi_hotspot.key(),
+        sector: Sector::WiFi,
+        is_active: false,
+    });
+
+    Ok(())
+}
+
+/// Return a hotspot to service at the current density multiplier of its hex
+pub fn reactivate_wifi_hotspot(ctx: Context<SetWiFiHotspotStatus>) -> Result<()> {
+    let wifi_hotspot = &mut ctx.accounts.wifi_hotspot;
+    let hex_cell = &mut ctx.accounts.hex_cell;
+
+    set_asset_active(
+        &mut wifi_hotspot.is_active,
+        &mut ctx.accounts.network_state,
+        &mut ctx.accounts.user_profile,
+        Sector::WiFi,
+        true,
+    )?;
+    hex_cell.active_hotspots = hex_cell.active_hotspots.safe_add(1)?;
+    wifi_hotspot.reward_multiplier_bps =
+        hex_density_multiplier_bps(hex_cell.active_hotspots, ctx.accounts.network_state.hex_density_target);
+
+    msg!("WiFi hotspot {} reactivated", wifi_hotspot.key());
+    msg!("Hotspots in cell: {}, Reward multiplier: {} bps",
+         hex_cell.active_hotspots, wifi_hotspot.reward_multiplier_bps);
+
+    emit_cpi!(AssetStatusChanged {
+        owner: wifi_hotspot.owner,
+        asset: wifi_hotspot.key(),
+        sector: Sector::WiFi,
+        is_active: true,
+    });
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CloseWiFiHotspot<'info> {
+    #[account(
+        mut,
+        seeds = [WIFI_HOTSPOT_SEED, &wifi_hotspot.asset_id.to_le_bytes()],
+        bump = wifi_hotspot.bump,
+        close = owner
+    )]
+    pub wifi_hotspot: Account<'info, WiFiHotspot>,
+    
+    #[account(
+        mut,
+        token::mint = wifi_hotspot.nft_mint,
+        token::authority = owner,
+        constraint = owner_nft.amount == 1 @ DePINError::NotAssetHolder
+    )]
+    pub owner_nft: Account<'info, TokenAccount>,
+    
+    #[account(mut, address = wifi_hotspot.nft_mint)]
+    pub nft_mint: Account<'info, Mint>,
+    
+    /// CHECK: Metadata PDA, closed by the token metadata program
+    #[account(
+        mut,
+        seeds = [METADATA_SEED, token_metadata_program.key().as_ref(), nft_mint.key().as_ref()],
+        bump,
+        seeds::program = token_metadata_program.key()
+    )]
+    pub nft_metadata: UncheckedAccount<'info>,
+    
+    /// CHECK: Master edition PDA, closed by the token metadata program
+    #[account(
+        mut,
+        seeds = [METADATA_SEED, token_metadata_program.key().as_ref(), nft_mint.key().as_ref(), EDITION_SEED],
+        bump,
+        seeds::program = token_metadata_program.key()
+    )]
+    pub nft_master_edition: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        seeds = [HEX_CELL_SEED, &wifi_hotspot.h3_index.to_le_bytes()],
+        bump = hex_cell.bump
+    )]
+    pub hex_cell: Account<'info, HexCell>,
+    
+    #[account(
+        mut,
+        seeds = [NETWORK_STATE_SEED],
+        bump = network_state.bump
+    )]
+    pub network_state: Account<'info, NetworkState>,
+    
+    #[account(
+        mut,
+        seeds = [USER_PROFILE_SEED, wifi_hotspot.owner.as_ref()],
+        bump = user_profile.bump
+    )]
+    pub user_profile: Account<'info, UserProfile>,
+    
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    pub token_metadata_program: Program<'info, Metadata>,
+}
+
+/// Close a hotspot account and reclaim its rent. Open coverage challenges must be
+/// resolved first so their bonds can be settled.
+pub fn close_wifi_hotspot(ctx: Context<CloseWiFiHotspot>) -> Result<()> {
+    let wifi_hotspot = &ctx.accounts.wifi_hotspot;
+    require!(wifi_hotspot.open_challenges == 0, DePINError::ChallengesOpen);
+
+    if wifi_hotspot.is_active {
+        update_asset_counts(&mut ctx.accounts.network_state, &mut ctx.accounts.user_profile, Sector::WiFi, false)?;
+        ctx.accounts.hex_cell.active_hotspots = ctx.accounts.hex_cell.active_hotspots.safe_sub(1)?;
+    }
+
+    burn_asset_nft(
+        &ctx.accounts.owner,
+        &ctx.accounts.nft_mint,
+        &ctx.accounts.owner_nft,
+        &ctx.accounts.nft_metadata,
+        &ctx.accounts.nft_master_edition,
+        &ctx.accounts.token_program,
+        &ctx.accounts.token_metadata_program,
+    )?;
+
+    msg!("WiFi hotspot {} closed", wifi_hotspot.key());
+
+    emit_cpi!(AssetClosed {
+        owner: wifi_hotspot.owner,
+        asset: wifi_hotspot.key(),
+        sector: Sector::WiFi,
+    });
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateLogisticsPartner<'info> {
+    #[account(
+        mut,
+        seeds = [LOGISTICS_PARTNER_SEED, &logistics_partner.asset_id.to_le_bytes()],
+        bump = logistics_partner.bump
+    )]
+    pub logistics_partner: Account<'info, LogisticsPartner>,
+    
+    #[account(
+        token::mint = logistics_partner.nft_mint,
+        token::authority = owner,
+        constraint = owner_nft.amount == 1 @ DePINError::NotAssetHolder
+    )]
+    pub owner_nft: Account<'info, TokenAccount>,
+    
+    pub owner: Signer<'info>,
+}
+
+pub fn update_logistics_partner(
+    ctx: Context<UpdateLogisticsPartner>,
+    partner_name: String,
+    service_areas: Vec<String>,
+    vehicle_count: u32,
+) -> Result<()> {
+    require!(partner_name.len() <= LogisticsPartner::MAX_NAME_LEN, DePINError::NameTooLong);
+    require!(service_areas.len() <= LogisticsPartner::MAX_SERVICE_AREAS, DePINError::TooManyServiceAreas);
+    require!(vehicle_count > 0 && vehicle_count <= 1000, DePINError::InvalidVehicleCount);
+
+    for area in &service_areas {
+        require!(area.len() <= LogisticsPartner::MAX_AREA_LEN, DePINError::ServiceAreaTooLong);
+    }
+
+    let logistics_partner = &mut ctx.accounts.logistics_partner;
+
+    logistics_partner.partner_name = partner_name.clone();
+    logistics_partner.service_areas = service_areas;
+    logistics_partner.vehicle_count = vehicle_count;
+
+    msg!("Logistics partner '{}' updated", partner_name);
+    msg!("Vehicles: {}, Service areas: {}", vehicle_count, logistics_partner.service_areas.len());
+
+    emit_cpi!(AssetUpdated {
+        owner: logistics_partner.owner,
+        asset: logistics_partner.key(),
+        sector: Sector::Logistics,
+    });
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetLogisticsPartnerStatus<'info> {
+    #[account(
+        mut,
+        seeds = [LOGISTICS_PARTNER_SEED, &logistics_partner.asset_id.to_le_bytes()],
+        bump = logistics_partner.bump
+    )]
+    pub logistics_partner: Account<'info, LogisticsPartner>,
+    
+    #[account(
+        token::mint = logistics_partner.nft_mint,
+        token::authority = owner,
+        constraint = owner_nft.amount == 1 @ DePINError::NotAssetHolder
+    )]
+    pub owner_nft: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [NETWORK_STATE_SEED],
+        bump = network_state.bump
+    )]
+    pub network_state: Account<'info, NetworkState>,
+    
+    #[account(
+        mut,
+        seeds = [USER_PROFILE_SEED, logistics_partner.owner.as_ref()],
+        bump = user_profile.bump
+    )]
+    pub user_profile: Account<'info, UserProfile>,
+    
+    pub owner: Signer<'info>,
+}
+
+pub fn deactivate_logistics_partner(ctx: Context<SetLogisticsPartnerStatus>) -> Result<()> {
+    set_logistics_partner_status(ctx, false)
+}
+
+pub fn reactivate_logistics_partner(ctx: Context<SetLogisticsPartnerStatus>) -> Result<()> {
+    set_logistics_partner_status(ctx, true)
+}
+
+fn set_logistics_partner_status(ctx: Context<SetLogisticsPartnerStatus>, active: bool) -> Result<()> {
+    let logistics_partner = &mut ctx.accounts.logistics_partner;
+
+    set_asset_active(
+        &mut logistics_partner.is_active,
+        &mut ctx.accounts.network_state,
+        &mut ctx.accounts.user_profile,
+        Sector::Logistics,
+        active,
+    )?;
+
+    msg!("Logistics partner {} active: {}", logistics_partner.key(), active);
+
+    emit_cpi!(AssetStatusChanged {
+        owner: logistics_partner.owner,
+        asset: logistics_partner.key(),
+        sector: Sector::Logistics,
+        is_active: active,
+    });
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CloseLogisticsPartner<'info> {
+    #[account(
+        mut,
+        seeds = [LOGISTICS_PARTNER_SEED, &logistics_partner.asset_id.to_le_bytes()],
+        bump = logistics_partner.bump,
+        close = owner
+    )]
+    pub logistics_partner: Account<'info, LogisticsPartner>,
+    
+    #[account(
+        mut,
+        token::mint = logistics_partner.nft_mint,
+        token::authority = owner,
+        constraint = owner_nft.amount == 1 @ DePINError::NotAssetHolder
+    )]
+    pub owner_nft: Account<'info, TokenAccount>,
+    
+    #[account(mut, address = logistics_partner.nft_mint)]
+    pub nft_mint: Account<'info, Mint>,
+    
+    /// CHECK: Metadata PDA, closed by the token metadata program
+    #[account(
+        mut,
+        seeds = [METADATA_SEED, token_metadata_program.key().as_ref(), nft_mint.key().as_ref()],
+        bump,
+        seeds::program = token_metadata_program.key()
+    )]
+    pub nft_metadata: UncheckedAccount<'info>,
+    
+    /// CHECK: Master edition PDA, closed by the token metadata program
+    #[account(
+        mut,
+        seeds = [METADATA_SEED, token_metadata_program.key().as_ref(), nft_mint.key().as_ref(), EDITION_SEED],
+        bump,
+        seeds::program = token_metadata_program.key()
+    )]
+    pub nft_master_edition: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        seeds = [NETWORK_STATE_SEED],
+        bump = network_state.bump
+    )]
+    pub network_state: Account<'info, NetworkState>,
+    
+    #[account(
+        mut,
+        seeds = [USER_PROFILE_SEED, logistics_partner.owner.as_ref()],
+        bump = user_profile.bump
+    )]
+    pub user_profile: Account<'info, UserProfile>,
+    
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    pub token_metadata_program: Program<'info, Metadata>,
+}
+
+pub fn close_logistics_partner(ctx: Context<CloseLogisticsPartner>) -> Result<()> {
+    let logistics_partner = &ctx.accounts.logistics_partner;
+
+    if logistics_partner.is_active {
+        update_asset_counts(&mut ctx.accounts.network_state, &mut ctx.accounts.user_profile, Sector::Logistics, false)?;
+    }
+
+    burn_asset_nft(
+        &ctx.accounts.owner,
+        &ctx.accounts.nft_mint,
+        &ctx.accounts.owner_nft,
+        &ctx.accounts.nft_metadata,
+        &ctx.accounts.nft_master_edition,
+        &ctx.accounts.token_program,
+        &ctx.accounts.token_metadata_program,
+    )?;
+
+    msg!("Logistics partner {} closed", logistics_partner.key());
+
+    emit_cpi!(AssetClosed {
+        owner: logistics_partner.owner,
+        asset: logistics_partner.key(),
+        sector: Sector::Logistics,
+    });
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateFarm<'info> {
+    #[account(
+        mut,
+        seeds = [FARM_SEED, &farm.asset_id.to_le_bytes()],
+        bump = farm.bump
+    )]
+    pub farm: Account<'info, Farm>,
+    
+    #[account(
+        token::mint = farm.nft_mint,
+        token::authority = owner,
+        constraint = owner_nft.amount == 1 @ DePINError::NotAssetHolder
+    )]
+    pub owner_nft: Account<'info, TokenAccount>,
+    
+    pub owner: Signer<'info>,
+}
+
+pub fn update_farm(
+    ctx: Context<UpdateFarm>,
+    farm_name: String,
+    location: String,
+    latitude: i32,
+    longitude: i32,
+    farm_size_acres: u32,
+    crop_types: Vec<String>,
+) -> Result<()> {
+    require!(farm_name.len() <= Farm::MAX_NAME_LEN, DePINError::NameTooLong);
+    require!(location.len() <= Farm::MAX_LOCATION_LEN, DePINError::LocationTooLong);
+    require!(validate_micro_degree_coordinates(latitude, longitude), DePINError::InvalidCoordinates);
+    require!(farm_size_acres > 0 && farm_size_acres <= 10000, DePINError::InvalidFarmSize);
+    require!(crop_types.len() <= Farm::MAX_CROP_TYPES, DePINError::TooManyCropTypes);
+
+    for crop in &crop_types {
+        require!(crop.len() <= Farm::MAX_CROP_LEN, DePINError::CropNameTooLong);
+    }
+
+    let farm = &mut ctx.accounts.farm;
+
+    farm.farm_name = farm_name.clone();
+    farm.location = location;
+    farm.latitude = latitude;
+    farm.longitude = longitude;
+    farm.farm_size_acres = farm_size_acres;
+    farm.crop_types = crop_types;
+
+    msg!("Farm '{}' updated", farm_name);
+    msg!("Size: {} acres, Crops: {:?}", farm_size_acres, farm.crop_types);
+
+    emit_cpi!(AssetUpdated {
+        owner: farm.owner,
+        asset: farm.key(),
+        sector: Sector::Agriculture,
+    });
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetFarmStatus<'info> {
+    #[account(
+        mut,
+        seeds = [FARM_SEED, &farm.asset_id.to_le_bytes()],
+        bump = farm.bump
+    )]
+    pub farm: Account<'info, Farm>,
+    
+    #[account(
+        token::mint = farm.nft_mint,
+        token::authority = owner,
+        constraint = owner_nft.amount == 1 @ DePINError::NotAssetHolder
+    )]
+    pub owner_nft: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [NETWORK_STATE_SEED],
+        bump = network_state.bump
+    )]
+    pub network_state: Account<'info, NetworkState>,
+    
+    #[account(
+        mut,
+        seeds = [USER_PROFILE_SEED, farm.owner.as_ref()],
+        bump = user_profile.bump
+    )]
+    pub user_profile: Account<'info, UserProfile>,
+    
+    pub owner: Signer<'info>,
+}
+
+pub fn deactivate_farm(ctx: Context<SetFarmStatus>) -> Result<()> {
+    set_farm_status(ctx, false)
+}
+
+pub fn reactivate_farm(ctx: Context<SetFarmStatus>) -> Result<()> {
+    set_farm_status(ctx, true)
+}
+
+fn set_farm_status(ctx: Context<SetFarmStatus>, active: bool) -> Result<()> {
+    let farm = &mut ctx.accounts.farm;
+
+    set_asset_active(
+        &mut farm.is_active,
+        &mut ctx.accounts.network_state,
+        &mut ctx.accounts.user_profile,
+        Sector::Agriculture,
+        active,
+    )?;
+
+    msg!("Farm {} active: {}", farm.key(), active);
+
+    emit_cpi!(AssetStatusChanged {
+        owner: farm.owner,
+        asset: farm.key(),
+        sector: Sector::Agriculture,
+        is_active: active,
+    });
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CloseFarm<'info> {
+    #[account(
+        mut,
+        seeds = [FARM_SEED, &farm.asset_id.to_le_bytes()],
+        bump = farm.bump,
+        close = owner
+    )]
+    pub farm: Account<'info, Farm>,
+    
+    #[account(
+        mut,
+        token::mint = farm.nft_mint,
+        token::authority = owner,
+        constraint = owner_nft.amount == 1 @ DePINError::NotAssetHolder
+    )]
+    pub owner_nft: Account<'info, TokenAccount>,
+    
+    #[account(mut, address = farm.nft_mint)]
+    pub nft_mint: Account<'info, Mint>,
+    
+    /// CHECK: Metadata PDA, closed by the token metadata program
+    #[account(
+        mut,
+        seeds = [METADATA_SEED, token_metadata_program.key().as_ref(), nft_mint.key().as_ref()],
+        bump,
+        seeds::program = token_metadata_program.key()
+    )]
+    pub nft_metadata: UncheckedAccount<'info>,
+    
+    /// CHECK: Master edition PDA, closed by the token metadata program
+    #[account(
+        mut,
+        seeds = [METADATA_SEED, token_metadata_program.key().as_ref(), nft_mint.key().as_ref(), EDITION_SEED],
+        bump,
+        seeds::program = token_metadata_program.key()
+    )]
+    pub nft_master_edition: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        seeds = [NETWORK_STATE_SEED],
+        bump = network_state.bump
+    )]
+    pub network_state: Account<'info, NetworkState>,
+    
+    #[account(
+        mut,
+        seeds = [USER_PROFILE_SEED, farm.owner.as_ref()],
+        bump = user_profile.bump
+    )]
+    pub user_profile: Account<'info, UserProfile>,
+    
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    pub token_metadata_program: Program<'info, Metadata>,
+}
+
+pub fn close_farm(ctx: Context<CloseFarm>) -> Result<()> {
+    let farm = &ctx.accounts.farm;
+
+    if farm.is_active {
+        update_asset_counts(&mut ctx.accounts.network_state, &mut ctx.accounts.user_profile, Sector::Agriculture, false)?;
+    }
+
+    burn_asset_nft(
+        &ctx.accounts.owner,
+        &ctx.accounts.nft_mint,
+        &ctx.accounts.owner_nft,
+        &ctx.accounts.nft_metadata,
+        &ctx.accounts.nft_master_edition,
+        &ctx.accounts.token_program,
+        &ctx.accounts.token_metadata_program,
+    )?;
+
+    msg!("Farm {} closed", farm.key());
+
+    emit_cpi!(AssetClosed {
+        owner: farm.owner,
+        asset: farm.key(),
+        sector: Sector::Agriculture,
+    });
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateHealthcareProvider<'info> {
+    #[account(
+        mut,
+        seeds = [HEALTHCARE_PROVIDER_SEED, &healthcare_provider.asset_id.to_le_bytes()],
+        bump = healthcare_provider.bump
+    )]
+    pub healthcare_provider: Account<'info, HealthcareProvider>,
+    
+    #[account(
+        token::mint = healthcare_provider.nft_mint,
+        token::authority = owner,
+        constraint = owner_nft.amount == 1 @ DePINError::NotAssetHolder
+    )]
+    pub owner_nft: Account<'info, TokenAccount>,
+    
+    pub owner: Signer<'info>,
+}
+
+pub fn update_healthcare_provider(
+    ctx: Context<UpdateHealthcareProvider>,
+    provider_name: String,
+    provider_type: String,
+    location: String,
+    latitude: i32,
+    longitude: i32,
+    license_number: String,
+) -> Result<()> {
+    require!(provider_name.len() <= HealthcareProvider::MAX_NAME_LEN, DePINError::NameTooLong);
+    require!(provider_type.len() <= HealthcareProvider::MAX_TYPE_LEN, DePINError::NameTooLong);
+    require!(location.len() <= HealthcareProvider::MAX_LOCATION_LEN, DePINError::LocationTooLong);
+    require!(validate_micro_degree_coordinates(latitude, longitude), DePINError::InvalidCoordinates);
+    require!(license_number.len() <= HealthcareProvider::MAX_LICENSE_LEN, DePINError::NameTooLong);
+
+    let healthcare_provider = &mut ctx.accounts.healthcare_provider;
+
+    healthcare_provider.provider_name = provider_name.clone();
+    healthcare_provider.provider_type = provider_type;
+    healthcare_provider.location = location;
+    healthcare_provider.latitude = latitude;
+    healthcare_provider.longitude = longitude;
+    healthcare_provider.license_number = license_number;
+
+    msg!("Healthcare provider '{}' updated", provider_name);
+
+    emit_cpi!(AssetUpdated {
+        owner: healthcare_provider.owner,
+        asset: healthcare_provider.key(),
+        sector: Sector::Healthcare,
+    });
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetHealthcareProviderStatus<'info> {
+    #[account(
+        mut,
+        seeds = [HEALTHCARE_PROVIDER_SEED, &healthcare_provider.asset_id.to_le_bytes()],
+        bump = healthcare_provider.bump
+    )]
+    pub healthcare_provider: Account<'info, HealthcareProvider>,
+    
+    #[account(
+        token::mint = healthcare_provider.nft_mint,
+        token::authority = owner,
+        constraint = owner_nft.amount == 1 @ DePINError::NotAssetHolder
+    )]
+    pub owner_nft: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [NETWORK_STATE_SEED],
+        bump = network_state.bump
+    )]
+    pub network_state: Account<'info, NetworkState>,
+    
+    #[account(
+        mut,
+        seeds = [USER_PROFILE_SEED, healthcare_provider.owner.as_ref()],
+        bump = user_profile.bump
+    )]
+    pub user_profile: Account<'info, UserProfile>,
+    
+    pub owner: Signer<'info>,
+}
+
+pub fn deactivate_healthcare_provider(ctx: Context<SetHealthcareProviderStatus>) -> Result<()> {
+    set_healthcare_provider_status(ctx, false)
+}
+
+pub fn reactivate_healthcare_provider(ctx: Context<SetHealthcareProviderStatus>) -> Result<()> {
+    set_healthcare_provider_status(ctx, true)
+}
+
+fn set_healthcare_provider_status(ctx: Context<SetHealthcareProviderStatus>, active: bool) -> Result<()> {
+    let healthcare_provider = &mut ctx.accounts.healthcare_provider;
+
+    set_asset_active(
+        &mut healthcare_provider.is_active,
+        &mut ctx.accounts.network_state,
+        &mut ctx.accounts.user_profile,
+        Sector::Healthcare,
+        active,
+    )?;
+
+    msg!("Healthcare provider {} active: {}", healthcare_provider.key(), active);
+
+    emit_cpi!(AssetStatusChanged {
+        owner: healthcare_provider.owner,
+        asset: healthcare_provider.key(),
+        sector: Sector::Healthcare,
+        is_active: active,
+    });
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CloseHealthcareProvider<'info> {
+    #[account(
+        mut,
+        seeds = [HEALTHCARE_PROVIDER_SEED, &healthcare_provider.asset_id.to_le_bytes()],
+        bump = healthcare_provider.bump,
+        close = owner
+    )]
+    pub healthcare_provider: Account<'info, HealthcareProvider>,
+    
+    #[account(
+        mut,
+        token::mint = healthcare_provider.nft_mint,
+        token::authority = owner,
+        constraint = owner_nft.amount == 1 @ DePINError::NotAssetHolder
+    )]
+    pub owner_nft: Account<'info, TokenAccount>,
+    
+    #[account(mut, address = healthcare_provider.nft_mint)]
+    pub nft_mint: Account<'info, Mint>,
+    
+    /// CHECK: Metadata PDA, closed by the token metadata program
+    #[account(
+        mut,
+        seeds = [METADATA_SEED, token_metadata_program.key().as_ref(), nft_mint.key().as_ref()],
+        bump,
+        seeds::program = token_metadata_program.key()
+    )]
+    pub nft_metadata: UncheckedAccount<'info>,
+    
+    /// CHECK: Master edition PDA, closed by the token metadata program
+    #[account(
+        mut,
+        seeds = [METADATA_SEED, token_metadata_program.key().as_ref(), nft_mint.key().as_ref(), EDITION_SEED],
+        bump,
+        seeds::program = token_metadata_program.key()
+    )]
+    pub nft_master_edition: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        seeds = [NETWORK_STATE_SEED],
+        bump = network_state.bump
+    )]
+    pub network_state: Account<'info, NetworkState>,
+    
+    #[account(
+        mut,
+        seeds = [USER_PROFILE_SEED, healthcare_provider.owner.as_ref()],
+        bump = user_profile.bump
+    )]
+    pub user_profile: Account<'info, UserProfile>,
+    
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    pub token_metadata_program: Program<'info, Metadata>,
+}
+
+pub fn close_healthcare_provider(ctx: Context<CloseHealthcareProvider>) -> Result<()> {
+    let healthcare_provider = &ctx.accounts.healthcare_provider;
+
+    if healthcare_provider.is_active {
+        update_asset_counts(&mut ctx.accounts.network_state, &mut ctx.accounts.user_profile, Sector::Healthcare, false)?;
+    }
+
+    burn_asset_nft(
+        &ctx.accounts.owner,
+        &ctx.accounts.nft_mint,
+        &ctx.accounts.owner_nft,
+        &ctx.accounts.nft_metadata,
+        &ctx.accounts.nft_master_edition,
+        &ctx.accounts.token_program,
+        &ctx.accounts.token_metadata_program,
+    )?;
+
+    msg!("Healthcare provider {} closed", healthcare_provider.key());
+
+    emit_cpi!(AssetClosed {
+        owner: healthcare_provider.owner,
+        asset: healthcare_provider.key(),
+        sector: Sector::Healthcare,
+    });
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateTaxPoint<'info> {
+    #[account(
+        mut,
+        seeds = [TAX_POINT_SEED, &tax_point.asset_id.to_le_bytes()],
+        bump = tax_point.bump
+    )]
+    pub tax_point: Account<'info, TaxCollectionPoint>,
+    
+    #[account(
+        token::mint = tax_point.nft_mint,
+        token::authority = owner,
+        constraint = owner_nft.amount == 1 @ DePINError::NotAssetHolder
+    )]
+    pub owner_nft: Account<'info, TokenAccount>,
+    
+    pub owner: Signer<'info>,
+}
+
+pub fn update_tax_point(
+    ctx: Context<UpdateTaxPoint>,
+    point_name: String,
+    authority_type: String,
+    location: String,
+    latitude: i32,
+    longitude: i32,
+    jurisdiction: String,
+) -> Result<()> {
+    require!(point_name.len() <= TaxCollectionPoint::MAX_NAME_LEN, DePINError::NameTooLong);
+    require!(authority_type.len() <= TaxCollectionPoint::MAX_TYPE_LEN, DePINError::NameTooLong);
+    require!(location.len() <= TaxCollectionPoint::MAX_LOCATION_LEN, DePINError::LocationTooLong);
+    require!(validate_micro_degree_coordinates(latitude, longitude), DePINError::InvalidCoordinates);
+    require!(jurisdiction.len() <= TaxCollectionPoint::MAX_JURISDICTION_LEN, DePINError::NameTooLong);
+
+    let tax_point = &mut ctx.accounts.tax_point;
+
+    tax_point.point_name = point_name.clone();
+    tax_point.authority_type = authority_type;
+    tax_point.location = location;
+    tax_point.latitude = latitude;
+    tax_point.longitude = longitude;
+    tax_point.jurisdiction = jurisdiction;
+
+    msg!("Tax collection point '{}' updated", point_name);
+
+    emit_cpi!(AssetUpdated {
+        owner: tax_point.owner,
+        asset: tax_point.key(),
+        sector: Sector::Taxation,
+    });
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetTaxPointStatus<'info> {
+    #[account(
+        mut,
+        seeds = [TAX_POINT_SEED, &tax_point.asset_id.to_le_bytes()],
+        bump = tax_point.bump
+    )]
+    pub tax_point: Account<'info, TaxCollectionPoint>,
+    
+    #[account(
+        token::mint = tax_point.nft_mint,
+        token::authority = owner,
+        constraint = owner_nft.amount == 1 @ DePINError::NotAssetHolder
+    )]
+    pub owner_nft: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [NETWORK_STATE_SEED],
+        bump = network_state.bump
+    )]
+    pub network_state: Account<'info, NetworkState>,
+    
+    #[account(
+        mut,
+        seeds = [USER_PROFILE_SEED, tax_point.owner.as_ref()],
+        bump = user_profile.bump
+    )]
+    pub user_profile: Account<'info, UserProfile>,
+    
+    pub owner: Signer<'info>,
+}
+
+pub fn deactivate_tax_point(ctx: Context<SetTaxPointStatus>) -> Result<()> {
+    set_tax_point_status(ctx, false)
+}
+
+pub fn reactivate_tax_point(ctx: Context<SetTaxPointStatus>) -> Result<()> {
+    set_tax_point_status(ctx, true)
+}
+
+fn set_tax_point_status(ctx: Context<SetTaxPointStatus>, active: bool) -> Result<()> {
+    let tax_point = &mut ctx.accounts.tax_point;
+
+    set_asset_active(
+        &mut tax_point.is_active,
+        &mut ctx.accounts.network_state,
+        &mut ctx.accounts.user_profile,
+        Sector::Taxation,
+        active,
+    )?;
+
+    msg!("Tax collection point {} active: {}", tax_point.key(), active);
+
+    emit_cpi!(AssetStatusChanged {
+        owner: tax_point.owner,
+        asset: tax_point.key(),
+        sector: Sector::Taxation,
+        is_active: active,
+    });
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CloseTaxPoint<'info> {
+    #[account(
+        mut,
+        seeds = [TAX_POINT_SEED, &tax_point.asset_id.to_le_bytes()],
+        bump = tax_point.bump,
+        close = owner
+    )]
+    pub tax_point: Account<'info, TaxCollectionPoint>,
+    
+    #[account(
+        mut,
+        token::mint = tax_point.nft_mint,
+        token::authority = owner,
+        constraint = owner_nft.amount == 1 @ DePINError::NotAssetHolder
+    )]
+    pub owner_nft: Account<'info, TokenAccount>,
+    
+    #[account(mut, address = tax_point.nft_mint)]
+    pub nft_mint: Account<'info, Mint>,
+    
+    /// CHECK: Metadata PDA, closed by the token metadata program
+    #[account(
+        mut,
+        seeds = [METADATA_SEED, token_metadata_program.key().as_ref(), nft_mint.key().as_ref()],
+        bump,
+        seeds::program = token_metadata_program.key()
+    )]
+    pub nft_metadata: UncheckedAccount<'info>,
+    
+    /// CHECK: Master edition PDA, closed by the token metadata program
+    #[account(
+        mut,
+        seeds = [METADATA_SEED, token_metadata_program.key().as_ref(), nft_mint.key().as_ref(), EDITION_SEED],
+        bump,
+        seeds::program = token_metadata_program.key()
+    )]
+    pub nft_master_edition: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        seeds = [NETWORK_STATE_SEED],
+        bump = network_state.bump
+    )]
+    pub network_state: Account<'info, NetworkState>,
+    
+    #[account(
+        mut,
+        seeds = [USER_PROFILE_SEED, tax_point.owner.as_ref()],
+        bump = user_profile.bump
+    )]
+    pub user_profile: Account<'info, UserProfile>,
+    
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    pub token_metadata_program: Program<'info, Metadata>,
+}
+
+pub fn close_tax_point(ctx: Context<CloseTaxPoint>) -> Result<()> {
+    let tax_point = &ctx.accounts.tax_point;
+
+    if tax_point.is_active {
+        update_asset_counts(&mut ctx.accounts.network_state, &mut ctx.accounts.user_profile, Sector::Taxation, false)?;
+    }
+
+    burn_asset_nft(
+        &ctx.accounts.owner,
+        &ctx.accounts.nft_mint,
+        &ctx.accounts.owner_nft,
+        &ctx.accounts.nft_metadata,
+        &ctx.accounts.nft_master_edition,
+        &ctx.accounts.token_program,
+        &ctx.accounts.token_metadata_program,
+    )?;
+
+    msg!("Tax collection point {} closed", tax_point.key());
+
+    emit_cpi!(AssetClosed {
+        owner: tax_point.owner,
+        asset: tax_point.key(),
+        sector: Sector::Taxation,
+    });
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateEVCharger<'info> {
+    #[account(
+        mut,
+        seeds = [EV_CHARGER_SEED, &ev_charger.asset_id.to_le_bytes()],
+        bump = ev_charger.bump
+    )]
+    pub ev_charger: Account<'info, EVCharger>,
+    
+    #[account(
+        token::mint = ev_charger.nft_mint,
+        token::authority = owner,
+        constraint = owner_nft.amount == 1 @ DePINError::NotAssetHolder
+    )]
+    pub owner_nft: Account<'info, TokenAccount>,
+    
+    #[account(address = ev_charger.plot)]
+    pub charging_plot: Account<'info, ChargingPlot>,
+    
+    #[account(address = charging_plot.city)]
+    pub plot_city: Account<'info, PlotCity>,
+    
+    pub owner: Signer<'info>,
+}
+
+pub fn update_ev_charger(
+    ctx: Context<UpdateEVCharger>,
+    charger_name: String,
+    charger_type: ChargerType,
+    power_output_kw: u32,
+    latitude: i32,
+    longitude: i32,
+) -> Result<()> {
+    require!(charger_name.len() <= EVCharger::MAX_NAME_LEN, DePINError::NameTooLong);
+    require!(power_output_kw > 0 && power_output_kw <= 350, DePINError::InvalidPowerOutput);
+    require!(validate_micro_degree_coordinates(latitude, longitude), DePINError::InvalidCoordinates);
+
+    // The charger can be adjusted but must stay on its plot
+    let charging_plot = &ctx.accounts.charging_plot;
+    require!(
+        plot_contains(&ctx.accounts.plot_city, charging_plot.row, charging_plot.col, latitude, longitude),
+        DePINError::ChargerOutsidePlot
+    );
+
+    let ev_charger = &mut ctx.accounts.ev_charger;
+    require!(ev_charger.active_session == Pubkey::default(), DePINError::ChargerBusy);
+
+    ev_charger.charger_name = charger_name.clone();
+    ev_charger.charger_type = charger_type;
+    ev_charger.power_output_kw = power_output_kw;
+    ev_charger.latitude = latitude;
+    ev_charger.longitude = longitude;
+
+    msg!("EV charger '{}' updated", charger_name);
+
+    emit_cpi!(AssetUpdated {
+        owner: ev_charger.owner,
+        asset: ev_charger.key(),
+        sector: Sector::Power,
+    });
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetEVChargerStatus<'info> {
+    #[account(
+        mut,
+        seeds = [EV_CHARGER_SEED, &ev_charger.asset_id.to_le_bytes()],
+        bump = ev_charger.bump
+    )]
+    pub ev_charger: Account<'info, EVCharger>,
+    
+    #[account(
+        token::mint = ev_charger.nft_mint,
+        token::authority = owner,
+        constraint = owner_nft.amount == 1 @ DePINError::NotAssetHolder
+    )]
+    pub owner_nft: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [NETWORK_STATE_SEED],
+        bump = network_state.bump
+    )]
+    pub network_state: Account<'info, NetworkState>,
+    
+    #[account(
+        mut,
+        seeds = [USER_PROFILE_SEED, ev_charger.owner.as_ref()],
+        bump = user_profile.bump
+    )]
+    pub user_profile: Account<'info, UserProfile>,
+    
+    pub owner: Signer<'info>,
+}
+
+pub fn deactivate_ev_charger(ctx: Context<SetEVChargerStatus>) -> Result<()> {
+    set_ev_charger_status(ctx, false)
+}
+
+pub fn reactivate_ev_charger(ctx: Context<SetEVChargerStatus>) -> Result<()> {
+    set_ev_charger_status(ctx, true)
+}
+
+fn set_ev_charger_status(ctx: Context<SetEVChargerStatus>, active: bool) -> Result<()> {
+    let ev_charger = &mut ctx.accounts.ev_charger;
+    require!(ev_charger.active_session == Pubkey::default(), DePINError::ChargerBusy);
+
+    set_asset_active(
+        &mut ev_charger.is_active,
+        &mut ctx.accounts.network_state,
+        &mut ctx.accounts.user_profile,
+        Sector::Power,
+        active,
+    )?;
+
+    msg!("EV charger {} active: {}", ev_charger.key(), active);
+
+    emit_cpi!(AssetStatusChanged {
+        owner: ev_charger.owner,
+        asset: ev_charger.key(),
+        sector: Sector::Power,
+        is_active: active,
+    });
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CloseEVCharger<'info> {
+    #[account(
+        mut,
+        seeds = [EV_CHARGER_SEED, &ev_charger.asset_id.to_le_bytes()],
+        bump = ev_charger.bump,
+        close = owner
+    )]
+    pub ev_charger: Account<'info, EVCharger>,
+    
+    #[account(
+        mut,
+        token::mint = ev_charger.nft_mint,
+        token::authority = owner,
+        constraint = owner_nft.amount == 1 @ DePINError::NotAssetHolder
+    )]
+    pub owner_nft: Account<'info, TokenAccount>,
+    
+    #[account(mut, address = ev_charger.nft_mint)]
+    pub nft_mint: Account<'info, Mint>,
+    
+    /// CHECK: Metadata PDA, closed by the token metadata program
+    #[account(
+        mut,
+        seeds = [METADATA_SEED, token_metadata_program.key().as_ref(), nft_mint.key().as_ref()],
+        bump,
+        seeds::program = token_metadata_program.key()
+    )]
+    pub nft_metadata: UncheckedAccount<'info>,
+    
+    /// CHECK: Master edition PDA, closed by the token metadata program
+    #[account(
+        mut,
+        seeds = [METADATA_SEED, token_metadata_program.key().as_ref(), nft_mint.key().as_ref(), EDITION_SEED],
+        bump,
+        seeds::program = token_metadata_program.key()
+    )]
+    pub nft_master_edition: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        seeds = [NETWORK_STATE_SEED],
+        bump = network_state.bump
+    )]
+    pub network_state: Account<'info, NetworkState>,
+    
+    #[account(
+        mut,
+        seeds = [USER_PROFILE_SEED, ev_charger.owner.as_ref()],
+        bump = user_profile.bump
+    )]
+    pub user_profile: Account<'info, UserProfile>,
+    
+    #[account(mut, address = ev_charger.plot)]
+    pub charging_plot: Account<'info, ChargingPlot>,
+    
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    pub token_metadata_program: Program<'info, Metadata>,
+}
+
+pub fn close_ev_charger(ctx: Context<CloseEVCharger>) -> Result<()> {
+    let ev_charger = &ctx.accounts.ev_charger;
+    require!(ev_charger.active_session == Pubkey::default(), DePINError::ChargerBusy);
+
+    if ev_charger.is_active {
+        update_asset_counts(&mut ctx.accounts.network_state, &mut ctx.accounts.user_profile, Sector::Power, false)?;
+    }
+
+    // Free the plot so its owner can install or sell again
+    ctx.accounts.charging_plot.charger = Pubkey::default();
+
+    burn_asset_nft(
+        &ctx.accounts.owner,
+        &ctx.accounts.nft_mint,
+        &ctx.accounts.owner_nft,
+        &ctx.accounts.nft_metadata,
+        &ctx.accounts.nft_master_edition,
+        &ctx.accounts.token_program,
+        &ctx.accounts.token_metadata_program,
+    )?;
+
+    msg!("EV charger {} closed", ev_charger.key());
+
+    emit_cpi!(AssetClosed {
+        owner: ev_charger.owner,
+        asset: ev_charger.key(),
+        sector: Sector::Power,
+    });
+
+    Ok(())
+}
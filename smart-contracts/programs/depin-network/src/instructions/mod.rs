@@ -1,13 +1,27 @@
 pub mod initialize;
 pub mod infrastructure;
 pub mod healthcare_taxation;
+pub mod power;
+pub mod plots;
 pub mod staking;
 pub mod governance;
 pub mod rewards;
+pub mod treasury;
+pub mod coverage;
+pub mod delegation;
+pub mod lifecycle;
+pub mod ownership;
 
 pub use initialize::*;
 pub use infrastructure::*;
 pub use healthcare_taxation::*;
+pub use power::*;
+pub use plots::*;
 pub use staking::*;
 pub use governance::*;
-pub use rewards::*;
\ No newline at end of file
+pub use rewards::*;
+pub use treasury::*;
+pub use coverage::*;
+pub use delegation::*;
+pub use lifecycle::*;
+pub use ownership::*;
\ No newline at end of file
@@ -0,0 +1,414 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::*;
+use crate::events::*;
+use crate::utils::{init_user_profile_if_needed, SafeMath};
+
+// An asset's NFT carries its operating rights: whoever holds it can submit data
+// and manage the asset, whether or not they have claimed it. Claiming is optional
+// bookkeeping that moves the owner record and its per-owner count to the holder's
+// profile. The asset keeps its address, device key and history.
+
+/// Move one active asset from the previous owner's counter to the new owner's.
+/// Per-owner limits apply to registration and reactivation only, so a claim never
+/// fails on them.
+fn move_asset_count(from: &mut UserProfile, to: &mut UserProfile, sector: Sector) -> Result<()> {
+    let (from_count, to_count) = match sector {
+        Sector::WiFi => (&mut from.wifi_hotspots, &mut to.wifi_hotspots),
+        Sector::Logistics => (&mut from.logistics_partners, &mut to.logistics_partners),
+        Sector::Agriculture => (&mut from.farms, &mut to.farms),
+        Sector::Healthcare => (&mut from.healthcare_providers, &mut to.healthcare_providers),
+        Sector::Taxation => (&mut from.tax_points, &mut to.tax_points),
+        Sector::Power => (&mut from.ev_chargers, &mut to.ev_chargers),
+    };
+
+    *from_count = from_count.safe_sub(1)?;
+    *to_count = to_count.safe_add(1)?;
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ClaimWiFiHotspotOwnership<'info> {
+    #[account(
+        mut,
+        seeds = [WIFI_HOTSPOT_SEED, &wifi_hotspot.asset_id.to_le_bytes()],
+        bump = wifi_hotspot.bump,
+        constraint = wifi_hotspot.owner != new_owner.key() @ DePINError::InvalidNewOwner
+    )]
+    pub wifi_hotspot: Account<'info, WiFiHotspot>,
+    
+    #[account(
+        token::mint = wifi_hotspot.nft_mint,
+        token::authority = new_owner,
+        constraint = new_owner_nft.amount == 1 @ DePINError::NotAssetHolder
+    )]
+    pub new_owner_nft: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [USER_PROFILE_SEED, wifi_hotspot.owner.as_ref()],
+        bump = previous_owner_profile.bump
+    )]
+    pub previous_owner_profile: Account<'info, UserProfile>,
+    
+    #[account(
+        init_if_needed,
+        payer = new_owner,
+        space = UserProfile::LEN,
+        seeds = [USER_PROFILE_SEED, new_owner.key().as_ref()],
+        bump
+    )]
+    pub new_owner_profile: Account<'info, UserProfile>,
+    
+    #[account(mut)]
+    pub new_owner: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn claim_wifi_hotspot_ownership(ctx: Context<ClaimWiFiHotspotOwnership>) -> Result<()> {
+    let wifi_hotspot = &mut ctx.accounts.wifi_hotspot;
+    let new_owner_profile = &mut ctx.accounts.new_owner_profile;
+    let previous_owner = wifi_hotspot.owner;
+
+    init_user_profile_if_needed(new_owner_profile, ctx.accounts.new_owner.key(), ctx.bumps.new_owner_profile)?;
+    if wifi_hotspot.is_active {
+        move_asset_count(&mut ctx.accounts.previous_owner_profile, new_owner_profile, Sector::WiFi)?;
+    }
+
+    wifi_hotspot.owner = ctx.accounts.new_owner.key();
+
+    msg!("WiFi hotspot {} transferred from {} to {}", wifi_hotspot.key(), previous_owner, wifi_hotspot.owner);
+
+    emit_cpi!(AssetTransferred {
+        previous_owner,
+        new_owner: wifi_hotspot.owner,
+        asset: wifi_hotspot.key(),
+        sector: Sector::WiFi,
+    });
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ClaimLogisticsPartnerOwnership<'info> {
+    #[account(
+        mut,
+        seeds = [LOGISTICS_PARTNER_SEED, &logistics_partner.asset_id.to_le_bytes()],
+        bump = logistics_partner.bump,
+        constraint = logistics_partner.owner != new_owner.key() @ DePINError::InvalidNewOwner
+    )]
+    pub logistics_partner: Account<'info, LogisticsPartner>,
+    
+    #[account(
+        token::mint = logistics_partner.nft_mint,
+        token::authority = new_owner,
+        constraint = new_owner_nft.amount == 1 @ DePINError::NotAssetHolder
+    )]
+    pub new_owner_nft: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [USER_PROFILE_SEED, logistics_partner.owner.as_ref()],
+        bump = previous_owner_profile.bump
+    )]
+    pub previous_owner_profile: Account<'info, UserProfile>,
+    
+    #[account(
+        init_if_needed,
+        payer = new_owner,
+        space = UserProfile::LEN,
+        seeds = [USER_PROFILE_SEED, new_owner.key().as_ref()],
+        bump
+    )]
+    pub new_owner_profile: Account<'info, UserProfile>,
+    
+    #[account(mut)]
+    pub new_owner: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn claim_logistics_partner_ownership(ctx: Context<ClaimLogisticsPartnerOwnership>) -> Result<()> {
+    let logistics_partner = &mut ctx.accounts.logistics_partner;
+    let new_owner_profile = &mut ctx.accounts.new_owner_profile;
+    let previous_owner = logistics_partner.owner;
+
+    init_user_profile_if_needed(new_owner_profile, ctx.accounts.new_owner.key(), ctx.bumps.new_owner_profile)?;
+    if logistics_partner.is_active {
+        move_asset_count(&mut ctx.accounts.previous_owner_profile, new_owner_profile, Sector::Logistics)?;
+    }
+
+    logistics_partner.owner = ctx.accounts.new_owner.key();
+
+    msg!("Logistics partner {} transferred from {} to {}", logistics_partner.key(), previous_owner, logistics_partner.owner);
+
+    emit_cpi!(AssetTransferred {
+        previous_owner,
+        new_owner: logistics_partner.owner,
+        asset: logistics_partner.key(),
+        sector: Sector::Logistics,
+    });
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ClaimFarmOwnership<'info> {
+    #[account(
+        mut,
+        seeds = [FARM_SEED, &farm.asset_id.to_le_bytes()],
+        bump = farm.bump,
+        constraint = farm.owner != new_owner.key() @ DePINError::InvalidNewOwner
+    )]
+    pub farm: Account<'info, Farm>,
+    
+    #[account(
+        token::mint = farm.nft_mint,
+        token::authority = new_owner,
+        constraint = new_owner_nft.amount == 1 @ DePINError::NotAssetHolder
+    )]
+    pub new_owner_nft: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [USER_PROFILE_SEED, farm.owner.as_ref()],
+        bump = previous_owner_profile.bump
+    )]
+    pub previous_owner_profile: Account<'info, UserProfile>,
+    
+    #[account(
+        init_if_needed,
+        payer = new_owner,
+        space = UserProfile::LEN,
+        seeds = [USER_PROFILE_SEED, new_owner.key().as_ref()],
+        bump
+    )]
+    pub new_owner_profile: Account<'info, UserProfile>,
+    
+    #[account(mut)]
+    pub new_owner: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn claim_farm_ownership(ctx: Context<ClaimFarmOwnership>) -> Result<()> {
+    let farm = &mut ctx.accounts.farm;
+    let new_owner_profile = &mut ctx.accounts.new_owner_profile;
+    let previous_owner = farm.owner;
+
+    init_user_profile_if_needed(new_owner_profile, ctx.accounts.new_owner.key(), ctx.bumps.new_owner_profile)?;
+    if farm.is_active {
+        move_asset_count(&mut ctx.accounts.previous_owner_profile, new_owner_profile, Sector::Agriculture)?;
+    }
+
+    farm.owner = ctx.accounts.new_owner.key();
+
+    msg!("Farm {} transferred from {} to {}", farm.key(), previous_owner, farm.owner);
+
+    emit_cpi!(AssetTransferred {
+        previous_owner,
+        new_owner: farm.owner,
+        asset: farm.key(),
+        sector: Sector::Agriculture,
+    });
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ClaimHealthcareProviderOwnership<'info> {
+    #[account(
+        mut,
+        seeds = [HEALTHCARE_PROVIDER_SEED, &healthcare_provider.asset_id.to_le_bytes()],
+        bump = healthcare_provider.bump,
+        constraint = healthcare_provider.owner != new_owner.key() @ DePINError::InvalidNewOwner
+    )]
+    pub healthcare_provider: Account<'info, HealthcareProvider>,
+    
+    #[account(
+        token::mint = healthcare_provider.nft_mint,
+        token::authority = new_owner,
+        constraint = new_owner_nft.amount == 1 @ DePINError::NotAssetHolder
+    )]
+    pub new_owner_nft: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [USER_PROFILE_SEED, healthcare_provider.owner.as_ref()],
+        bump = previous_owner_profile.bump
+    )]
+    pub previous_owner_profile: Account<'info, UserProfile>,
+    
+    #[account(
+        init_if_needed,
+        payer = new_owner,
+        space = UserProfile::LEN,
+        seeds = [USER_PROFILE_SEED, new_owner.key().as_ref()],
+        bump
+    )]
+    pub new_owner_profile: Account<'info, UserProfile>,
+    
+    #[account(mut)]
+    pub new_owner: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn claim_healthcare_provider_ownership(ctx: Context<ClaimHealthcareProviderOwnership>) -> Result<()> {
+    let healthcare_provider = &mut ctx.accounts.healthcare_provider;
+    let new_owner_profile = &mut ctx.accounts.new_owner_profile;
+    let previous_owner = healthcare_provider.owner;
+
+    init_user_profile_if_needed(new_owner_profile, ctx.accounts.new_owner.key(), ctx.bumps.new_owner_profile)?;
+    if healthcare_provider.is_active {
+        move_asset_count(&mut ctx.accounts.previous_owner_profile, new_owner_profile, Sector::Healthcare)?;
+    }
+
+    healthcare_provider.owner = ctx.accounts.new_owner.key();
+
+    msg!("Healthcare provider {} transferred from {} to {}", healthcare_provider.key(), previous_owner, healthcare_provider.owner);
+
+    emit_cpi!(AssetTransferred {
+        previous_owner,
+        new_owner: healthcare_provider.owner,
+        asset: healthcare_provider.key(),
+        sector: Sector::Healthcare,
+    });
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ClaimTaxPointOwnership<'info> {
+    #[account(
+        mut,
+        seeds = [TAX_POINT_SEED, &tax_point.asset_id.to_le_bytes()],
+        bump = tax_point.bump,
+        constraint = tax_point.owner != new_owner.key() @ DePINError::InvalidNewOwner
+    )]
+    pub tax_point: Account<'info, TaxCollectionPoint>,
+    
+    #[account(
+        token::mint = tax_point.nft_mint,
+        token::authority = new_owner,
+        constraint = new_owner_nft.amount == 1 @ DePINError::NotAssetHolder
+    )]
+    pub new_owner_nft: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [USER_PROFILE_SEED, tax_point.owner.as_ref()],
+        bump = previous_owner_profile.bump
+    )]
+    pub previous_owner_profile: Account<'info, UserProfile>,
+    
+    #[account(
+        init_if_needed,
+        payer = new_owner,
+        space = UserProfile::LEN,
+        seeds = [USER_PROFILE_SEED, new_owner.key().as_ref()],
+        bump
+    )]
+    pub new_owner_profile: Account<'info, UserProfile>,
+    
+    #[account(mut)]
+    pub new_owner: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn claim_tax_point_ownership(ctx: Context<ClaimTaxPointOwnership>) -> Result<()> {
+    let tax_point = &mut ctx.accounts.tax_point;
+    let new_owner_profile = &mut ctx.accounts.new_owner_profile;
+    let previous_owner = tax_point.owner;
+
+    init_user_profile_if_needed(new_owner_profile, ctx.accounts.new_owner.key(), ctx.bumps.new_owner_profile)?;
+    if tax_point.is_active {
+        move_asset_count(&mut ctx.accounts.previous_owner_profile, new_owner_profile, Sector::Taxation)?;
+    }
+
+    tax_point.owner = ctx.accounts.new_owner.key();
+
+    msg!("Tax point {} transferred from {} to {}", tax_point.key(), previous_owner, tax_point.owner);
+
+    emit_cpi!(AssetTransferred {
+        previous_owner,
+        new_owner: tax_point.owner,
+        asset: tax_point.key(),
+        sector: Sector::Taxation,
+    });
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ClaimEVChargerOwnership<'info> {
+    #[account(
+        mut,
+        seeds = [EV_CHARGER_SEED, &ev_charger.asset_id.to_le_bytes()],
+        bump = ev_charger.bump,
+        constraint = ev_charger.owner != new_owner.key() @ DePINError::InvalidNewOwner
+    )]
+    pub ev_charger: Account<'info, EVCharger>,
+    
+    #[account(
+        token::mint = ev_charger.nft_mint,
+        token::authority = new_owner,
+        constraint = new_owner_nft.amount == 1 @ DePINError::NotAssetHolder
+    )]
+    pub new_owner_nft: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [USER_PROFILE_SEED, ev_charger.owner.as_ref()],
+        bump = previous_owner_profile.bump
+    )]
+    pub previous_owner_profile: Account<'info, UserProfile>,
+    
+    #[account(
+        init_if_needed,
+        payer = new_owner,
+        space = UserProfile::LEN,
+        seeds = [USER_PROFILE_SEED, new_owner.key().as_ref()],
+        bump
+    )]
+    pub new_owner_profile: Account<'info, UserProfile>,
+    
+    #[account(mut)]
+    pub new_owner: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn claim_ev_charger_ownership(ctx: Context<ClaimEVChargerOwnership>) -> Result<()> {
+    let ev_charger = &mut ctx.accounts.ev_charger;
+    let new_owner_profile = &mut ctx.accounts.new_owner_profile;
+    let previous_owner = ev_charger.owner;
+
+    init_user_profile_if_needed(new_owner_profile, ctx.accounts.new_owner.key(), ctx.bumps.new_owner_profile)?;
+    if ev_charger.is_active {
+        move_asset_count(&mut ctx.accounts.previous_owner_profile, new_owner_profile, Sector::Power)?;
+    }
+
+    ev_charger.owner = ctx.accounts.new_owner.key();
+
+    msg!("EV charger {} transferred from {} to {}", ev_charger.key(), previous_owner, ev_charger.owner);
+
+    emit_cpi!(AssetTransferred {
+        previous_owner,
+        new_owner: ev_charger.owner,
+        asset: ev_charger.key(),
+        sector: Sector::Power,
+    });
+
+    Ok(())
+}
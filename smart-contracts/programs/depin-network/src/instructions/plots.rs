@@ -0,0 +1,390 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::*;
+use crate::events::*;
+use crate::utils::{validate_micro_degree_coordinates, SafeMath};
+
+// Charging plots are scarce: each city has a fixed grid, every cell can be bought
+// from the treasury once, and afterwards plots only change hands on the market.
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(name: String)]
+pub struct CreatePlotCity<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = PlotCity::LEN,
+        seeds = [PLOT_CITY_SEED, name.as_bytes()],
+        bump
+    )]
+    pub plot_city: Account<'info, PlotCity>,
+    
+    #[account(
+        seeds = [NETWORK_STATE_SEED],
+        bump = network_state.bump,
+        has_one = authority
+    )]
+    pub network_state: Account<'info, NetworkState>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn create_plot_city(
+    ctx: Context<CreatePlotCity>,
+    name: String,
+    origin_latitude: i32,
+    origin_longitude: i32,
+    cell_size: u32,
+    rows: u16,
+    cols: u16,
+    plot_price: u64,
+) -> Result<()> {
+    require!(name.len() <= PlotCity::MAX_NAME_LEN, DePINError::NameTooLong);
+    require!(cell_size > 0, DePINError::InvalidPlotGrid);
+    require!(rows > 0 && rows <= MAX_PLOT_GRID_SIZE, DePINError::InvalidPlotGrid);
+    require!(cols > 0 && cols <= MAX_PLOT_GRID_SIZE, DePINError::InvalidPlotGrid);
+    require!(plot_price > 0, DePINError::InvalidPlotPrice);
+
+    // Both corners of the grid must be valid coordinates
+    let north = origin_latitude as i64 + rows as i64 * cell_size as i64;
+    let east = origin_longitude as i64 + cols as i64 * cell_size as i64;
+    require!(
+        validate_micro_degree_coordinates(origin_latitude, origin_longitude)
+            && north <= i32::MAX as i64
+            && east <= i32::MAX as i64
+            && validate_micro_degree_coordinates(north as i32, east as i32),
+        DePINError::InvalidPlotGrid
+    );
+
+    let plot_city = &mut ctx.accounts.plot_city;
+    plot_city.name = name.clone();
+    plot_city.origin_latitude = origin_latitude;
+    plot_city.origin_longitude = origin_longitude;
+    plot_city.cell_size = cell_size;
+    plot_city.rows = rows;
+    plot_city.cols = cols;
+    plot_city.plot_price = plot_price;
+    plot_city.plots_sold = 0;
+    plot_city.bump = ctx.bumps.plot_city;
+
+    msg!("Plot grid created for {}: {} x {} plots", name, rows, cols);
+    msg!("Plot price: {} PKN", plot_price / 10_u64.pow(9));
+
+    emit_cpi!(PlotCityCreated {
+        city: plot_city.key(),
+        name,
+        rows,
+        cols,
+        plot_price,
+    });
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdatePlotPrice<'info> {
+    #[account(
+        mut,
+        seeds = [PLOT_CITY_SEED, plot_city.name.as_bytes()],
+        bump = plot_city.bump
+    )]
+    pub plot_city: Account<'info, PlotCity>,
+    
+    #[account(
+        seeds = [NETWORK_STATE_SEED],
+        bump = network_state.bump,
+        has_one = authority
+    )]
+    pub network_state: Account<'info, NetworkState>,
+    
+    pub authority: Signer<'info>,
+}
+
+pub fn update_plot_price(ctx: Context<UpdatePlotPrice>, plot_price: u64) -> Result<()> {
+    require!(plot_price > 0, DePINError::InvalidPlotPrice);
+
+    let plot_city = &mut ctx.accounts.plot_city;
+    plot_city.plot_price = plot_price;
+
+    msg!("Plot price in {} set to {} PKN", plot_city.name, plot_price / 10_u64.pow(9));
+
+    emit_cpi!(PlotPriceUpdated {
+        city: plot_city.key(),
+        plot_price,
+    });
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(row: u16, col: u16)]
+pub struct PurchasePlot<'info> {
+    #[account(
+        mut,
+        seeds = [PLOT_CITY_SEED, plot_city.name.as_bytes()],
+        bump = plot_city.bump
+    )]
+    pub plot_city: Account<'info, PlotCity>,
+    
+    #[account(
+        init,
+        payer = buyer,
+        space = ChargingPlot::LEN,
+        seeds = [CHARGING_PLOT_SEED, plot_city.key().as_ref(), &row.to_le_bytes(), &col.to_le_bytes()],
+        bump
+    )]
+    pub charging_plot: Account<'info, ChargingPlot>,
+    
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = buyer
+    )]
+    pub buyer_token_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        seeds = [MINT_SEED],
+        bump
+    )]
+    pub mint: Account<'info, Mint>,
+    
+    #[account(
+        mut,
+        seeds = [NETWORK_STATE_SEED, TREASURY_SEED],
+        bump = network_state.treasury_bump
+    )]
+    pub treasury: Account<'info, TokenAccount>,
+    
+    #[account(
+        seeds = [NETWORK_STATE_SEED],
+        bump = network_state.bump
+    )]
+    pub network_state: Account<'info, NetworkState>,
+    
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+}
+
+/// Buy an unclaimed plot from the grid; the price goes to the treasury. Fails if the
+/// city's price is above `max_price`, so a price update cannot overcharge a pending purchase.
+pub fn purchase_plot(ctx: Context<PurchasePlot>, row: u16, col: u16, max_price: u64) -> Result<()> {
+    let plot_city = &mut ctx.accounts.plot_city;
+    require!(row < plot_city.rows && col < plot_city.cols, DePINError::PlotOutOfBounds);
+
+    let price = plot_city.plot_price;
+    require!(price <= max_price, DePINError::PriceAboveMax);
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.buyer_token_account.to_account_info(),
+        to: ctx.accounts.treasury.to_account_info(),
+        authority: ctx.accounts.buyer.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+    token::transfer(cpi_ctx, price)?;
+
+    let charging_plot = &mut ctx.accounts.charging_plot;
+    charging_plot.city = plot_city.key();
+    charging_plot.row = row;
+    charging_plot.col = col;
+    charging_plot.owner = ctx.accounts.buyer.key();
+    charging_plot.charger = Pubkey::default();
+    charging_plot.purchased_at = Clock::get()?.unix_timestamp;
+    charging_plot.last_price = price;
+    charging_plot.bump = ctx.bumps.charging_plot;
+
+    plot_city.plots_sold = plot_city.plots_sold.safe_add(1)?;
+
+    msg!("Plot ({}, {}) in {} purchased for {} PKN", row, col, plot_city.name, price / 10_u64.pow(9));
+    msg!("Plots sold: {} of {}", plot_city.plots_sold, plot_city.rows as u32 * plot_city.cols as u32);
+
+    emit_cpi!(PlotPurchased {
+        city: plot_city.key(),
+        plot: charging_plot.key(),
+        buyer: charging_plot.owner,
+        row,
+        col,
+        price,
+    });
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ListPlot<'info> {
+    #[account(
+        mut,
+        seeds = [CHARGING_PLOT_SEED, charging_plot.city.as_ref(), &charging_plot.row.to_le_bytes(), &charging_plot.col.to_le_bytes()],
+        bump = charging_plot.bump,
+        constraint = charging_plot.owner == seller.key() @ DePINError::NotPlotOwner
+    )]
+    pub charging_plot: Account<'info, ChargingPlot>,
+    
+    #[account(
+        init,
+        payer = seller,
+        space = PlotListing::LEN,
+        seeds = [PLOT_LISTING_SEED, charging_plot.key().as_ref()],
+        bump
+    )]
+    pub plot_listing: Account<'info, PlotListing>,
+    
+    #[account(mut)]
+    pub seller: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Put a plot up for sale, moving its ownership into the listing until it sells or is cancelled
+pub fn list_plot(ctx: Context<ListPlot>, price: u64) -> Result<()> {
+    require!(price > 0, DePINError::InvalidPlotPrice);
+
+    let charging_plot = &mut ctx.accounts.charging_plot;
+    let plot_listing = &mut ctx.accounts.plot_listing;
+    require!(charging_plot.charger == Pubkey::default(), DePINError::PlotOccupied);
+
+    plot_listing.plot = charging_plot.key();
+    plot_listing.seller = ctx.accounts.seller.key();
+    plot_listing.price = price;
+    plot_listing.listed_at = Clock::get()?.unix_timestamp;
+    plot_listing.bump = ctx.bumps.plot_listing;
+
+    charging_plot.owner = plot_listing.key();
+
+    msg!("Plot {} listed for {} PKN", charging_plot.key(), price / 10_u64.pow(9));
+
+    emit_cpi!(PlotListed {
+        plot: charging_plot.key(),
+        seller: plot_listing.seller,
+        price,
+    });
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CancelListing<'info> {
+    #[account(
+        mut,
+        seeds = [CHARGING_PLOT_SEED, charging_plot.city.as_ref(), &charging_plot.row.to_le_bytes(), &charging_plot.col.to_le_bytes()],
+        bump = charging_plot.bump
+    )]
+    pub charging_plot: Account<'info, ChargingPlot>,
+    
+    #[account(
+        mut,
+        seeds = [PLOT_LISTING_SEED, charging_plot.key().as_ref()],
+        bump = plot_listing.bump,
+        has_one = seller,
+        close = seller
+    )]
+    pub plot_listing: Account<'info, PlotListing>,
+    
+    #[account(mut)]
+    pub seller: Signer<'info>,
+}
+
+pub fn cancel_listing(ctx: Context<CancelListing>) -> Result<()> {
+    let charging_plot = &mut ctx.accounts.charging_plot;
+    charging_plot.owner = ctx.accounts.seller.key();
+
+    msg!("Listing for plot {} cancelled", charging_plot.key());
+
+    emit_cpi!(PlotListingCancelled {
+        plot: charging_plot.key(),
+        seller: charging_plot.owner,
+    });
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct BuyListedPlot<'info> {
+    #[account(
+        mut,
+        seeds = [CHARGING_PLOT_SEED, charging_plot.city.as_ref(), &charging_plot.row.to_le_bytes(), &charging_plot.col.to_le_bytes()],
+        bump = charging_plot.bump
+    )]
+    pub charging_plot: Account<'info, ChargingPlot>,
+    
+    #[account(
+        mut,
+        seeds = [PLOT_LISTING_SEED, charging_plot.key().as_ref()],
+        bump = plot_listing.bump,
+        has_one = seller,
+        close = seller
+    )]
+    pub plot_listing: Account<'info, PlotListing>,
+    
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = buyer
+    )]
+    pub buyer_token_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = seller
+    )]
+    pub seller_token_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        seeds = [MINT_SEED],
+        bump
+    )]
+    pub mint: Account<'info, Mint>,
+    
+    /// CHECK: The listing's seller, checked by `has_one`; receives the listing rent
+    #[account(mut)]
+    pub seller: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        constraint = buyer.key() != seller.key() @ DePINError::InvalidNewOwner
+    )]
+    pub buyer: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+/// Buy a listed plot, paying the seller the listing price. Fails if the price is
+/// above `max_price`, so a seller cannot reprice a listing under a pending purchase.
+pub fn buy_listed_plot(ctx: Context<BuyListedPlot>, max_price: u64) -> Result<()> {
+    let price = ctx.accounts.plot_listing.price;
+    require!(price <= max_price, DePINError::PriceAboveMax);
+
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.buyer_token_account.to_account_info(),
+        to: ctx.accounts.seller_token_account.to_account_info(),
+        authority: ctx.accounts.buyer.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+    token::transfer(cpi_ctx, price)?;
+
+    let charging_plot = &mut ctx.accounts.charging_plot;
+    charging_plot.owner = ctx.accounts.buyer.key();
+    charging_plot.purchased_at = Clock::get()?.unix_timestamp;
+    charging_plot.last_price = price;
+
+    msg!("Plot {} sold for {} PKN", charging_plot.key(), price / 10_u64.pow(9));
+
+    emit_cpi!(PlotSold {
+        plot: charging_plot.key(),
+        seller: ctx.accounts.seller.key(),
+        buyer: charging_plot.owner,
+        price,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,479 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions::ID as INSTRUCTIONS_SYSVAR_ID;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::metadata::Metadata;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::*;
+use crate::events::*;
+use crate::utils::{
+    calculate_reward, init_user_profile_if_needed, mint_asset_nft, plot_contains,
+    record_epoch_contribution, telemetry_message, validate_micro_degree_coordinates,
+    validate_telemetry_freshness, verify_device_signature, MintAssetNft, SafeMath,
+};
+
+// EV Charger Registration
+#[event_cpi]
+#[derive(Accounts)]
+pub struct RegisterEVCharger<'info> {
+    #[account(
+        init,
+        payer = owner,
+        space = EVCharger::LEN,
+        seeds = [EV_CHARGER_SEED, &network_state.next_asset_id.to_le_bytes()],
+        bump
+    )]
+    pub ev_charger: Account<'info, EVCharger>,
+    
+    #[account(
+        mut,
+        seeds = [NETWORK_STATE_SEED],
+        bump = network_state.bump
+    )]
+    pub network_state: Account<'info, NetworkState>,
+    
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = UserProfile::LEN,
+        seeds = [USER_PROFILE_SEED, owner.key().as_ref()],
+        bump
+    )]
+    pub user_profile: Account<'info, UserProfile>,
+    
+    #[account(
+        seeds = [PLOT_CITY_SEED, plot_city.name.as_bytes()],
+        bump = plot_city.bump
+    )]
+    pub plot_city: Box<Account<'info, PlotCity>>,
+    
+    #[account(
+        mut,
+        seeds = [CHARGING_PLOT_SEED, plot_city.key().as_ref(), &charging_plot.row.to_le_bytes(), &charging_plot.col.to_le_bytes()],
+        bump = charging_plot.bump,
+        constraint = charging_plot.owner == owner.key() @ DePINError::NotPlotOwner,
+        constraint = charging_plot.charger == Pubkey::default() @ DePINError::PlotOccupied
+    )]
+    pub charging_plot: Box<Account<'info, ChargingPlot>>,
+    
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    #[account(
+        init,
+        payer = owner,
+        seeds = [ASSET_MINT_SEED, ev_charger.key().as_ref()],
+        bump,
+        mint::decimals = 0,
+        mint::authority = network_state,
+        mint::freeze_authority = network_state
+    )]
+    pub nft_mint: Box<Account<'info, Mint>>,
+    
+    #[account(
+        init,
+        payer = owner,
+        associated_token::mint = nft_mint,
+        associated_token::authority = owner
+    )]
+    pub owner_nft: Box<Account<'info, TokenAccount>>,
+    
+    /// CHECK: Metadata PDA, created by the token metadata program
+    #[account(
+        mut,
+        seeds = [METADATA_SEED, token_metadata_program.key().as_ref(), nft_mint.key().as_ref()],
+        bump,
+        seeds::program = token_metadata_program.key()
+    )]
+    pub nft_metadata: UncheckedAccount<'info>,
+    
+    /// CHECK: Master edition PDA, created by the token metadata program
+    #[account(
+        mut,
+        seeds = [METADATA_SEED, token_metadata_program.key().as_ref(), nft_mint.key().as_ref(), EDITION_SEED],
+        bump,
+        seeds::program = token_metadata_program.key()
+    )]
+    pub nft_master_edition: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_metadata_program: Program<'info, Metadata>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn register_ev_charger(
+    ctx: Context<RegisterEVCharger>,
+    device_pubkey: Pubkey,
+    charger_name: String,
+    charger_type: ChargerType,
+    power_output_kw: u32,
+    latitude: i32,
+    longitude: i32,
+    metadata_uri: String,
+) -> Result<()> {
+    require!(ctx.accounts.user_profile.ev_chargers < MAX_EV_CHARGERS_PER_USER, DePINError::TooManyAssets);
+    require!(charger_name.len() <= EVCharger::MAX_NAME_LEN, DePINError::NameTooLong);
+    require!(power_output_kw > 0 && power_output_kw <= 350, DePINError::InvalidPowerOutput);
+    require!(validate_micro_degree_coordinates(latitude, longitude), DePINError::InvalidCoordinates);
+    require!(metadata_uri.len() <= MAX_NFT_URI_LEN, DePINError::UriTooLong);
+
+    let charging_plot = &mut ctx.accounts.charging_plot;
+    require!(
+        plot_contains(&ctx.accounts.plot_city, charging_plot.row, charging_plot.col, latitude, longitude),
+        DePINError::ChargerOutsidePlot
+    );
+
+    let ev_charger = &mut ctx.accounts.ev_charger;
+    let network_state = &mut ctx.accounts.network_state;
+    let user_profile = &mut ctx.accounts.user_profile;
+
+    ev_charger.owner = ctx.accounts.owner.key();
+    ev_charger.asset_id = network_state.next_asset_id;
+    ev_charger.nft_mint = ctx.accounts.nft_mint.key();
+    ev_charger.device_pubkey = device_pubkey;
+    ev_charger.device_nonce = 0;
+    ev_charger.charger_name = charger_name.clone();
+    ev_charger.charger_type = charger_type;
+    ev_charger.power_output_kw = power_output_kw;
+    ev_charger.latitude = latitude;
+    ev_charger.longitude = longitude;
+    ev_charger.plot = charging_plot.key();
+    ev_charger.active_session = Pubkey::default();
+    ev_charger.last_session_end = 0;
+    ev_charger.total_sessions = 0;
+    ev_charger.total_energy_wh = 0;
+    ev_charger.total_rewards_earned = 0;
+    ev_charger.is_active = true;
+    ev_charger.bump = ctx.bumps.ev_charger;
+
+    charging_plot.charger = ev_charger.key();
+
+    network_state.ev_chargers_count = network_state.ev_chargers_count.safe_add(1)?;
+    network_state.next_asset_id = network_state.next_asset_id.safe_add(1)?;
+
+    init_user_profile_if_needed(user_profile, ctx.accounts.owner.key(), ctx.bumps.user_profile)?;
+    user_profile.ev_chargers = user_profile.ev_chargers.safe_add(1)?;
+
+    mint_asset_nft(
+        MintAssetNft {
+            network_state: &ctx.accounts.network_state,
+            nft_mint: &ctx.accounts.nft_mint,
+            owner_nft: &ctx.accounts.owner_nft,
+            nft_metadata: &ctx.accounts.nft_metadata,
+            nft_master_edition: &ctx.accounts.nft_master_edition,
+            payer: &ctx.accounts.owner,
+            token_program: &ctx.accounts.token_program,
+            token_metadata_program: &ctx.accounts.token_metadata_program,
+            system_program: &ctx.accounts.system_program,
+            rent: &ctx.accounts.rent,
+        },
+        format!("PKN EV Charger #{}", ev_charger.asset_id),
+        metadata_uri,
+    )?;
+
+    msg!("EV charger '{}' registered successfully", charger_name);
+    msg!("Type: {:?}, Output: {} kW", charger_type, power_output_kw);
+    msg!("Coordinates: ({}, {}) micro-degrees", latitude, longitude);
+
+    emit_cpi!(InfrastructureRegistered {
+        owner: ev_charger.owner,
+        asset: ev_charger.key(),
+        sector: Sector::Power,
+        asset_id: ev_charger.asset_id,
+        nft_mint: ev_charger.nft_mint,
+        device_pubkey,
+    });
+
+    Ok(())
+}
+
+// Charging sessions: the driver opens a session on a free charger, and the
+// operator closes it with the energy delivered, signed by the charger's device
+// and co-signed by the driver. The charger's current NFT holder cannot drive its
+// sessions; an operator driving from a second wallet is not detected.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct StartChargingSession<'info> {
+    #[account(
+        mut,
+        seeds = [EV_CHARGER_SEED, &ev_charger.asset_id.to_le_bytes()],
+        bump = ev_charger.bump
+    )]
+    pub ev_charger: Account<'info, EVCharger>,
+    
+    #[account(
+        init,
+        payer = driver,
+        space = ChargingSession::LEN,
+        seeds = [CHARGING_SESSION_SEED, ev_charger.key().as_ref(), &ev_charger.total_sessions.to_le_bytes()],
+        bump
+    )]
+    pub charging_session: Account<'info, ChargingSession>,
+    
+    // The account holding the charger's NFT, which identifies its current operator
+    #[account(
+        token::mint = ev_charger.nft_mint,
+        constraint = charger_nft.amount == 1 @ DePINError::NotAssetHolder,
+        constraint = charger_nft.owner != driver.key() @ DePINError::DriverIsOperator
+    )]
+    pub charger_nft: Account<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub driver: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn start_charging_session(ctx: Context<StartChargingSession>) -> Result<()> {
+    let ev_charger = &mut ctx.accounts.ev_charger;
+    let charging_session = &mut ctx.accounts.charging_session;
+    require!(ev_charger.is_active, DePINError::AssetInactive);
+    require!(ev_charger.active_session == Pubkey::default(), DePINError::ChargerBusy);
+
+    // Check minimum idle time since the previous session
+    let current_time = Clock::get()?.unix_timestamp;
+    require!(
+        current_time.safe_sub(ev_charger.last_session_end)? >= MIN_CHARGING_SESSION_INTERVAL,
+        DePINError::ChargerCoolingDown
+    );
+
+    charging_session.charger = ev_charger.key();
+    charging_session.driver = ctx.accounts.driver.key();
+    charging_session.session_id = ev_charger.total_sessions;
+    charging_session.start_time = current_time;
+    charging_session.end_time = 0;
+    charging_session.energy_delivered_wh = 0;
+    charging_session.reward_amount = 0;
+    charging_session.bump = ctx.bumps.charging_session;
+
+    ev_charger.active_session = charging_session.key();
+    ev_charger.total_sessions = ev_charger.total_sessions.safe_add(1)?;
+
+    msg!("Charging session {} started on charger {}", charging_session.session_id, ev_charger.key());
+
+    emit_cpi!(ChargingSessionStarted {
+        charger: ev_charger.key(),
+        session: charging_session.key(),
+        driver: charging_session.driver,
+        session_id: charging_session.session_id,
+        start_time: current_time,
+    });
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct EndChargingSession<'info> {
+    #[account(
+        mut,
+        seeds = [EV_CHARGER_SEED, &ev_charger.asset_id.to_le_bytes()],
+        bump = ev_charger.bump,
+        constraint = ev_charger.active_session == charging_session.key() @ DePINError::SessionNotActive
+    )]
+    pub ev_charger: Account<'info, EVCharger>,
+    
+    #[account(
+        token::mint = ev_charger.nft_mint,
+        token::authority = owner,
+        constraint = owner_nft.amount == 1 @ DePINError::NotAssetHolder
+    )]
+    pub owner_nft: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [CHARGING_SESSION_SEED, ev_charger.key().as_ref(), &charging_session.session_id.to_le_bytes()],
+        bump = charging_session.bump
+    )]
+    pub charging_session: Account<'info, ChargingSession>,
+    
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = UserProfile::LEN,
+        seeds = [USER_PROFILE_SEED, owner.key().as_ref()],
+        bump
+    )]
+    pub user_profile: Account<'info, UserProfile>,
+    
+    #[account(
+        seeds = [NETWORK_STATE_SEED],
+        bump = network_state.bump
+    )]
+    pub network_state: Account<'info, NetworkState>,
+    
+    #[account(
+        mut,
+        seeds = [EPOCH_SEED, &network_state.current_epoch.to_le_bytes()],
+        bump = reward_epoch.bump
+    )]
+    pub reward_epoch: Account<'info, RewardEpoch>,
+    
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = EpochContribution::LEN,
+        seeds = [EPOCH_CONTRIBUTION_SEED, reward_epoch.key().as_ref(), owner.key().as_ref()],
+        bump
+    )]
+    pub epoch_contribution: Account<'info, EpochContribution>,
+    
+    /// CHECK: Instructions sysvar, used to inspect the device's ed25519 signature
+    #[account(address = INSTRUCTIONS_SYSVAR_ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+    
+    /// The session's driver co-signs the energy reading
+    #[account(address = charging_session.driver)]
+    pub driver: Signer<'info>,
+    
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn end_charging_session(
+    ctx: Context<EndChargingSession>,
+    energy_delivered_wh: u64,
+    nonce: u64,
+    timestamp: i64,
+) -> Result<()> {
+    let ev_charger = &mut ctx.accounts.ev_charger;
+    let charging_session = &mut ctx.accounts.charging_session;
+    let user_profile = &mut ctx.accounts.user_profile;
+    let network_state = &ctx.accounts.network_state;
+
+    // A session left open past `MAX_CHARGING_SESSION_DURATION` is only credited up to it
+    let current_time = Clock::get()?.unix_timestamp;
+    let duration = current_time
+        .safe_sub(charging_session.start_time)?
+        .min(MAX_CHARGING_SESSION_DURATION);
+
+    // The charger cannot deliver more than its rated output over the session
+    let max_energy_wh = (ev_charger.power_output_kw as u64)
+        .safe_mul(1000)?
+        .safe_mul(duration as u64)?
+        .safe_div(3600)?;
+    require!(energy_delivered_wh <= max_energy_wh, DePINError::EnergyExceedsCapacity);
+
+    // Verify the report was signed by the charger's registered device
+    validate_telemetry_freshness(nonce, ev_charger.device_nonce, timestamp, current_time)?;
+    let metrics = (charging_session.session_id, energy_delivered_wh).try_to_vec()?;
+    let message = telemetry_message(&ev_charger.key(), nonce, timestamp, &metrics);
+    verify_device_signature(&ctx.accounts.instructions_sysvar, &ev_charger.device_pubkey, &message)?;
+    ev_charger.device_nonce = nonce;
+
+    // Rewards accrue per kWh delivered
+    let reward_amount = calculate_reward(energy_delivered_wh, network_state.power_reward_rate, 1, 1000)?;
+
+    charging_session.end_time = current_time;
+    charging_session.energy_delivered_wh = energy_delivered_wh;
+    charging_session.reward_amount = reward_amount;
+
+    ev_charger.active_session = Pubkey::default();
+    ev_charger.last_session_end = current_time;
+    ev_charger.total_energy_wh = ev_charger.total_energy_wh.safe_add(energy_delivered_wh)?;
+    ev_charger.total_rewards_earned = ev_charger.total_rewards_earned.safe_add(reward_amount)?;
+
+    // Set up the holder's profile on their first report
+    init_user_profile_if_needed(user_profile, ctx.accounts.owner.key(), ctx.bumps.user_profile)?;
+
+    // Accrue contribution towards this epoch's power emission budget
+    record_epoch_contribution(
+        &mut ctx.accounts.reward_epoch,
+        &mut ctx.accounts.epoch_contribution,
+        ctx.accounts.owner.key(),
+        ctx.bumps.epoch_contribution,
+        Sector::Power,
+        reward_amount,
+    )?;
+
+    msg!("Charging session {} ended", charging_session.session_id);
+    msg!("Energy delivered: {} Wh over {} seconds", energy_delivered_wh, duration);
+    msg!("Epoch contribution: {} PKN", reward_amount / 10_u64.pow(9));
+
+    emit_cpi!(ChargingSessionEnded {
+        charger: ev_charger.key(),
+        session: charging_session.key(),
+        driver: charging_session.driver,
+        energy_delivered_wh,
+        duration,
+        reward_amount,
+    });
+    emit_cpi!(DataSubmitted {
+        owner: ctx.accounts.owner.key(),
+        asset: ev_charger.key(),
+        sector: Sector::Power,
+        epoch: ctx.accounts.reward_epoch.id,
+        nonce,
+        reward_amount,
+        timestamp: current_time,
+    });
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CancelChargingSession<'info> {
+    #[account(
+        mut,
+        seeds = [EV_CHARGER_SEED, &ev_charger.asset_id.to_le_bytes()],
+        bump = ev_charger.bump,
+        constraint = ev_charger.active_session == charging_session.key() @ DePINError::SessionNotActive
+    )]
+    pub ev_charger: Account<'info, EVCharger>,
+    
+    #[account(
+        mut,
+        seeds = [CHARGING_SESSION_SEED, ev_charger.key().as_ref(), &charging_session.session_id.to_le_bytes()],
+        bump = charging_session.bump
+    )]
+    pub charging_session: Account<'info, ChargingSession>,
+    
+    /// The charger's NFT, required when the operator cancels
+    #[account(
+        token::mint = ev_charger.nft_mint,
+        token::authority = authority,
+        constraint = authority_nft.amount == 1 @ DePINError::NotAssetHolder
+    )]
+    pub authority_nft: Option<Account<'info, TokenAccount>>,
+    
+    pub authority: Signer<'info>,
+}
+
+/// Cancel a session without a reward. The driver can cancel at any time; the
+/// operator only once the session has run past `MAX_CHARGING_SESSION_DURATION`,
+/// so an abandoned session cannot hold the charger indefinitely.
+pub fn cancel_charging_session(ctx: Context<CancelChargingSession>) -> Result<()> {
+    let ev_charger = &mut ctx.accounts.ev_charger;
+    let charging_session = &mut ctx.accounts.charging_session;
+    let authority = ctx.accounts.authority.key();
+    let current_time = Clock::get()?.unix_timestamp;
+
+    if authority != charging_session.driver {
+        require!(ctx.accounts.authority_nft.is_some(), DePINError::Unauthorized);
+        require!(
+            current_time.safe_sub(charging_session.start_time)? >= MAX_CHARGING_SESSION_DURATION,
+            DePINError::SessionNotExpired
+        );
+    }
+
+    charging_session.end_time = current_time;
+    ev_charger.active_session = Pubkey::default();
+    ev_charger.last_session_end = current_time;
+
+    msg!("Charging session {} cancelled", charging_session.session_id);
+
+    emit_cpi!(ChargingSessionCancelled {
+        charger: ev_charger.key(),
+        session: charging_session.key(),
+        driver: charging_session.driver,
+        cancelled_by: authority,
+    });
+
+    Ok(())
+}
@@ -1,52 +1,300 @@
 use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
 use crate::state::*;
 use crate::constants::*;
 use crate::errors::*;
+use crate::events::*;
+use crate::utils::{mul_div, pay_reward, SafeMath};
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct DistributeRewards<'info> {
     #[account(
         mut,
         seeds = [NETWORK_STATE_SEED],
-        bump = network_state.bump,
-        has_one = authority
+        bump = network_state.bump
     )]
     pub network_state: Account<'info, NetworkState>,
     
     #[account(
-        seeds = [MINT_SEED],
+        mut,
+        seeds = [EPOCH_SEED, &network_state.current_epoch.to_le_bytes()],
+        bump = reward_epoch.bump
+    )]
+    pub reward_epoch: Account<'info, RewardEpoch>,
+    
+    #[account(
+        init,
+        payer = payer,
+        space = RewardEpoch::LEN,
+        seeds = [EPOCH_SEED, &network_state.current_epoch.saturating_add(1).to_le_bytes()],
         bump
     )]
-    pub mint: Account<'info, anchor_spl::token::Mint>,
+    pub next_epoch: Account<'info, RewardEpoch>,
     
-    pub authority: Signer<'info>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
 }
 
+/// Finalize the current reward epoch and open the next one.
+/// Permissionless: anyone can crank once the epoch has run for 24 hours.
 pub fn distribute_rewards(ctx: Context<DistributeRewards>) -> Result<()> {
     let network_state = &mut ctx.accounts.network_state;
+    let reward_epoch = &mut ctx.accounts.reward_epoch;
     let current_time = Clock::get()?.unix_timestamp;
 
-    // Check if enough time has passed since last distribution (24 hours)
+    require!(!reward_epoch.finalized, DePINError::EpochAlreadyFinalized);
     require!(
-        current_time - network_state.last_reward_distribution >= 86400,
+        current_time.safe_sub(reward_epoch.start_time)? >= EPOCH_DURATION,
         DePINError::DistributionTooFrequent
     );
 
+    // Split the emission across sectors; sectors nobody contributed to keep their share in the treasury
+    let mut emission_budget = 0u64;
+    for i in 0..SECTOR_COUNT {
+        if reward_epoch.sector_contributions[i] == 0 {
+            reward_epoch.sector_budgets[i] = 0;
+            continue;
+        }
+        let sector_budget = mul_div(
+            network_state.epoch_emission,
+            network_state.sector_emission_bps[i] as u64,
+            BASIS_POINTS,
+        )?;
+        reward_epoch.sector_budgets[i] = sector_budget;
+        emission_budget = emission_budget.safe_add(sector_budget)?;
+    }
+
+    reward_epoch.emission_budget = emission_budget;
+    reward_epoch.end_time = current_time;
+    reward_epoch.finalized = true;
+
+    let next_epoch = &mut ctx.accounts.next_epoch;
+    next_epoch.id = reward_epoch.id.safe_add(1)?;
+    next_epoch.start_time = current_time;
+    next_epoch.end_time = 0;
+    next_epoch.emission_budget = 0;
+    next_epoch.sector_budgets = [0; SECTOR_COUNT];
+    next_epoch.sector_contributions = [0; SECTOR_COUNT];
+    next_epoch.finalized = false;
+    next_epoch.bump = ctx.bumps.next_epoch;
+
+    network_state.current_epoch = next_epoch.id;
     network_state.last_reward_distribution = current_time;
 
-    // In a production system, this would:
-    // 1. Calculate rewards for all active infrastructure
-    // 2. Mint new tokens or transfer from treasury
-    // 3. Distribute to operators based on performance
+    msg!("Reward epoch {} finalized", reward_epoch.id);
+    msg!("Emission budget: {} PKN", emission_budget / 10_u64.pow(9));
+    msg!("Sector budgets: {:?}", reward_epoch.sector_budgets);
+
+    emit_cpi!(EpochFinalized {
+        epoch: reward_epoch.id,
+        emission_budget,
+        sector_budgets: reward_epoch.sector_budgets,
+        sector_contributions: reward_epoch.sector_contributions,
+        end_time: current_time,
+    });
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SettleEpochRewards<'info> {
+    #[account(
+        seeds = [EPOCH_SEED, &reward_epoch.id.to_le_bytes()],
+        bump = reward_epoch.bump
+    )]
+    pub reward_epoch: Account<'info, RewardEpoch>,
     
-    msg!("Daily rewards distribution completed");
-    msg!("WiFi hotspots: {}", network_state.wifi_hotspots_count);
-    msg!("Logistics partners: {}", network_state.logistics_partners_count);
-    msg!("Farms: {}", network_state.farms_count);
+    #[account(
+        mut,
+        seeds = [EPOCH_CONTRIBUTION_SEED, reward_epoch.key().as_ref(), owner.key().as_ref()],
+        bump = epoch_contribution.bump,
+        has_one = owner,
+        close = owner
+    )]
+    pub epoch_contribution: Account<'info, EpochContribution>,
+    
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = OperatorRewards::LEN,
+        seeds = [OPERATOR_REWARDS_SEED, owner.key().as_ref()],
+        bump
+    )]
+    pub operator_rewards: Account<'info, OperatorRewards>,
+    
+    /// CHECK: Operator that earned the contribution; receives the contribution account's rent
+    #[account(mut)]
+    pub owner: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Convert an operator's contribution in a finalized epoch into a claimable
+/// pro-rata share of each sector's budget. Permissionless.
+pub fn settle_epoch_rewards(ctx: Context<SettleEpochRewards>) -> Result<()> {
+    let reward_epoch = &ctx.accounts.reward_epoch;
+    let epoch_contribution = &ctx.accounts.epoch_contribution;
+    let operator_rewards = &mut ctx.accounts.operator_rewards;
+
+    require!(reward_epoch.finalized, DePINError::EpochNotFinalized);
+
+    let mut share = 0u64;
+    for i in 0..SECTOR_COUNT {
+        let total = reward_epoch.sector_contributions[i];
+        if total == 0 {
+            continue;
+        }
+        share = share.safe_add(mul_div(reward_epoch.sector_budgets[i], epoch_contribution.contributions[i], total)?)?;
+    }
+
+    if operator_rewards.owner == Pubkey::default() {
+        operator_rewards.owner = ctx.accounts.owner.key();
+        operator_rewards.claimable = 0;
+        operator_rewards.total_claimed = 0;
+        operator_rewards.bump = ctx.bumps.operator_rewards;
+    }
+
+    operator_rewards.claimable = operator_rewards.claimable.safe_add(share)?;
+    operator_rewards.last_settled_epoch = reward_epoch.id;
+
+    msg!("Epoch {} settled for {}", reward_epoch.id, operator_rewards.owner);
+    msg!("Epoch share: {} PKN, claimable: {} PKN",
+         share / 10_u64.pow(9), operator_rewards.claimable / 10_u64.pow(9));
+
+    emit_cpi!(EpochRewardsSettled {
+        owner: operator_rewards.owner,
+        epoch: reward_epoch.id,
+        share,
+        claimable: operator_rewards.claimable,
+    });
 
     Ok(())
 }
 
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ClaimOperatorRewards<'info> {
+    #[account(
+        mut,
+        seeds = [OPERATOR_REWARDS_SEED, owner.key().as_ref()],
+        bump = operator_rewards.bump,
+        has_one = owner
+    )]
+    pub operator_rewards: Account<'info, OperatorRewards>,
+    
+    #[account(
+        mut,
+        seeds = [USER_PROFILE_SEED, owner.key().as_ref()],
+        bump = user_profile.bump,
+        has_one = owner
+    )]
+    pub user_profile: Account<'info, UserProfile>,
+    
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = owner
+    )]
+    pub user_token_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [MINT_SEED],
+        bump
+    )]
+    pub mint: Account<'info, Mint>,
+    
+    #[account(
+        mut,
+        seeds = [NETWORK_STATE_SEED, TREASURY_SEED],
+        bump = network_state.treasury_bump
+    )]
+    pub treasury: Account<'info, TokenAccount>,
+    
+    #[account(
+        seeds = [NETWORK_STATE_SEED],
+        bump = network_state.bump
+    )]
+    pub network_state: Account<'info, NetworkState>,
+    
+    pub owner: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+/// Pay out an operator's settled epoch rewards from the configured reward source.
+/// This is the only path by which operators are paid for submitted data.
+pub fn claim_operator_rewards(ctx: Context<ClaimOperatorRewards>) -> Result<()> {
+    let amount = ctx.accounts.operator_rewards.claimable;
+    require!(amount > 0, DePINError::NoRewardsToClaim);
+
+    pay_reward(
+        &ctx.accounts.mint,
+        &ctx.accounts.treasury,
+        &ctx.accounts.user_token_account,
+        &ctx.accounts.network_state,
+        &ctx.accounts.token_program,
+        amount,
+    )?;
+
+    let operator_rewards = &mut ctx.accounts.operator_rewards;
+    let user_profile = &mut ctx.accounts.user_profile;
+
+    operator_rewards.claimable = 0;
+    operator_rewards.total_claimed = operator_rewards.total_claimed.safe_add(amount)?;
+    user_profile.total_earned = user_profile.total_earned.safe_add(amount)?;
+    user_profile.last_activity = Clock::get()?.unix_timestamp;
+
+    msg!("Claimed {} PKN of epoch rewards", amount / 10_u64.pow(9));
+
+    emit_cpi!(RewardMinted {
+        recipient: operator_rewards.owner,
+        amount,
+        source: ctx.accounts.network_state.reward_source,
+    });
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateEpochEmission<'info> {
+    #[account(
+        mut,
+        seeds = [NETWORK_STATE_SEED],
+        bump = network_state.bump,
+        has_one = authority
+    )]
+    pub network_state: Account<'info, NetworkState>,
+    
+    pub authority: Signer<'info>,
+}
+
+pub fn update_epoch_emission(
+    ctx: Context<UpdateEpochEmission>,
+    epoch_emission: u64,
+    sector_emission_bps: [u16; SECTOR_COUNT],
+) -> Result<()> {
+    let total_bps: u32 = sector_emission_bps.iter().map(|bps| *bps as u32).sum();
+    require!(total_bps == 10_000, DePINError::InvalidEmissionSplit);
+
+    let network_state = &mut ctx.accounts.network_state;
+    network_state.epoch_emission = epoch_emission;
+    network_state.sector_emission_bps = sector_emission_bps;
+
+    msg!("Epoch emission updated: {} PKN per epoch", epoch_emission / 10_u64.pow(9));
+    msg!("Sector split (bps): {:?}", sector_emission_bps);
+
+    emit_cpi!(ParamsUpdated::from_network_state(network_state));
+
+    Ok(())
+}
+
+#[event_cpi]
 #[derive(Accounts)]
 pub struct UpdateNetworkParams<'info> {
     #[account(
@@ -88,5 +336,7 @@ pub fn update_network_params(
     msg!("Agriculture reward rate: {} -> {} PKN per submission", 
          old_agriculture_rate / 10_u64.pow(9), agriculture_reward_rate / 10_u64.pow(9));
 
+    emit_cpi!(ParamsUpdated::from_network_state(network_state));
+
     Ok(())
 }
\ No newline at end of file
@@ -1,18 +1,22 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Token, TokenAccount, Transfer, transfer};
+use anchor_spl::token::{Mint, Token, TokenAccount, Transfer, transfer};
 use crate::state::*;
 use crate::constants::*;
 use crate::errors::*;
+use crate::events::*;
+use crate::utils::{
+    reset_reward_debt, settle_stake_rewards, transfer_from_treasury, update_pool_rewards,
+    write_delegate_checkpoint, write_vote_checkpoint, SafeMath,
+};
 
+#[event_cpi]
 #[derive(Accounts)]
 #[instruction(pool_type: PoolType)]
 pub struct StakeTokens<'info> {
     #[account(
-        init_if_needed,
-        payer = user,
-        space = StakingPool::LEN,
+        mut,
         seeds = [STAKING_POOL_SEED, &[pool_type as u8]],
-        bump
+        bump = staking_pool.bump
     )]
     pub staking_pool: Account<'info, StakingPool>,
     
@@ -34,8 +38,7 @@ pub struct StakeTokens<'info> {
     
     #[account(
         mut,
-        associated_token::mint = mint,
-        associated_token::authority = staking_pool
+        address = staking_pool.vault @ DePINError::InvalidPoolVault
     )]
     pub pool_token_account: Account<'info, TokenAccount>,
     
@@ -44,7 +47,7 @@ pub struct StakeTokens<'info> {
         seeds = [MINT_SEED],
         bump
     )]
-    pub mint: Account<'info, anchor_spl::token::Mint>,
+    pub mint: Account<'info, Mint>,
     
     #[account(
         mut,
@@ -57,10 +60,18 @@ pub struct StakeTokens<'info> {
         mut,
         seeds = [USER_PROFILE_SEED, user.key().as_ref()],
         bump = user_profile.bump,
-        has_one = user
+        constraint = user_profile.owner == user.key()
     )]
     pub user_profile: Account<'info, UserProfile>,
     
+    // Required while the user delegates their votes
+    #[account(
+        mut,
+        seeds = [DELEGATE_RECORD_SEED, user_profile.delegate.as_ref()],
+        bump = delegate_record.bump
+    )]
+    pub delegate_record: Option<Account<'info, DelegateRecord>>,
+    
     #[account(mut)]
     pub user: Signer<'info>,
     pub system_program: Program<'info, System>,
@@ -79,35 +90,10 @@ pub fn stake_tokens(
     let network_state = &mut ctx.accounts.network_state;
     let user_profile = &mut ctx.accounts.user_profile;
 
-    // Initialize staking pool if needed
-    if staking_pool.pool_type == PoolType::WiFiInfrastructure && staking_pool.total_staked == 0 {
-        staking_pool.pool_type = pool_type.clone();
-        staking_pool.total_staked = 0;
-        staking_pool.reward_rate = match pool_type {
-            PoolType::WiFiInfrastructure => 1200, // 12% APY
-            PoolType::LogisticsOptimization => 1500, // 15% APY
-            PoolType::AgricultureData => 1800, // 18% APY
-            PoolType::Governance => 800, // 8% APY
-            PoolType::LiquidityMining => 2500, // 25% APY
-        };
-        staking_pool.min_stake_amount = 100 * 10_u64.pow(9); // 100 PKN minimum
-        staking_pool.max_capacity = 1_000_000 * 10_u64.pow(9); // 1M PKN capacity
-        staking_pool.lock_period = match pool_type {
-            PoolType::WiFiInfrastructure => 30 * 24 * 3600, // 30 days
-            PoolType::LogisticsOptimization => 90 * 24 * 3600, // 90 days
-            PoolType::AgricultureData => 180 * 24 * 3600, // 180 days
-            PoolType::Governance => 0, // No lock period
-            PoolType::LiquidityMining => 14 * 24 * 3600, // 14 days
-        };
-        staking_pool.total_rewards_paid = 0;
-        staking_pool.is_active = true;
-        staking_pool.bump = ctx.bumps.staking_pool;
-    }
-
     require!(staking_pool.is_active, DePINError::PoolInactive);
     require!(amount >= staking_pool.min_stake_amount, DePINError::BelowMinimumStake);
     require!(
-        staking_pool.total_staked + amount <= staking_pool.max_capacity,
+        staking_pool.total_staked.safe_add(amount)? <= staking_pool.max_capacity,
         DePINError::PoolCapacityExceeded
     );
 
@@ -118,12 +104,16 @@ pub fn stake_tokens(
         user_stake.user = ctx.accounts.user.key();
         user_stake.pool = staking_pool.key();
         user_stake.amount = 0;
-        user_stake.start_time = current_time;
         user_stake.last_reward_claim = current_time;
         user_stake.pending_rewards = 0;
+        user_stake.reward_debt = 0;
         user_stake.bump = ctx.bumps.user_stake;
     }
 
+    // Settle rewards accrued on the existing stake before its size changes
+    update_pool_rewards(staking_pool, current_time)?;
+    settle_stake_rewards(user_stake, staking_pool)?;
+
     // Transfer tokens to staking pool
     let cpi_accounts = Transfer {
         from: ctx.accounts.user_token_account.to_account_info(),
@@ -134,18 +124,37 @@ pub fn stake_tokens(
     let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
     transfer(cpi_ctx, amount)?;
 
-    // Update balances
-    user_stake.amount += amount;
-    staking_pool.total_staked += amount;
-    network_state.total_staked += amount;
-    user_profile.total_staked += amount;
+    // Update balances; adding stake restarts the lock on the whole position
+    user_stake.amount = user_stake.amount.safe_add(amount)?;
+    user_stake.start_time = current_time;
+    staking_pool.total_staked = staking_pool.total_staked.safe_add(amount)?;
+    network_state.total_staked = network_state.total_staked.safe_add(amount)?;
+    user_profile.total_staked = user_profile.total_staked.safe_add(amount)?;
+    write_vote_checkpoint(user_profile, current_time, network_state.last_proposal_time)?;
+
+    // Delegated stake counts towards the delegate's voting power
+    if user_profile.delegate != Pubkey::default() {
+        let delegate_record = ctx.accounts.delegate_record.as_mut().ok_or(DePINError::InvalidDelegate)?;
+        delegate_record.delegated_power = delegate_record.delegated_power.safe_add(amount)?;
+        write_delegate_checkpoint(delegate_record, current_time, network_state.last_proposal_time)?;
+    }
+    reset_reward_debt(user_stake, staking_pool)?;
 
     msg!("Successfully staked {} PKN in {:?} pool", amount / 10_u64.pow(9), pool_type);
     msg!("Total staked in pool: {} PKN", staking_pool.total_staked / 10_u64.pow(9));
 
+    emit_cpi!(Staked {
+        user: ctx.accounts.user.key(),
+        pool: staking_pool.key(),
+        pool_type,
+        amount,
+        total_staked: user_stake.amount,
+    });
+
     Ok(())
 }
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct UnstakeTokens<'info> {
     #[account(
@@ -160,7 +169,7 @@ pub struct UnstakeTokens<'info> {
         seeds = [USER_STAKE_SEED, user.key().as_ref(), staking_pool.key().as_ref()],
         bump = user_stake.bump,
         has_one = user,
-        has_one = pool @ staking_pool
+        constraint = user_stake.pool == staking_pool.key()
     )]
     pub user_stake: Account<'info, UserStake>,
     
@@ -173,8 +182,7 @@ pub struct UnstakeTokens<'info> {
     
     #[account(
         mut,
-        associated_token::mint = mint,
-        associated_token::authority = staking_pool
+        address = staking_pool.vault @ DePINError::InvalidPoolVault
     )]
     pub pool_token_account: Account<'info, TokenAccount>,
     
@@ -182,7 +190,7 @@ pub struct UnstakeTokens<'info> {
         seeds = [MINT_SEED],
         bump
     )]
-    pub mint: Account<'info, anchor_spl::token::Mint>,
+    pub mint: Account<'info, Mint>,
     
     #[account(
         mut,
@@ -195,10 +203,18 @@ pub struct UnstakeTokens<'info> {
         mut,
         seeds = [USER_PROFILE_SEED, user.key().as_ref()],
         bump = user_profile.bump,
-        has_one = user
+        constraint = user_profile.owner == user.key()
     )]
     pub user_profile: Account<'info, UserProfile>,
     
+    // Required while the user delegates their votes
+    #[account(
+        mut,
+        seeds = [DELEGATE_RECORD_SEED, user_profile.delegate.as_ref()],
+        bump = delegate_record.bump
+    )]
+    pub delegate_record: Option<Account<'info, DelegateRecord>>,
+    
     pub user: Signer<'info>,
     pub token_program: Program<'info, Token>,
 }
@@ -220,18 +236,14 @@ pub fn unstake_tokens(
     let current_time = Clock::get()?.unix_timestamp;
     if staking_pool.lock_period > 0 {
         require!(
-            current_time >= user_stake.start_time + staking_pool.lock_period,
+            current_time >= user_stake.start_time.safe_add(staking_pool.lock_period)?,
             DePINError::StakeLocked
         );
     }
 
-    // Calculate and add pending rewards before unstaking
-    let time_staked = current_time - user_stake.last_reward_claim;
-    let reward_rate_per_second = staking_pool.reward_rate / (365 * 24 * 3600 * 100); // Convert APY to per-second rate
-    let rewards = (user_stake.amount * reward_rate_per_second * time_staked as u64) / 10_u64.pow(9);
-    
-    user_stake.pending_rewards += rewards;
-    user_stake.last_reward_claim = current_time;
+    // Settle rewards accrued on the full stake before unstaking
+    update_pool_rewards(staking_pool, current_time)?;
+    settle_stake_rewards(user_stake, staking_pool)?;
 
     // Transfer tokens back to user (using PDA signer)
     let pool_type_bytes = [staking_pool.pool_type as u8];
@@ -252,17 +264,34 @@ pub fn unstake_tokens(
     transfer(cpi_ctx, amount)?;
 
     // Update balances
-    user_stake.amount -= amount;
-    staking_pool.total_staked -= amount;
-    network_state.total_staked -= amount;
-    user_profile.total_staked -= amount;
+    user_stake.amount = user_stake.amount.safe_sub(amount)?;
+    staking_pool.total_staked = staking_pool.total_staked.safe_sub(amount)?;
+    network_state.total_staked = network_state.total_staked.safe_sub(amount)?;
+    user_profile.total_staked = user_profile.total_staked.safe_sub(amount)?;
+    write_vote_checkpoint(user_profile, current_time, network_state.last_proposal_time)?;
+
+    if user_profile.delegate != Pubkey::default() {
+        let delegate_record = ctx.accounts.delegate_record.as_mut().ok_or(DePINError::InvalidDelegate)?;
+        delegate_record.delegated_power = delegate_record.delegated_power.safe_sub(amount)?;
+        write_delegate_checkpoint(delegate_record, current_time, network_state.last_proposal_time)?;
+    }
+    reset_reward_debt(user_stake, staking_pool)?;
 
     msg!("Successfully unstaked {} PKN", amount / 10_u64.pow(9));
     msg!("Pending rewards: {} PKN", user_stake.pending_rewards / 10_u64.pow(9));
 
+    emit_cpi!(Unstaked {
+        user: ctx.accounts.user.key(),
+        pool: staking_pool.key(),
+        pool_type: staking_pool.pool_type,
+        amount,
+        remaining_stake: user_stake.amount,
+    });
+
     Ok(())
 }
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct ClaimRewards<'info> {
     #[account(
@@ -277,7 +306,7 @@ pub struct ClaimRewards<'info> {
         seeds = [USER_STAKE_SEED, user.key().as_ref(), staking_pool.key().as_ref()],
         bump = user_stake.bump,
         has_one = user,
-        has_one = pool @ staking_pool
+        constraint = user_stake.pool == staking_pool.key()
     )]
     pub user_stake: Account<'info, UserStake>,
     
@@ -288,17 +317,23 @@ pub struct ClaimRewards<'info> {
     )]
     pub user_token_account: Account<'info, TokenAccount>,
     
+    #[account(
+        mut,
+        address = staking_pool.reward_vault @ DePINError::InvalidPoolVault
+    )]
+    pub reward_vault: Account<'info, TokenAccount>,
+    
     #[account(
         seeds = [MINT_SEED],
         bump
     )]
-    pub mint: Account<'info, anchor_spl::token::Mint>,
+    pub mint: Account<'info, Mint>,
     
     #[account(
         mut,
         seeds = [USER_PROFILE_SEED, user.key().as_ref()],
         bump = user_profile.bump,
-        has_one = user
+        constraint = user_profile.owner == user.key()
     )]
     pub user_profile: Account<'info, UserProfile>,
     
@@ -311,27 +346,272 @@ pub fn claim_rewards(ctx: Context<ClaimRewards>) -> Result<()> {
     let user_stake = &mut ctx.accounts.user_stake;
     let user_profile = &mut ctx.accounts.user_profile;
 
-    require!(user_stake.amount > 0, DePINError::NoStakeFound);
+    // Rewards settled by a full unstake remain claimable
+    require!(
+        user_stake.amount > 0 || user_stake.pending_rewards > 0,
+        DePINError::NoStakeFound
+    );
 
     let current_time = Clock::get()?.unix_timestamp;
 
-    // Calculate rewards since last claim
-    let time_staked = current_time - user_stake.last_reward_claim;
-    let reward_rate_per_second = staking_pool.reward_rate / (365 * 24 * 3600 * 100); // Convert APY to per-second rate
-    let new_rewards = (user_stake.amount * reward_rate_per_second * time_staked as u64) / 10_u64.pow(9);
-    
-    let total_rewards = user_stake.pending_rewards + new_rewards;
-    
+    // Settle rewards accrued since the last settlement
+    update_pool_rewards(staking_pool, current_time)?;
+    settle_stake_rewards(user_stake, staking_pool)?;
+
+    let total_rewards = user_stake.pending_rewards;
+
     require!(total_rewards > 0, DePINError::NoRewardsToClaim);
+    require!(
+        ctx.accounts.reward_vault.amount >= total_rewards,
+        DePINError::InsufficientRewardVault
+    );
+
+    // Pay out of the pool's reward vault (using PDA signer)
+    let pool_type_bytes = [staking_pool.pool_type as u8];
+    let signer_seeds = &[
+        STAKING_POOL_SEED,
+        &pool_type_bytes,
+        &[staking_pool.bump],
+    ];
+    let signer = &[&signer_seeds[..]];
+
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.reward_vault.to_account_info(),
+        to: ctx.accounts.user_token_account.to_account_info(),
+        authority: staking_pool.to_account_info(),
+    };
+    let cpi_program = ctx.accounts.token_program.to_account_info();
+    let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+    transfer(cpi_ctx, total_rewards)?;
 
-    // In production, this would mint new tokens or transfer from treasury
-    // For now, we'll emit an event for the frontend to handle
     user_stake.pending_rewards = 0;
     user_stake.last_reward_claim = current_time;
-    staking_pool.total_rewards_paid += total_rewards;
-    user_profile.total_earned += total_rewards;
+    staking_pool.total_rewards_paid = staking_pool.total_rewards_paid.safe_add(total_rewards)?;
+    user_profile.total_earned = user_profile.total_earned.safe_add(total_rewards)?;
 
     msg!("Successfully claimed {} PKN rewards", total_rewards / 10_u64.pow(9));
 
+    emit_cpi!(RewardsClaimed {
+        user: ctx.accounts.user.key(),
+        pool: staking_pool.key(),
+        amount: total_rewards,
+    });
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct FundStakingRewards<'info> {
+    #[account(
+        seeds = [STAKING_POOL_SEED, &[staking_pool.pool_type as u8]],
+        bump = staking_pool.bump
+    )]
+    pub staking_pool: Account<'info, StakingPool>,
+    
+    #[account(
+        mut,
+        address = staking_pool.reward_vault @ DePINError::InvalidPoolVault
+    )]
+    pub reward_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [NETWORK_STATE_SEED, TREASURY_SEED],
+        bump = network_state.treasury_bump
+    )]
+    pub treasury: Account<'info, TokenAccount>,
+    
+    #[account(
+        seeds = [NETWORK_STATE_SEED],
+        bump = network_state.bump,
+        has_one = authority
+    )]
+    pub network_state: Account<'info, NetworkState>,
+    
+    pub authority: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+/// Move PKN from the treasury into a staking pool's reward vault (admin only)
+pub fn fund_staking_rewards(ctx: Context<FundStakingRewards>, amount: u64) -> Result<()> {
+    require!(amount > 0, DePINError::InvalidAmount);
+
+    transfer_from_treasury(
+        &ctx.accounts.treasury,
+        &ctx.accounts.reward_vault,
+        &ctx.accounts.network_state,
+        &ctx.accounts.token_program,
+        amount,
+    )?;
+
+    msg!("Funded {:?} pool rewards with {} PKN", ctx.accounts.staking_pool.pool_type, amount / 10_u64.pow(9));
+    msg!("Reward vault balance: {} PKN", ctx.accounts.reward_vault.amount.safe_add(amount)? / 10_u64.pow(9));
+
+    emit_cpi!(StakingRewardsFunded {
+        pool: ctx.accounts.staking_pool.key(),
+        amount,
+    });
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(pool_type: PoolType)]
+pub struct CreateStakingPool<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = StakingPool::LEN,
+        seeds = [STAKING_POOL_SEED, &[pool_type as u8]],
+        bump
+    )]
+    pub staking_pool: Account<'info, StakingPool>,
+    
+    #[account(
+        init,
+        payer = authority,
+        token::mint = mint,
+        token::authority = staking_pool,
+        seeds = [POOL_VAULT_SEED, staking_pool.key().as_ref()],
+        bump
+    )]
+    pub pool_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        init,
+        payer = authority,
+        token::mint = mint,
+        token::authority = staking_pool,
+        seeds = [REWARD_VAULT_SEED, staking_pool.key().as_ref()],
+        bump
+    )]
+    pub reward_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        seeds = [MINT_SEED],
+        bump
+    )]
+    pub mint: Account<'info, Mint>,
+    
+    #[account(
+        seeds = [NETWORK_STATE_SEED],
+        bump = network_state.bump,
+        has_one = authority
+    )]
+    pub network_state: Account<'info, NetworkState>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+}
+
+/// Create a staking pool with explicit parameters (admin only)
+pub fn create_staking_pool(
+    ctx: Context<CreateStakingPool>,
+    pool_type: PoolType,
+    reward_rate: u64,
+    min_stake_amount: u64,
+    max_capacity: u64,
+    lock_period: i64,
+    is_active: bool,
+) -> Result<()> {
+    require!(min_stake_amount > 0, DePINError::InvalidPoolConfig);
+    require!(max_capacity >= min_stake_amount, DePINError::InvalidPoolConfig);
+    require!(lock_period >= 0, DePINError::InvalidPoolConfig);
+
+    let staking_pool = &mut ctx.accounts.staking_pool;
+
+    staking_pool.pool_type = pool_type;
+    staking_pool.vault = ctx.accounts.pool_vault.key();
+    staking_pool.reward_vault = ctx.accounts.reward_vault.key();
+    staking_pool.total_staked = 0;
+    staking_pool.reward_rate = reward_rate;
+    staking_pool.min_stake_amount = min_stake_amount;
+    staking_pool.max_capacity = max_capacity;
+    staking_pool.lock_period = lock_period;
+    staking_pool.total_rewards_paid = 0;
+    staking_pool.reward_per_token_stored = 0;
+    staking_pool.last_update_time = Clock::get()?.unix_timestamp;
+    staking_pool.is_active = is_active;
+    staking_pool.bump = ctx.bumps.staking_pool;
+
+    msg!("{:?} staking pool created with vault {}", pool_type, staking_pool.vault);
+    msg!("APY: {} bps, Min stake: {} PKN, Capacity: {} PKN, Lock period: {} days",
+         reward_rate, min_stake_amount / 10_u64.pow(9), max_capacity / 10_u64.pow(9), lock_period / SECONDS_PER_DAY);
+
+    emit_cpi!(StakingPoolConfigured {
+        pool: staking_pool.key(),
+        pool_type: staking_pool.pool_type,
+        reward_rate: staking_pool.reward_rate,
+        min_stake_amount: staking_pool.min_stake_amount,
+        max_capacity: staking_pool.max_capacity,
+        lock_period: staking_pool.lock_period,
+        is_active: staking_pool.is_active,
+    });
+
     Ok(())
-}
\ No newline at end of file
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateStakingPool<'info> {
+    #[account(
+        mut,
+        seeds = [STAKING_POOL_SEED, &[staking_pool.pool_type as u8]],
+        bump = staking_pool.bump
+    )]
+    pub staking_pool: Account<'info, StakingPool>,
+    
+    #[account(
+        seeds = [NETWORK_STATE_SEED],
+        bump = network_state.bump,
+        has_one = authority
+    )]
+    pub network_state: Account<'info, NetworkState>,
+    
+    pub authority: Signer<'info>,
+}
+
+/// Update a staking pool's parameters (admin only)
+pub fn update_staking_pool(
+    ctx: Context<UpdateStakingPool>,
+    reward_rate: u64,
+    min_stake_amount: u64,
+    max_capacity: u64,
+    lock_period: i64,
+    is_active: bool,
+) -> Result<()> {
+    let staking_pool = &mut ctx.accounts.staking_pool;
+
+    require!(min_stake_amount > 0, DePINError::InvalidPoolConfig);
+    require!(max_capacity >= min_stake_amount, DePINError::InvalidPoolConfig);
+    require!(lock_period >= 0, DePINError::InvalidPoolConfig);
+
+    // Accrue at the old rate up to now before the new rate takes effect
+    update_pool_rewards(staking_pool, Clock::get()?.unix_timestamp)?;
+
+    staking_pool.reward_rate = reward_rate;
+    staking_pool.min_stake_amount = min_stake_amount;
+    staking_pool.max_capacity = max_capacity;
+    staking_pool.lock_period = lock_period;
+    staking_pool.is_active = is_active;
+
+    msg!("{:?} staking pool updated", staking_pool.pool_type);
+    msg!("APY: {} bps, Min stake: {} PKN, Capacity: {} PKN, Lock period: {} days, Active: {}",
+         reward_rate, min_stake_amount / 10_u64.pow(9), max_capacity / 10_u64.pow(9), lock_period / SECONDS_PER_DAY, is_active);
+
+    emit_cpi!(StakingPoolConfigured {
+        pool: staking_pool.key(),
+        pool_type: staking_pool.pool_type,
+        reward_rate: staking_pool.reward_rate,
+        min_stake_amount: staking_pool.min_stake_amount,
+        max_capacity: staking_pool.max_capacity,
+        lock_period: staking_pool.lock_period,
+        is_active: staking_pool.is_active,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,146 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount, Transfer, transfer};
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::*;
+use crate::events::*;
+use crate::utils::transfer_from_treasury;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct DepositTreasury<'info> {
+    #[account(
+        mut,
+        seeds = [NETWORK_STATE_SEED, TREASURY_SEED],
+        bump = network_state.treasury_bump
+    )]
+    pub treasury: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = authority
+    )]
+    pub authority_token_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        seeds = [MINT_SEED],
+        bump
+    )]
+    pub mint: Account<'info, Mint>,
+    
+    #[account(
+        seeds = [NETWORK_STATE_SEED],
+        bump = network_state.bump,
+        has_one = authority
+    )]
+    pub network_state: Account<'info, NetworkState>,
+    
+    pub authority: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn deposit_treasury(ctx: Context<DepositTreasury>, amount: u64) -> Result<()> {
+    require!(amount > 0, DePINError::InvalidAmount);
+
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.authority_token_account.to_account_info(),
+        to: ctx.accounts.treasury.to_account_info(),
+        authority: ctx.accounts.authority.to_account_info(),
+    };
+    let cpi_program = ctx.accounts.token_program.to_account_info();
+    let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+    transfer(cpi_ctx, amount)?;
+
+    msg!("Deposited {} PKN into the treasury", amount / 10_u64.pow(9));
+
+    emit_cpi!(TreasuryDeposited {
+        from: ctx.accounts.authority.key(),
+        amount,
+    });
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct WithdrawTreasury<'info> {
+    #[account(
+        mut,
+        seeds = [NETWORK_STATE_SEED, TREASURY_SEED],
+        bump = network_state.treasury_bump
+    )]
+    pub treasury: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        token::mint = mint
+    )]
+    pub recipient_token_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        seeds = [MINT_SEED],
+        bump
+    )]
+    pub mint: Account<'info, Mint>,
+    
+    #[account(
+        seeds = [NETWORK_STATE_SEED],
+        bump = network_state.bump,
+        has_one = authority
+    )]
+    pub network_state: Account<'info, NetworkState>,
+    
+    pub authority: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn withdraw_treasury(ctx: Context<WithdrawTreasury>, amount: u64) -> Result<()> {
+    require!(amount > 0, DePINError::InvalidAmount);
+
+    transfer_from_treasury(
+        &ctx.accounts.treasury,
+        &ctx.accounts.recipient_token_account,
+        &ctx.accounts.network_state,
+        &ctx.accounts.token_program,
+        amount,
+    )?;
+
+    msg!("Withdrew {} PKN from the treasury to {}",
+         amount / 10_u64.pow(9), ctx.accounts.recipient_token_account.key());
+
+    emit_cpi!(TreasuryWithdrawn {
+        recipient: ctx.accounts.recipient_token_account.key(),
+        amount,
+    });
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetRewardSource<'info> {
+    #[account(
+        mut,
+        seeds = [NETWORK_STATE_SEED],
+        bump = network_state.bump,
+        has_one = authority
+    )]
+    pub network_state: Account<'info, NetworkState>,
+    
+    pub authority: Signer<'info>,
+}
+
+pub fn set_reward_source(ctx: Context<SetRewardSource>, reward_source: RewardSource) -> Result<()> {
+    let network_state = &mut ctx.accounts.network_state;
+    network_state.reward_source = reward_source;
+
+    msg!("Operator rewards are now paid from the {}", match network_state.reward_source {
+        RewardSource::Mint => "mint",
+        RewardSource::Treasury => "treasury",
+    });
+
+    emit_cpi!(ParamsUpdated::from_network_state(network_state));
+
+    Ok(())
+}
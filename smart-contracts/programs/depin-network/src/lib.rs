@@ -1,17 +1,15 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Mint, Transfer};
-use anchor_spl::associated_token::AssociatedToken;
 
 declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
 
 pub mod constants;
 pub mod errors;
+pub mod events;
 pub mod instructions;
 pub mod state;
 pub mod utils;
 
 use constants::*;
-use errors::*;
 use instructions::*;
 use state::*;
 
@@ -29,34 +27,76 @@ pub mod depin_network {
     }
 
     /// Register a WiFi hotspot infrastructure
+    #[allow(clippy::too_many_arguments)]
     pub fn register_wifi_hotspot(
         ctx: Context<RegisterWiFiHotspot>,
+        device_pubkey: Pubkey,
         location: String,
+        latitude: i32,
+        longitude: i32,
+        h3_index: u64,
         coverage_radius: u32,
         bandwidth_mbps: u32,
+        metadata_uri: String,
     ) -> Result<()> {
-        instructions::register_wifi_hotspot(ctx, location, coverage_radius, bandwidth_mbps)
+        instructions::register_wifi_hotspot(ctx, device_pubkey, location, latitude, longitude, h3_index, coverage_radius, bandwidth_mbps, metadata_uri)
+    }
+
+    /// Issue a proof-of-coverage challenge against a hotspot, escrowing the challenger's bond
+    pub fn issue_poc_challenge(ctx: Context<IssuePocChallenge>) -> Result<()> {
+        instructions::issue_poc_challenge(ctx)
+    }
+
+    /// Submit a device-signed witness receipt for a coverage challenge
+    pub fn submit_witness_receipt(
+        ctx: Context<SubmitWitnessReceipt>,
+        signal_strength_dbm: i16,
+        nonce: u64,
+        timestamp: i64,
+    ) -> Result<()> {
+        instructions::submit_witness_receipt(ctx, signal_strength_dbm, nonce, timestamp)
+    }
+
+    /// Resolve a coverage challenge, update the hotspot's reputation and settle the bond
+    pub fn resolve_poc_challenge(ctx: Context<ResolvePocChallenge>) -> Result<()> {
+        instructions::resolve_poc_challenge(ctx)
+    }
+
+    /// Update the H3 resolution and per-hex hotspot density target (admin only)
+    pub fn update_hex_config(
+        ctx: Context<UpdateHexConfig>,
+        h3_resolution: u8,
+        hex_density_target: u32,
+    ) -> Result<()> {
+        instructions::update_hex_config(ctx, h3_resolution, hex_density_target)
     }
 
     /// Register a logistics partner
     pub fn register_logistics_partner(
         ctx: Context<RegisterLogisticsPartner>,
+        device_pubkey: Pubkey,
         partner_name: String,
         service_areas: Vec<String>,
         vehicle_count: u32,
+        metadata_uri: String,
     ) -> Result<()> {
-        instructions::register_logistics_partner(ctx, partner_name, service_areas, vehicle_count)
+        instructions::register_logistics_partner(ctx, device_pubkey, partner_name, service_areas, vehicle_count, metadata_uri)
     }
 
     /// Register an agricultural farm
+    #[allow(clippy::too_many_arguments)]
     pub fn register_farm(
         ctx: Context<RegisterFarm>,
+        device_pubkey: Pubkey,
         farm_name: String,
         location: String,
+        latitude: i32,
+        longitude: i32,
         farm_size_acres: u32,
         crop_types: Vec<String>,
+        metadata_uri: String,
     ) -> Result<()> {
-        instructions::register_farm(ctx, farm_name, location, farm_size_acres, crop_types)
+        instructions::register_farm(ctx, device_pubkey, farm_name, location, latitude, longitude, farm_size_acres, crop_types, metadata_uri)
     }
 
     /// Submit WiFi network data for rewards
@@ -65,8 +105,10 @@ pub mod depin_network {
         users_connected: u32,
         data_transferred_gb: u64,
         uptime_percentage: u8,
+        nonce: u64,
+        timestamp: i64,
     ) -> Result<()> {
-        instructions::submit_wifi_data(ctx, users_connected, data_transferred_gb, uptime_percentage)
+        instructions::submit_wifi_data(ctx, users_connected, data_transferred_gb, uptime_percentage, nonce, timestamp)
     }
 
     /// Submit logistics data for rewards
@@ -76,8 +118,10 @@ pub mod depin_network {
         distance_traveled_km: u32,
         fuel_efficiency: u32,
         route_optimization_score: u8,
+        nonce: u64,
+        timestamp: i64,
     ) -> Result<()> {
-        instructions::submit_logistics_data(ctx, deliveries_completed, distance_traveled_km, fuel_efficiency, route_optimization_score)
+        instructions::submit_logistics_data(ctx, deliveries_completed, distance_traveled_km, fuel_efficiency, route_optimization_score, nonce, timestamp)
     }
 
     /// Submit agricultural sensor data for rewards
@@ -87,30 +131,42 @@ pub mod depin_network {
         temperature: i16,
         humidity: u8,
         ph_level: u8,
+        nonce: u64,
+        timestamp: i64,
     ) -> Result<()> {
-        instructions::submit_agriculture_data(ctx, soil_moisture, temperature, humidity, ph_level)
+        instructions::submit_agriculture_data(ctx, soil_moisture, temperature, humidity, ph_level, nonce, timestamp)
     }
 
     /// Register a healthcare provider
+    #[allow(clippy::too_many_arguments)]
     pub fn register_healthcare_provider(
         ctx: Context<RegisterHealthcareProvider>,
+        device_pubkey: Pubkey,
         provider_name: String,
         provider_type: String,
         location: String,
+        latitude: i32,
+        longitude: i32,
         license_number: String,
+        metadata_uri: String,
     ) -> Result<()> {
-        instructions::register_healthcare_provider(ctx, provider_name, provider_type, location, license_number)
+        instructions::register_healthcare_provider(ctx, device_pubkey, provider_name, provider_type, location, latitude, longitude, license_number, metadata_uri)
     }
 
     /// Register a tax collection point
+    #[allow(clippy::too_many_arguments)]
     pub fn register_tax_point(
         ctx: Context<RegisterTaxPoint>,
+        device_pubkey: Pubkey,
         point_name: String,
         authority_type: String,
         location: String,
+        latitude: i32,
+        longitude: i32,
         jurisdiction: String,
+        metadata_uri: String,
     ) -> Result<()> {
-        instructions::register_tax_point(ctx, point_name, authority_type, location, jurisdiction)
+        instructions::register_tax_point(ctx, device_pubkey, point_name, authority_type, location, latitude, longitude, jurisdiction, metadata_uri)
     }
 
     /// Submit healthcare data for rewards
@@ -118,8 +174,10 @@ pub mod depin_network {
         ctx: Context<SubmitHealthcareData>,
         records_count: u32,
         data_quality_score: u8,
+        nonce: u64,
+        timestamp: i64,
     ) -> Result<()> {
-        instructions::submit_healthcare_data(ctx, records_count, data_quality_score)
+        instructions::submit_healthcare_data(ctx, records_count, data_quality_score, nonce, timestamp)
     }
 
     /// Submit taxation data for rewards
@@ -128,8 +186,310 @@ pub mod depin_network {
         records_count: u32,
         amount_processed: u64,
         verification_score: u8,
+        nonce: u64,
+        timestamp: i64,
+    ) -> Result<()> {
+        instructions::submit_taxation_data(ctx, records_count, amount_processed, verification_score, nonce, timestamp)
+    }
+
+    /// Register an EV charger on a charging plot the owner holds
+    #[allow(clippy::too_many_arguments)]
+    pub fn register_ev_charger(
+        ctx: Context<RegisterEVCharger>,
+        device_pubkey: Pubkey,
+        charger_name: String,
+        charger_type: ChargerType,
+        power_output_kw: u32,
+        latitude: i32,
+        longitude: i32,
+        metadata_uri: String,
+    ) -> Result<()> {
+        instructions::register_ev_charger(ctx, device_pubkey, charger_name, charger_type, power_output_kw, latitude, longitude, metadata_uri)
+    }
+
+    /// Start a charging session on a free EV charger
+    pub fn start_charging_session(ctx: Context<StartChargingSession>) -> Result<()> {
+        instructions::start_charging_session(ctx)
+    }
+
+    /// End a charging session with the energy delivered, signed by the charger's device and co-signed by the driver
+    pub fn end_charging_session(
+        ctx: Context<EndChargingSession>,
+        energy_delivered_wh: u64,
+        nonce: u64,
+        timestamp: i64,
+    ) -> Result<()> {
+        instructions::end_charging_session(ctx, energy_delivered_wh, nonce, timestamp)
+    }
+
+    /// Cancel a charging session without a reward, by its driver or an operator after it expires
+    pub fn cancel_charging_session(ctx: Context<CancelChargingSession>) -> Result<()> {
+        instructions::cancel_charging_session(ctx)
+    }
+
+    /// Lay out a city's grid of charging plots (admin only)
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_plot_city(
+        ctx: Context<CreatePlotCity>,
+        name: String,
+        origin_latitude: i32,
+        origin_longitude: i32,
+        cell_size: u32,
+        rows: u16,
+        cols: u16,
+        plot_price: u64,
+    ) -> Result<()> {
+        instructions::create_plot_city(ctx, name, origin_latitude, origin_longitude, cell_size, rows, cols, plot_price)
+    }
+
+    /// Set the price of unsold plots in a city (admin only)
+    pub fn update_plot_price(ctx: Context<UpdatePlotPrice>, plot_price: u64) -> Result<()> {
+        instructions::update_plot_price(ctx, plot_price)
+    }
+
+    /// Buy an unsold charging plot from the treasury, paying at most `max_price`
+    pub fn purchase_plot(ctx: Context<PurchasePlot>, row: u16, col: u16, max_price: u64) -> Result<()> {
+        instructions::purchase_plot(ctx, row, col, max_price)
+    }
+
+    /// List an empty charging plot for sale
+    pub fn list_plot(ctx: Context<ListPlot>, price: u64) -> Result<()> {
+        instructions::list_plot(ctx, price)
+    }
+
+    /// Take a charging plot off the market
+    pub fn cancel_listing(ctx: Context<CancelListing>) -> Result<()> {
+        instructions::cancel_listing(ctx)
+    }
+
+    /// Buy a listed charging plot from its seller
+    pub fn buy_listed_plot(ctx: Context<BuyListedPlot>, max_price: u64) -> Result<()> {
+        instructions::buy_listed_plot(ctx, max_price)
+    }
+
+    /// Move a WiFi hotspot or change its radio settings
+    pub fn update_wifi_hotspot(
+        ctx: Context<UpdateWiFiHotspot>,
+        location: String,
+        latitude: i32,
+        longitude: i32,
+        h3_index: u64,
+        coverage_radius: u32,
+        bandwidth_mbps: u32,
+    ) -> Result<()> {
+        instructions::update_wifi_hotspot(ctx, location, latitude, longitude, h3_index, coverage_radius, bandwidth_mbps)
+    }
+
+    /// Take a WiFi hotspot out of service
+    pub fn deactivate_wifi_hotspot(ctx: Context<SetWiFiHotspotStatus>) -> Result<()> {
+        instructions::deactivate_wifi_hotspot(ctx)
+    }
+
+    /// Return a WiFi hotspot to service
+    pub fn reactivate_wifi_hotspot(ctx: Context<SetWiFiHotspotStatus>) -> Result<()> {
+        instructions::reactivate_wifi_hotspot(ctx)
+    }
+
+    /// Close a WiFi hotspot and reclaim its rent
+    pub fn close_wifi_hotspot(ctx: Context<CloseWiFiHotspot>) -> Result<()> {
+        instructions::close_wifi_hotspot(ctx)
+    }
+
+    /// Update a logistics partner's name, service areas and fleet size
+    pub fn update_logistics_partner(
+        ctx: Context<UpdateLogisticsPartner>,
+        partner_name: String,
+        service_areas: Vec<String>,
+        vehicle_count: u32,
+    ) -> Result<()> {
+        instructions::update_logistics_partner(ctx, partner_name, service_areas, vehicle_count)
+    }
+
+    /// Take a logistics partner out of service
+    pub fn deactivate_logistics_partner(ctx: Context<SetLogisticsPartnerStatus>) -> Result<()> {
+        instructions::deactivate_logistics_partner(ctx)
+    }
+
+    /// Return a logistics partner to service
+    pub fn reactivate_logistics_partner(ctx: Context<SetLogisticsPartnerStatus>) -> Result<()> {
+        instructions::reactivate_logistics_partner(ctx)
+    }
+
+    /// Close a logistics partner and reclaim its rent
+    pub fn close_logistics_partner(ctx: Context<CloseLogisticsPartner>) -> Result<()> {
+        instructions::close_logistics_partner(ctx)
+    }
+
+    /// Update a farm's details and crops
+    pub fn update_farm(
+        ctx: Context<UpdateFarm>,
+        farm_name: String,
+        location: String,
+        latitude: i32,
+        longitude: i32,
+        farm_size_acres: u32,
+        crop_types: Vec<String>,
+    ) -> Result<()> {
+        instructions::update_farm(ctx, farm_name, location, latitude, longitude, farm_size_acres, crop_types)
+    }
+
+    /// Take a farm out of service
+    pub fn deactivate_farm(ctx: Context<SetFarmStatus>) -> Result<()> {
+        instructions::deactivate_farm(ctx)
+    }
+
+    /// Return a farm to service
+    pub fn reactivate_farm(ctx: Context<SetFarmStatus>) -> Result<()> {
+        instructions::reactivate_farm(ctx)
+    }
+
+    /// Close a farm and reclaim its rent
+    pub fn close_farm(ctx: Context<CloseFarm>) -> Result<()> {
+        instructions::close_farm(ctx)
+    }
+
+    /// Update a healthcare provider's details
+    pub fn update_healthcare_provider(
+        ctx: Context<UpdateHealthcareProvider>,
+        provider_name: String,
+        provider_type: String,
+        location: String,
+        latitude: i32,
+        longitude: i32,
+        license_number: String,
+    ) -> Result<()> {
+        instructions::update_healthcare_provider(ctx, provider_name, provider_type, location, latitude, longitude, license_number)
+    }
+
+    /// Take a healthcare provider out of service
+    pub fn deactivate_healthcare_provider(ctx: Context<SetHealthcareProviderStatus>) -> Result<()> {
+        instructions::deactivate_healthcare_provider(ctx)
+    }
+
+    /// Return a healthcare provider to service
+    pub fn reactivate_healthcare_provider(ctx: Context<SetHealthcareProviderStatus>) -> Result<()> {
+        instructions::reactivate_healthcare_provider(ctx)
+    }
+
+    /// Close a healthcare provider and reclaim its rent
+    pub fn close_healthcare_provider(ctx: Context<CloseHealthcareProvider>) -> Result<()> {
+        instructions::close_healthcare_provider(ctx)
+    }
+
+    /// Update a tax collection point's details
+    pub fn update_tax_point(
+        ctx: Context<UpdateTaxPoint>,
+        point_name: String,
+        authority_type: String,
+        location: String,
+        latitude: i32,
+        longitude: i32,
+        jurisdiction: String,
+    ) -> Result<()> {
+        instructions::update_tax_point(ctx, point_name, authority_type, location, latitude, longitude, jurisdiction)
+    }
+
+    /// Take a tax collection point out of service
+    pub fn deactivate_tax_point(ctx: Context<SetTaxPointStatus>) -> Result<()> {
+        instructions::deactivate_tax_point(ctx)
+    }
+
+    /// Return a tax collection point to service
+    pub fn reactivate_tax_point(ctx: Context<SetTaxPointStatus>) -> Result<()> {
+        instructions::reactivate_tax_point(ctx)
+    }
+
+    /// Close a tax collection point and reclaim its rent
+    pub fn close_tax_point(ctx: Context<CloseTaxPoint>) -> Result<()> {
+        instructions::close_tax_point(ctx)
+    }
+
+    /// Update an EV charger's details
+    pub fn update_ev_charger(
+        ctx: Context<UpdateEVCharger>,
+        charger_name: String,
+        charger_type: ChargerType,
+        power_output_kw: u32,
+        latitude: i32,
+        longitude: i32,
+    ) -> Result<()> {
+        instructions::update_ev_charger(ctx, charger_name, charger_type, power_output_kw, latitude, longitude)
+    }
+
+    /// Take an EV charger out of service
+    pub fn deactivate_ev_charger(ctx: Context<SetEVChargerStatus>) -> Result<()> {
+        instructions::deactivate_ev_charger(ctx)
+    }
+
+    /// Return an EV charger to service
+    pub fn reactivate_ev_charger(ctx: Context<SetEVChargerStatus>) -> Result<()> {
+        instructions::reactivate_ev_charger(ctx)
+    }
+
+    /// Close an EV charger and reclaim its rent
+    pub fn close_ev_charger(ctx: Context<CloseEVCharger>) -> Result<()> {
+        instructions::close_ev_charger(ctx)
+    }
+
+    /// Move a WiFi hotspot's owner record to the holder of its NFT. Optional: the holder
+    /// can operate it without claiming.
+    pub fn claim_wifi_hotspot_ownership(ctx: Context<ClaimWiFiHotspotOwnership>) -> Result<()> {
+        instructions::claim_wifi_hotspot_ownership(ctx)
+    }
+
+    /// Move a logistics partner's owner record to the holder of its NFT. Optional: the holder
+    /// can operate it without claiming.
+    pub fn claim_logistics_partner_ownership(ctx: Context<ClaimLogisticsPartnerOwnership>) -> Result<()> {
+        instructions::claim_logistics_partner_ownership(ctx)
+    }
+
+    /// Move a farm's owner record to the holder of its NFT. Optional: the holder
+    /// can operate it without claiming.
+    pub fn claim_farm_ownership(ctx: Context<ClaimFarmOwnership>) -> Result<()> {
+        instructions::claim_farm_ownership(ctx)
+    }
+
+    /// Move a healthcare provider's owner record to the holder of its NFT. Optional: the holder
+    /// can operate it without claiming.
+    pub fn claim_healthcare_provider_ownership(ctx: Context<ClaimHealthcareProviderOwnership>) -> Result<()> {
+        instructions::claim_healthcare_provider_ownership(ctx)
+    }
+
+    /// Move a tax point's owner record to the holder of its NFT. Optional: the holder
+    /// can operate it without claiming.
+    pub fn claim_tax_point_ownership(ctx: Context<ClaimTaxPointOwnership>) -> Result<()> {
+        instructions::claim_tax_point_ownership(ctx)
+    }
+
+    /// Move an EV charger's owner record to the holder of its NFT. Optional: the holder
+    /// can operate it without claiming.
+    pub fn claim_ev_charger_ownership(ctx: Context<ClaimEVChargerOwnership>) -> Result<()> {
+        instructions::claim_ev_charger_ownership(ctx)
+    }
+
+    /// Create a staking pool (admin only)
+    pub fn create_staking_pool(
+        ctx: Context<CreateStakingPool>,
+        pool_type: PoolType,
+        reward_rate: u64,
+        min_stake_amount: u64,
+        max_capacity: u64,
+        lock_period: i64,
+        is_active: bool,
     ) -> Result<()> {
-        instructions::submit_taxation_data(ctx, records_count, amount_processed, verification_score)
+        instructions::create_staking_pool(ctx, pool_type, reward_rate, min_stake_amount, max_capacity, lock_period, is_active)
+    }
+
+    /// Update a staking pool's parameters (admin only)
+    pub fn update_staking_pool(
+        ctx: Context<UpdateStakingPool>,
+        reward_rate: u64,
+        min_stake_amount: u64,
+        max_capacity: u64,
+        lock_period: i64,
+        is_active: bool,
+    ) -> Result<()> {
+        instructions::update_staking_pool(ctx, reward_rate, min_stake_amount, max_capacity, lock_period, is_active)
     }
 
     /// Stake PKN tokens in a staking pool
@@ -154,11 +514,53 @@ pub mod depin_network {
         instructions::claim_rewards(ctx)
     }
 
-    /// Distribute rewards to infrastructure operators
+    /// Fund a staking pool's reward vault from the treasury (admin only)
+    pub fn fund_staking_rewards(ctx: Context<FundStakingRewards>, amount: u64) -> Result<()> {
+        instructions::fund_staking_rewards(ctx, amount)
+    }
+
+    /// Finalize the current reward epoch and open the next one
     pub fn distribute_rewards(ctx: Context<DistributeRewards>) -> Result<()> {
         instructions::distribute_rewards(ctx)
     }
 
+    /// Convert an operator's epoch contribution into claimable rewards
+    pub fn settle_epoch_rewards(ctx: Context<SettleEpochRewards>) -> Result<()> {
+        instructions::settle_epoch_rewards(ctx)
+    }
+
+    /// Claim settled epoch rewards
+    pub fn claim_operator_rewards(ctx: Context<ClaimOperatorRewards>) -> Result<()> {
+        instructions::claim_operator_rewards(ctx)
+    }
+
+    /// Update the per-epoch emission and its sector split (admin only)
+    pub fn update_epoch_emission(
+        ctx: Context<UpdateEpochEmission>,
+        epoch_emission: u64,
+        sector_emission_bps: [u16; SECTOR_COUNT],
+    ) -> Result<()> {
+        instructions::update_epoch_emission(ctx, epoch_emission, sector_emission_bps)
+    }
+
+    /// Deposit PKN into the program treasury (admin only)
+    pub fn deposit_treasury(ctx: Context<DepositTreasury>, amount: u64) -> Result<()> {
+        instructions::deposit_treasury(ctx, amount)
+    }
+
+    /// Withdraw PKN from the program treasury (admin only)
+    pub fn withdraw_treasury(ctx: Context<WithdrawTreasury>, amount: u64) -> Result<()> {
+        instructions::withdraw_treasury(ctx, amount)
+    }
+
+    /// Choose whether operator rewards are minted or paid from the treasury (admin only)
+    pub fn set_reward_source(
+        ctx: Context<SetRewardSource>,
+        reward_source: RewardSource,
+    ) -> Result<()> {
+        instructions::set_reward_source(ctx, reward_source)
+    }
+
     /// Update network parameters (admin only)
     pub fn update_network_params(
         ctx: Context<UpdateNetworkParams>,
@@ -175,8 +577,9 @@ pub mod depin_network {
         title: String,
         description: String,
         proposal_type: ProposalType,
+        action: ProposalAction,
     ) -> Result<()> {
-        instructions::create_proposal(ctx, title, description, proposal_type)
+        instructions::create_proposal(ctx, title, description, proposal_type, action)
     }
 
     /// Vote on governance proposal
@@ -187,8 +590,80 @@ pub mod depin_network {
         instructions::vote_proposal(ctx, vote)
     }
 
-    /// Execute approved governance proposal
+    /// Vote directly with stake delegated to someone else, overriding the delegate
+    pub fn override_delegate_vote(
+        ctx: Context<OverrideDelegateVote>,
+        vote: Vote,
+    ) -> Result<()> {
+        instructions::override_delegate_vote(ctx, vote)
+    }
+
+    /// Opt in to receiving delegated voting power
+    pub fn accept_delegations(ctx: Context<AcceptDelegations>) -> Result<()> {
+        instructions::accept_delegations(ctx)
+    }
+
+    /// Delegate voting power to another user
+    pub fn delegate_votes(ctx: Context<DelegateVotes>) -> Result<()> {
+        instructions::delegate_votes(ctx)
+    }
+
+    /// Take delegated voting power back
+    pub fn undelegate_votes(ctx: Context<UndelegateVotes>) -> Result<()> {
+        instructions::undelegate_votes(ctx)
+    }
+
+    /// Update the governance quorum and per-type approval thresholds (admin only)
+    pub fn update_voting_rules(
+        ctx: Context<UpdateVotingRules>,
+        quorum_bps: u16,
+        approval_threshold_bps: [u16; PROPOSAL_TYPE_COUNT],
+    ) -> Result<()> {
+        instructions::update_voting_rules(ctx, quorum_bps, approval_threshold_bps)
+    }
+
+    /// Tally a closed proposal and queue it behind the timelock if approved
+    pub fn finalize_proposal(ctx: Context<FinalizeProposal>) -> Result<()> {
+        instructions::finalize_proposal(ctx)
+    }
+
+    /// Execute a queued proposal once its timelock has elapsed
     pub fn execute_proposal(ctx: Context<ExecuteProposal>) -> Result<()> {
         instructions::execute_proposal(ctx)
     }
+
+    /// Withdraw a proposal before any votes are cast, refunding its deposit
+    pub fn cancel_proposal(ctx: Context<CancelProposal>) -> Result<()> {
+        instructions::cancel_proposal(ctx)
+    }
+
+    /// Close a finished proposal and its deposit escrow, returning the rent to the proposer
+    pub fn close_proposal(ctx: Context<CloseProposal>) -> Result<()> {
+        instructions::close_proposal(ctx)
+    }
+
+    /// Close a vote record once its proposal is finished
+    pub fn close_vote_record(ctx: Context<CloseVoteRecord>) -> Result<()> {
+        instructions::close_vote_record(ctx)
+    }
+
+    /// Set the deposit escrowed by new proposals (admin only)
+    pub fn update_proposal_deposit(ctx: Context<UpdateProposalDeposit>, proposal_deposit: u64) -> Result<()> {
+        instructions::update_proposal_deposit(ctx, proposal_deposit)
+    }
+
+    /// Cancel a queued proposal before its eta (security council only)
+    pub fn veto_proposal(ctx: Context<VetoProposal>) -> Result<()> {
+        instructions::veto_proposal(ctx)
+    }
+
+    /// Set the governance timelock and security council (admin only)
+    pub fn update_timelock_config(
+        ctx: Context<UpdateTimelockConfig>,
+        timelock_delay: i64,
+        execution_window: i64,
+        security_council: Pubkey,
+    ) -> Result<()> {
+        instructions::update_timelock_config(ctx, timelock_delay, execution_window, security_council)
+    }
 }
\ No newline at end of file
@@ -1,4 +1,5 @@
 use anchor_lang::prelude::*;
+use crate::constants::{PROPOSAL_TYPE_COUNT, SECTOR_COUNT};
 
 #[account]
 pub struct NetworkState {
@@ -19,17 +20,43 @@ pub struct NetworkState {
     pub last_reward_distribution: i64,
     pub governance_threshold: u64,   // Minimum tokens to create proposal
     pub proposals_count: u64,        // Total proposals created
+    pub treasury: Pubkey,            // Program-owned PKN treasury token account
+    pub reward_source: RewardSource, // Where operator rewards are paid from
+    pub current_epoch: u64,          // Epoch currently accruing contributions
+    pub epoch_emission: u64,         // PKN emitted per epoch across all sectors
+    pub sector_emission_bps: [u16; SECTOR_COUNT], // Share of each epoch's emission per sector
+    pub h3_resolution: u8,           // H3 resolution hotspots are indexed at
+    pub hex_density_target: u32,     // Hotspots per hex before rewards are diluted
+    pub quorum_bps: u16,             // Share of total stake that must vote for a proposal to count
+    pub approval_threshold_bps: [u16; PROPOSAL_TYPE_COUNT], // Yes share to exceed, per ProposalType
+    pub security_council: Pubkey,    // May veto queued proposals during the timelock
+    pub timelock_delay: i64,         // Seconds between approval and earliest execution
+    pub execution_window: i64,       // Seconds after the delay before a queued proposal expires
+    pub proposal_deposit: u64,       // PKN escrowed by each new proposal
+    pub next_asset_id: u64,          // ID assigned to the next registered asset
+    pub ev_chargers_count: u32,
+    pub power_reward_rate: u64,      // Tokens per kWh delivered
+    pub last_proposal_time: i64,     // Start time of the most recent proposal
     pub bump: u8,
+    pub treasury_bump: u8,
 }
 
 impl NetworkState {
-    pub const LEN: usize = 8 + 32 + 32 + 8 + 8 + 4 + 4 + 4 + 4 + 4 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 1;
+    pub const LEN: usize = 8 + 32 + 32 + 8 + 8 + 4 + 4 + 4 + 4 + 4 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 32 + 1 + 8 + 8 + (2 * SECTOR_COUNT) + 1 + 4 + 2 + (2 * PROPOSAL_TYPE_COUNT) + 32 + 8 + 8 + 8 + 8 + 4 + 8 + 8 + 1 + 1;
 }
 
 #[account]
 pub struct WiFiHotspot {
     pub owner: Pubkey,
-    pub location: String,
+    pub asset_id: u64,           // Network-wide ID used in the PDA seeds
+    pub nft_mint: Pubkey,        // One-of-one NFT whose holder operates the asset
+    pub device_pubkey: Pubkey,   // Ed25519 key of the hardware that signs telemetry
+    pub device_nonce: u64,       // Last accepted telemetry counter
+    pub location: String,        // Display label
+    pub latitude: i32,           // Micro-degrees
+    pub longitude: i32,          // Micro-degrees
+    pub h3_index: u64,           // H3 cell at the network's resolution
+    pub reward_multiplier_bps: u16, // Density multiplier of its hex, refreshed on every report
     pub coverage_radius: u32,
     pub bandwidth_mbps: u32,
     pub total_users_served: u32,
@@ -37,18 +64,25 @@ pub struct WiFiHotspot {
     pub total_rewards_earned: u64,
     pub last_data_submission: i64,
     pub is_active: bool,
-    pub reputation_score: u8,
+    pub reputation_score: u8,    // Also weights data rewards (percent)
+    pub successful_challenges: u32,
+    pub failed_challenges: u32,
+    pub open_challenges: u16,    // Issued and not yet resolved; the hotspot can't be closed until they are
     pub bump: u8,
 }
 
 impl WiFiHotspot {
     pub const MAX_LOCATION_LEN: usize = 100;
-    pub const LEN: usize = 8 + 32 + 4 + Self::MAX_LOCATION_LEN + 4 + 4 + 4 + 8 + 8 + 8 + 1 + 1 + 1;
+    pub const LEN: usize = 8 + 32 + 8 + 32 + 32 + 8 + 4 + Self::MAX_LOCATION_LEN + 4 + 4 + 8 + 2 + 4 + 4 + 4 + 8 + 8 + 8 + 1 + 1 + 4 + 4 + 2 + 1;
 }
 
 #[account]
 pub struct LogisticsPartner {
     pub owner: Pubkey,
+    pub asset_id: u64,           // Network-wide ID used in the PDA seeds
+    pub nft_mint: Pubkey,        // One-of-one NFT whose holder operates the asset
+    pub device_pubkey: Pubkey,   // Ed25519 key of the hardware that signs telemetry
+    pub device_nonce: u64,       // Last accepted telemetry counter
     pub partner_name: String,
     pub service_areas: Vec<String>,
     pub vehicle_count: u32,
@@ -65,14 +99,20 @@ impl LogisticsPartner {
     pub const MAX_NAME_LEN: usize = 50;
     pub const MAX_SERVICE_AREAS: usize = 10;
     pub const MAX_AREA_LEN: usize = 30;
-    pub const LEN: usize = 8 + 32 + 4 + Self::MAX_NAME_LEN + 4 + (Self::MAX_SERVICE_AREAS * (4 + Self::MAX_AREA_LEN)) + 4 + 4 + 4 + 8 + 8 + 1 + 1 + 1;
+    pub const LEN: usize = 8 + 32 + 8 + 32 + 32 + 8 + 4 + Self::MAX_NAME_LEN + 4 + (Self::MAX_SERVICE_AREAS * (4 + Self::MAX_AREA_LEN)) + 4 + 4 + 4 + 8 + 8 + 1 + 1 + 1;
 }
 
 #[account]
 pub struct Farm {
     pub owner: Pubkey,
+    pub asset_id: u64,           // Network-wide ID used in the PDA seeds
+    pub nft_mint: Pubkey,        // One-of-one NFT whose holder operates the asset
+    pub device_pubkey: Pubkey,   // Ed25519 key of the hardware that signs telemetry
+    pub device_nonce: u64,       // Last accepted telemetry counter
     pub farm_name: String,
-    pub location: String,
+    pub location: String,        // Display label
+    pub latitude: i32,           // Micro-degrees
+    pub longitude: i32,          // Micro-degrees
     pub farm_size_acres: u32,
     pub crop_types: Vec<String>,
     pub total_data_submissions: u32,
@@ -88,15 +128,21 @@ impl Farm {
     pub const MAX_LOCATION_LEN: usize = 100;
     pub const MAX_CROP_TYPES: usize = 5;
     pub const MAX_CROP_LEN: usize = 20;
-    pub const LEN: usize = 8 + 32 + 4 + Self::MAX_NAME_LEN + 4 + Self::MAX_LOCATION_LEN + 4 + 4 + (Self::MAX_CROP_TYPES * (4 + Self::MAX_CROP_LEN)) + 4 + 8 + 8 + 1 + 1 + 1;
+    pub const LEN: usize = 8 + 32 + 8 + 32 + 32 + 8 + 4 + Self::MAX_NAME_LEN + 4 + Self::MAX_LOCATION_LEN + 4 + 4 + 4 + 4 + (Self::MAX_CROP_TYPES * (4 + Self::MAX_CROP_LEN)) + 4 + 8 + 8 + 1 + 1 + 1;
 }
 
 #[account]
 pub struct HealthcareProvider {
     pub owner: Pubkey,
+    pub asset_id: u64,           // Network-wide ID used in the PDA seeds
+    pub nft_mint: Pubkey,        // One-of-one NFT whose holder operates the asset
+    pub device_pubkey: Pubkey,   // Ed25519 key of the hardware that signs telemetry
+    pub device_nonce: u64,       // Last accepted telemetry counter
     pub provider_name: String,
     pub provider_type: String,
-    pub location: String,
+    pub location: String,        // Display label
+    pub latitude: i32,           // Micro-degrees
+    pub longitude: i32,          // Micro-degrees
     pub license_number: String,
     pub total_data_submissions: u32,
     pub total_records_collected: u32,
@@ -112,15 +158,21 @@ impl HealthcareProvider {
     pub const MAX_TYPE_LEN: usize = 50;
     pub const MAX_LOCATION_LEN: usize = 100;
     pub const MAX_LICENSE_LEN: usize = 50;
-    pub const LEN: usize = 8 + 32 + 4 + Self::MAX_NAME_LEN + 4 + Self::MAX_TYPE_LEN + 4 + Self::MAX_LOCATION_LEN + 4 + Self::MAX_LICENSE_LEN + 4 + 4 + 8 + 8 + 1 + 1 + 1;
+    pub const LEN: usize = 8 + 32 + 8 + 32 + 32 + 8 + 4 + Self::MAX_NAME_LEN + 4 + Self::MAX_TYPE_LEN + 4 + Self::MAX_LOCATION_LEN + 4 + 4 + 4 + Self::MAX_LICENSE_LEN + 4 + 4 + 8 + 8 + 1 + 1 + 1;
 }
 
 #[account]
 pub struct TaxCollectionPoint {
     pub owner: Pubkey,
+    pub asset_id: u64,           // Network-wide ID used in the PDA seeds
+    pub nft_mint: Pubkey,        // One-of-one NFT whose holder operates the asset
+    pub device_pubkey: Pubkey,   // Ed25519 key of the hardware that signs telemetry
+    pub device_nonce: u64,       // Last accepted telemetry counter
     pub point_name: String,
     pub authority_type: String,
-    pub location: String,
+    pub location: String,        // Display label
+    pub latitude: i32,           // Micro-degrees
+    pub longitude: i32,          // Micro-degrees
     pub jurisdiction: String,
     pub total_records_submitted: u32,
     pub total_amount_processed: u64,
@@ -136,24 +188,121 @@ impl TaxCollectionPoint {
     pub const MAX_TYPE_LEN: usize = 50;
     pub const MAX_LOCATION_LEN: usize = 100;
     pub const MAX_JURISDICTION_LEN: usize = 50;
-    pub const LEN: usize = 8 + 32 + 4 + Self::MAX_NAME_LEN + 4 + Self::MAX_TYPE_LEN + 4 + Self::MAX_LOCATION_LEN + 4 + Self::MAX_JURISDICTION_LEN + 4 + 8 + 8 + 8 + 1 + 1 + 1;
+    pub const LEN: usize = 8 + 32 + 8 + 32 + 32 + 8 + 4 + Self::MAX_NAME_LEN + 4 + Self::MAX_TYPE_LEN + 4 + Self::MAX_LOCATION_LEN + 4 + 4 + 4 + Self::MAX_JURISDICTION_LEN + 4 + 8 + 8 + 8 + 1 + 1 + 1;
+}
+
+#[account]
+pub struct EVCharger {
+    pub owner: Pubkey,
+    pub asset_id: u64,           // Network-wide ID used in the PDA seeds
+    pub nft_mint: Pubkey,        // One-of-one NFT whose holder operates the asset
+    pub device_pubkey: Pubkey,   // Ed25519 key of the hardware that signs telemetry
+    pub device_nonce: u64,       // Last accepted telemetry counter
+    pub charger_name: String,
+    pub charger_type: ChargerType,
+    pub power_output_kw: u32,    // Rated output; caps the energy a session can report
+    pub latitude: i32,           // Micro-degrees
+    pub longitude: i32,          // Micro-degrees
+    pub plot: Pubkey,            // Charging plot the charger is installed on
+    pub active_session: Pubkey,  // Session in progress; default when the charger is free
+    pub last_session_end: i64,   // When the previous session ended or was cancelled
+    pub total_sessions: u64,     // Sessions started, also the next session's ID
+    pub total_energy_wh: u64,
+    pub total_rewards_earned: u64,
+    pub is_active: bool,
+    pub bump: u8,
+}
+
+impl EVCharger {
+    pub const MAX_NAME_LEN: usize = 50;
+    pub const LEN: usize = 8 + 32 + 8 + 32 + 32 + 8 + 4 + Self::MAX_NAME_LEN + 1 + 4 + 4 + 4 + 32 + 32 + 8 + 8 + 8 + 8 + 1 + 1;
+}
+
+#[account]
+pub struct ChargingSession {
+    pub charger: Pubkey,
+    pub driver: Pubkey,
+    pub session_id: u64,
+    pub start_time: i64,
+    pub end_time: i64,           // 0 while the session is in progress
+    pub energy_delivered_wh: u64, // Reported by the charger's device when the session ends
+    pub reward_amount: u64,      // PKN credited to the operator's epoch contribution
+    pub bump: u8,
+}
+
+impl ChargingSession {
+    pub const LEN: usize = 8 + 32 + 32 + 8 + 8 + 8 + 8 + 8 + 1;
+}
+
+/// A city's fixed grid of charging plots. Plot (row, col) covers the cell starting at
+/// `origin + (row, col) * cell_size`; no plots exist outside the grid.
+#[account]
+pub struct PlotCity {
+    pub name: String,            // Also the PDA seed
+    pub origin_latitude: i32,    // Micro-degrees, south-west corner of the grid
+    pub origin_longitude: i32,   // Micro-degrees
+    pub cell_size: u32,          // Micro-degrees per plot side
+    pub rows: u16,
+    pub cols: u16,
+    pub plot_price: u64,         // PKN paid to the treasury for an unclaimed plot
+    pub plots_sold: u32,
+    pub bump: u8,
+}
+
+impl PlotCity {
+    pub const MAX_NAME_LEN: usize = 32;
+    pub const LEN: usize = 8 + 4 + Self::MAX_NAME_LEN + 4 + 4 + 4 + 2 + 2 + 8 + 4 + 1;
+}
+
+#[account]
+pub struct ChargingPlot {
+    pub city: Pubkey,
+    pub row: u16,
+    pub col: u16,
+    pub owner: Pubkey,           // Plot owner, or the PlotListing PDA while listed
+    pub charger: Pubkey,         // Installed charger; default when the plot is empty
+    pub purchased_at: i64,
+    pub last_price: u64,         // PKN paid in the most recent sale
+    pub bump: u8,
+}
+
+impl ChargingPlot {
+    pub const LEN: usize = 8 + 32 + 2 + 2 + 32 + 32 + 8 + 8 + 1;
+}
+
+/// A plot for sale. The listing PDA holds the plot's ownership until it is bought or cancelled.
+#[account]
+pub struct PlotListing {
+    pub plot: Pubkey,
+    pub seller: Pubkey,
+    pub price: u64,
+    pub listed_at: i64,
+    pub bump: u8,
+}
+
+impl PlotListing {
+    pub const LEN: usize = 8 + 32 + 32 + 8 + 8 + 1;
 }
 
 #[account]
 pub struct StakingPool {
     pub pool_type: PoolType,
+    pub vault: Pubkey,           // Token account holding staked PKN
+    pub reward_vault: Pubkey,    // Token account funding staking rewards
     pub total_staked: u64,
     pub reward_rate: u64,        // APY in basis points (100 = 1%)
     pub min_stake_amount: u64,
     pub max_capacity: u64,
     pub lock_period: i64,        // Lock period in seconds
     pub total_rewards_paid: u64,
+    pub reward_per_token_stored: u128, // Accumulated rewards per staked token, scaled by REWARD_PRECISION
+    pub last_update_time: i64,   // Last time the accumulator was advanced
     pub is_active: bool,
     pub bump: u8,
 }
 
 impl StakingPool {
-    pub const LEN: usize = 8 + 1 + 8 + 8 + 8 + 8 + 8 + 8 + 1 + 1;
+    pub const LEN: usize = 8 + 1 + 32 + 32 + 8 + 8 + 8 + 8 + 8 + 8 + 16 + 8 + 1 + 1;
 }
 
 #[account]
@@ -164,11 +313,12 @@ pub struct UserStake {
     pub start_time: i64,
     pub last_reward_claim: i64,
     pub pending_rewards: u64,
+    pub reward_debt: u128,       // amount * reward_per_token_stored at the last settlement
     pub bump: u8,
 }
 
 impl UserStake {
-    pub const LEN: usize = 8 + 32 + 32 + 8 + 8 + 8 + 8 + 1;
+    pub const LEN: usize = 8 + 32 + 32 + 8 + 8 + 8 + 8 + 16 + 1;
 }
 
 #[account]
@@ -178,20 +328,108 @@ pub struct Proposal {
     pub title: String,
     pub description: String,
     pub proposal_type: ProposalType,
+    pub action: ProposalAction,      // Applied by execute_proposal if approved
     pub yes_votes: u64,
     pub no_votes: u64,
     pub total_votes: u32,
     pub start_time: i64,
     pub end_time: i64,
-    pub executed: bool,
-    pub approved: bool,
+    pub status: ProposalStatus,
     pub bump: u8,
+    pub abstain_votes: u64,
+    pub total_staked_snapshot: u64,  // Network stake when voting opened; the quorum base
+    pub quorum_bps: u16,             // Voting rules fixed at creation
+    pub approval_threshold_bps: u16,
+    pub eta: i64,                    // Earliest execution time once queued
+    pub expires_at: i64,             // Latest execution time once queued
+    pub deposit: u64,                // PKN escrowed at creation
 }
 
 impl Proposal {
     pub const MAX_TITLE_LEN: usize = 100;
     pub const MAX_DESCRIPTION_LEN: usize = 500;
-    pub const LEN: usize = 8 + 8 + 32 + 4 + Self::MAX_TITLE_LEN + 4 + Self::MAX_DESCRIPTION_LEN + 1 + 8 + 8 + 4 + 8 + 8 + 1 + 1 + 1;
+    pub const LEN: usize = 8 + 8 + 32 + 4 + Self::MAX_TITLE_LEN + 4 + Self::MAX_DESCRIPTION_LEN + 1 + ProposalAction::MAX_LEN + 8 + 8 + 4 + 8 + 8 + 1 + 1 + 8 + 8 + 2 + 2 + 8 + 8 + 8;
+
+    /// Whether the outcome can no longer change, so the proposal may be closed
+    pub fn is_final(&self, current_time: i64) -> bool {
+        match self.status {
+            ProposalStatus::Active => false,
+            ProposalStatus::Queued => current_time > self.expires_at,
+            _ => true,
+        }
+    }
+}
+
+#[account]
+pub struct RewardEpoch {
+    pub id: u64,
+    pub start_time: i64,
+    pub end_time: i64,           // Set when the epoch is finalized
+    pub emission_budget: u64,
+    pub sector_budgets: [u64; SECTOR_COUNT],
+    pub sector_contributions: [u64; SECTOR_COUNT],
+    pub finalized: bool,
+    pub bump: u8,
+}
+
+impl RewardEpoch {
+    pub const LEN: usize = 8 + 8 + 8 + 8 + 8 + (8 * SECTOR_COUNT) + (8 * SECTOR_COUNT) + 1 + 1;
+}
+
+#[account]
+pub struct EpochContribution {
+    pub owner: Pubkey,
+    pub epoch: u64,
+    pub contributions: [u64; SECTOR_COUNT],
+    pub bump: u8,
+}
+
+impl EpochContribution {
+    pub const LEN: usize = 8 + 32 + 8 + (8 * SECTOR_COUNT) + 1;
+}
+
+#[account]
+pub struct OperatorRewards {
+    pub owner: Pubkey,
+    pub claimable: u64,
+    pub total_claimed: u64,
+    pub last_settled_epoch: u64,
+    pub bump: u8,
+}
+
+impl OperatorRewards {
+    pub const LEN: usize = 8 + 32 + 8 + 8 + 8 + 1;
+}
+
+#[account]
+pub struct PocChallenge {
+    pub challenger: Pubkey,
+    pub hotspot: Pubkey,         // Challenged hotspot
+    pub epoch: u64,
+    pub issued_at: i64,
+    pub expires_at: i64,
+    pub witnesses: Vec<Pubkey>,  // Hotspots that submitted receipts
+    pub valid_witnesses: u8,     // Receipts from within the coverage radius
+    pub resolved: bool,
+    pub verified: bool,
+    pub bump: u8,
+}
+
+impl PocChallenge {
+    pub const MAX_WITNESSES: usize = 10;
+    pub const LEN: usize = 8 + 32 + 32 + 8 + 8 + 8 + 4 + (Self::MAX_WITNESSES * 32) + 1 + 1 + 1 + 1;
+}
+
+#[account]
+pub struct HexCell {
+    pub h3_index: u64,
+    pub resolution: u8,
+    pub active_hotspots: u32,
+    pub bump: u8,
+}
+
+impl HexCell {
+    pub const LEN: usize = 8 + 8 + 1 + 4 + 1;
 }
 
 #[account]
@@ -206,22 +444,69 @@ pub struct UserProfile {
     pub farms: u8,
     pub governance_votes: u32,
     pub bump: u8,
+    pub healthcare_providers: u8,
+    pub tax_points: u8,
+    pub vote_checkpoints: Vec<VoteCheckpoint>, // Voting power history, oldest first
+    pub delegate: Pubkey,            // Who votes this user's stake; default if not delegating
+    pub delegation_changed_at: i64,  // Last delegate or undelegate, for the re-delegation cooldown
+    pub ev_chargers: u8,
 }
 
 impl UserProfile {
-    pub const LEN: usize = 8 + 32 + 8 + 8 + 2 + 8 + 1 + 1 + 1 + 4 + 1;
+    pub const MAX_VOTE_CHECKPOINTS: usize = 32;
+    pub const LEN: usize = 8 + 32 + 8 + 8 + 2 + 8 + 1 + 1 + 1 + 4 + 1 + 1 + 1 + 4 + (Self::MAX_VOTE_CHECKPOINTS * VoteCheckpoint::LEN) + 32 + 8 + 1;
+}
+
+/// Voting power held from `timestamp` until the next checkpoint
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct VoteCheckpoint {
+    pub timestamp: i64,
+    pub voting_power: u64,           // Own undelegated stake; stake delegated to the user in a DelegateRecord
+    pub delegated_stake: u64,        // Own stake voted by a delegate
+}
+
+impl VoteCheckpoint {
+    pub const LEN: usize = 8 + 8 + 8;
+}
+
+#[account]
+pub struct Delegation {
+    pub delegator: Pubkey,
+    pub delegate: Pubkey,
+    pub delegated_at: i64,
+    pub bump: u8,
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+impl Delegation {
+    pub const LEN: usize = 8 + 32 + 32 + 8 + 1;
+}
+
+/// Stake delegated to a user. The delegate creates it to accept delegations, and
+/// delegators update it, so their changes never touch the delegate's own profile.
+#[account]
+pub struct DelegateRecord {
+    pub delegate: Pubkey,
+    pub delegated_power: u64,        // Stake currently delegated to this user
+    pub checkpoints: Vec<VoteCheckpoint>, // History of `delegated_power`, oldest first
+    pub bump: u8,
+}
+
+impl DelegateRecord {
+    pub const MAX_CHECKPOINTS: usize = 32;
+    pub const LEN: usize = 8 + 32 + 8 + 4 + (Self::MAX_CHECKPOINTS * VoteCheckpoint::LEN) + 1;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
 pub enum PoolType {
     WiFiInfrastructure,
     LogisticsOptimization,
     AgricultureData,
     Governance,
     LiquidityMining,
+    PowerInfrastructure,
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
 pub enum ProposalType {
     ParameterChange,
     TreasurySpend,
@@ -230,7 +515,109 @@ pub enum ProposalType {
     NetworkExpansion,
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ProposalStatus {
+    Active,    // Voting open, or closed and awaiting finalize_proposal
+    Defeated,  // Missed quorum or the approval threshold
+    Queued,    // Approved; executable between eta and expires_at
+    Executed,
+    Vetoed,    // Cancelled by the security council while queued
+    Cancelled, // Withdrawn by the proposer before any votes
+}
+
+/// On-chain effect of an approved proposal
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub enum ProposalAction {
+    /// No on-chain effect; the outcome is acted on off-chain
+    Signal,
+    UpdateRewardRates {
+        wifi_reward_rate: u64,
+        logistics_reward_rate: u64,
+        agriculture_reward_rate: u64,
+        healthcare_reward_rate: u64,
+        taxation_reward_rate: u64,
+        power_reward_rate: u64,
+    },
+    UpdateGovernanceThreshold {
+        governance_threshold: u64,
+    },
+    UpdatePoolRewardRate {
+        pool_type: PoolType,
+        reward_rate: u64,
+    },
+    /// Send `amount` PKN from the treasury to the `recipient` token account
+    TreasuryTransfer {
+        recipient: Pubkey,
+        amount: u64,
+    },
+    TransferAuthority {
+        new_authority: Pubkey,
+    },
+    UpdateVotingRules {
+        quorum_bps: u16,
+        approval_threshold_bps: [u16; PROPOSAL_TYPE_COUNT],
+    },
+}
+
+impl ProposalAction {
+    pub const MAX_LEN: usize = 1 + (8 * 6); // Largest variant: UpdateRewardRates
+
+    /// Whether applying this action changes the parameters reported by `ParamsUpdated`
+    pub fn updates_network_params(&self) -> bool {
+        matches!(
+            self,
+            ProposalAction::UpdateRewardRates { .. }
+                | ProposalAction::UpdateGovernanceThreshold { .. }
+                | ProposalAction::TransferAuthority { .. }
+                | ProposalAction::UpdateVotingRules { .. }
+        )
+    }
+
+    /// Whether this action may be carried by a proposal of the given type
+    pub fn is_allowed_for(&self, proposal_type: ProposalType) -> bool {
+        match self {
+            ProposalAction::Signal => matches!(
+                proposal_type,
+                ProposalType::ProtocolUpgrade | ProposalType::NetworkExpansion
+            ),
+            ProposalAction::UpdateRewardRates { .. } => proposal_type == ProposalType::RewardRateChange,
+            ProposalAction::UpdateGovernanceThreshold { .. } => proposal_type == ProposalType::ParameterChange,
+            ProposalAction::UpdatePoolRewardRate { .. } => matches!(
+                proposal_type,
+                ProposalType::ParameterChange | ProposalType::RewardRateChange
+            ),
+            ProposalAction::TreasuryTransfer { .. } => proposal_type == ProposalType::TreasurySpend,
+            ProposalAction::TransferAuthority { .. } => proposal_type == ProposalType::ProtocolUpgrade,
+            ProposalAction::UpdateVotingRules { .. } => proposal_type == ProposalType::ProtocolUpgrade,
+        }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Sector {
+    WiFi,
+    Logistics,
+    Agriculture,
+    Healthcare,
+    Taxation,
+    Power,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ChargerType {
+    Level1,
+    Level2,
+    DcFast,
+    TeslaSupercharger,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RewardSource {
+    Mint,     // Mint new PKN up to the total supply cap
+    Treasury, // Transfer PKN out of the pre-funded treasury
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
 pub enum Vote {
     Yes,
     No,
@@ -1,10 +1,379 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::ed25519_program;
+use anchor_lang::solana_program::sysvar::instructions::{load_current_index_checked, load_instruction_at_checked};
+use anchor_spl::metadata::mpl_token_metadata::types::DataV2;
+use anchor_spl::metadata::{
+    burn_nft, create_master_edition_v3, create_metadata_accounts_v3, BurnNft, CreateMasterEditionV3,
+    CreateMetadataAccountsV3, Metadata,
+};
+use anchor_spl::token::{self, Mint, MintTo, Token, TokenAccount, Transfer};
+use h3o::{LatLng, Resolution};
+use crate::constants::*;
+use crate::errors::*;
+use crate::state::*;
 
 /// Utility function to validate string lengths
 pub fn validate_string_length(s: &str, max_len: usize, field_name: &str) -> Result<()> {
-    require!(s.len() <= max_len, 
-        anchor_lang::error::Error::from(anchor_lang::error::ErrorCode::ConstraintRaw)
-            .with_account_name(field_name)
+    if s.len() > max_len {
+        return Err(anchor_lang::error::Error::from(anchor_lang::error::ErrorCode::ConstraintRaw)
+            .with_account_name(field_name));
+    }
+    Ok(())
+}
+
+/// Checked integer arithmetic that fails with `DePINError::ArithmeticOverflow`
+/// instead of wrapping or panicking
+pub trait SafeMath: Sized {
+    fn safe_add(self, rhs: Self) -> Result<Self>;
+    fn safe_sub(self, rhs: Self) -> Result<Self>;
+    fn safe_mul(self, rhs: Self) -> Result<Self>;
+    fn safe_div(self, rhs: Self) -> Result<Self>;
+}
+
+macro_rules! impl_safe_math {
+    ($($t:ty),*) => {$(
+        impl SafeMath for $t {
+            fn safe_add(self, rhs: Self) -> Result<Self> {
+                self.checked_add(rhs).ok_or_else(|| DePINError::ArithmeticOverflow.into())
+            }
+
+            fn safe_sub(self, rhs: Self) -> Result<Self> {
+                self.checked_sub(rhs).ok_or_else(|| DePINError::ArithmeticOverflow.into())
+            }
+
+            fn safe_mul(self, rhs: Self) -> Result<Self> {
+                self.checked_mul(rhs).ok_or_else(|| DePINError::ArithmeticOverflow.into())
+            }
+
+            fn safe_div(self, rhs: Self) -> Result<Self> {
+                self.checked_div(rhs).ok_or_else(|| DePINError::ArithmeticOverflow.into())
+            }
+        }
+    )*};
+}
+
+impl_safe_math!(u8, u16, u32, u64, u128, i64);
+
+/// Narrow a u128 intermediate back to a token amount
+pub fn to_u64(value: u128) -> Result<u64> {
+    u64::try_from(value).map_err(|_| DePINError::ArithmeticOverflow.into())
+}
+
+/// `value * numerator / denominator`, computed with a u128 intermediate
+pub fn mul_div(value: u64, numerator: u64, denominator: u64) -> Result<u64> {
+    to_u64((value as u128).safe_mul(numerator as u128)?.safe_div(denominator as u128)?)
+}
+
+/// Per-unit reward `quantity * rate`, scaled by `multiplier / divisor`, computed in u128
+pub fn calculate_reward(quantity: u64, rate: u64, multiplier: u64, divisor: u64) -> Result<u64> {
+    let base_reward = (quantity as u128).safe_mul(rate as u128)?;
+    to_u64(base_reward.safe_mul(multiplier as u128)?.safe_div(divisor as u128)?)
+}
+
+/// Mint PKN rewards to an operator, signing as the `network_state` mint authority.
+/// Fails if the mint would exceed `NetworkState.total_supply`.
+pub fn mint_reward<'info>(
+    mint: &Account<'info, Mint>,
+    to: &Account<'info, TokenAccount>,
+    network_state: &Account<'info, NetworkState>,
+    token_program: &Program<'info, Token>,
+    amount: u64,
+) -> Result<()> {
+    if amount == 0 {
+        return Ok(());
+    }
+
+    let new_supply = mint.supply.safe_add(amount)?;
+    require!(new_supply <= network_state.total_supply, DePINError::SupplyCapExceeded);
+
+    let signer_seeds: &[&[&[u8]]] = &[&[NETWORK_STATE_SEED, &[network_state.bump]]];
+    let cpi_accounts = MintTo {
+        mint: mint.to_account_info(),
+        to: to.to_account_info(),
+        authority: network_state.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(token_program.to_account_info(), cpi_accounts, signer_seeds);
+    token::mint_to(cpi_ctx, amount)
+}
+
+/// Transfer PKN out of the program treasury, signing as the `network_state` PDA.
+/// Fails if the treasury cannot cover the amount.
+pub fn transfer_from_treasury<'info>(
+    treasury: &Account<'info, TokenAccount>,
+    to: &Account<'info, TokenAccount>,
+    network_state: &Account<'info, NetworkState>,
+    token_program: &Program<'info, Token>,
+    amount: u64,
+) -> Result<()> {
+    if amount == 0 {
+        return Ok(());
+    }
+
+    require!(treasury.amount >= amount, DePINError::InsufficientTreasuryFunds);
+
+    let signer_seeds: &[&[&[u8]]] = &[&[NETWORK_STATE_SEED, &[network_state.bump]]];
+    let cpi_accounts = Transfer {
+        from: treasury.to_account_info(),
+        to: to.to_account_info(),
+        authority: network_state.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(token_program.to_account_info(), cpi_accounts, signer_seeds);
+    token::transfer(cpi_ctx, amount)
+}
+
+/// Move PKN out of a proposal's deposit escrow, signed by the proposal PDA
+pub fn transfer_from_proposal_escrow<'info>(
+    proposal: &Account<'info, Proposal>,
+    deposit_vault: &Account<'info, TokenAccount>,
+    to: &Account<'info, TokenAccount>,
+    token_program: &Program<'info, Token>,
+    amount: u64,
+) -> Result<()> {
+    if amount == 0 {
+        return Ok(());
+    }
+
+    let id_bytes = proposal.id.to_le_bytes();
+    let signer_seeds: &[&[&[u8]]] = &[&[PROPOSAL_SEED, &id_bytes, &[proposal.bump]]];
+    let cpi_accounts = Transfer {
+        from: deposit_vault.to_account_info(),
+        to: to.to_account_info(),
+        authority: proposal.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(token_program.to_account_info(), cpi_accounts, signer_seeds);
+    token::transfer(cpi_ctx, amount)
+}
+
+/// Pay an operator reward from the source configured on `NetworkState`.
+pub fn pay_reward<'info>(
+    mint: &Account<'info, Mint>,
+    treasury: &Account<'info, TokenAccount>,
+    to: &Account<'info, TokenAccount>,
+    network_state: &Account<'info, NetworkState>,
+    token_program: &Program<'info, Token>,
+    amount: u64,
+) -> Result<()> {
+    match network_state.reward_source {
+        RewardSource::Mint => mint_reward(mint, to, network_state, token_program, amount),
+        RewardSource::Treasury => transfer_from_treasury(treasury, to, network_state, token_program, amount),
+    }
+}
+
+/// Accounts a registration passes to `mint_asset_nft`
+pub struct MintAssetNft<'a, 'info> {
+    pub network_state: &'a Account<'info, NetworkState>,
+    pub nft_mint: &'a Account<'info, Mint>,
+    pub owner_nft: &'a Account<'info, TokenAccount>,
+    pub nft_metadata: &'a UncheckedAccount<'info>,
+    pub nft_master_edition: &'a UncheckedAccount<'info>,
+    pub payer: &'a Signer<'info>,
+    pub token_program: &'a Program<'info, Token>,
+    pub token_metadata_program: &'a Program<'info, Metadata>,
+    pub system_program: &'a Program<'info, System>,
+    pub rent: &'a Sysvar<'info, Rent>,
+}
+
+/// Mint an asset's one-of-one NFT to its owner, signing as the `network_state` PDA.
+/// The master edition takes over the mint authority, so the supply stays at one.
+pub fn mint_asset_nft(accounts: MintAssetNft<'_, '_>, name: String, uri: String) -> Result<()> {
+    let MintAssetNft {
+        network_state,
+        nft_mint,
+        owner_nft,
+        nft_metadata,
+        nft_master_edition,
+        payer,
+        token_program,
+        token_metadata_program,
+        system_program,
+        rent,
+    } = accounts;
+    let signer_seeds: &[&[&[u8]]] = &[&[NETWORK_STATE_SEED, &[network_state.bump]]];
+
+    let cpi_accounts = MintTo {
+        mint: nft_mint.to_account_info(),
+        to: owner_nft.to_account_info(),
+        authority: network_state.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(token_program.to_account_info(), cpi_accounts, signer_seeds);
+    token::mint_to(cpi_ctx, 1)?;
+
+    let cpi_accounts = CreateMetadataAccountsV3 {
+        metadata: nft_metadata.to_account_info(),
+        mint: nft_mint.to_account_info(),
+        mint_authority: network_state.to_account_info(),
+        payer: payer.to_account_info(),
+        update_authority: network_state.to_account_info(),
+        system_program: system_program.to_account_info(),
+        rent: rent.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(token_metadata_program.to_account_info(), cpi_accounts, signer_seeds);
+    let data = DataV2 {
+        name,
+        symbol: ASSET_NFT_SYMBOL.to_string(),
+        uri,
+        seller_fee_basis_points: 0,
+        creators: None,
+        collection: None,
+        uses: None,
+    };
+    create_metadata_accounts_v3(cpi_ctx, data, true, true, None)?;
+
+    let cpi_accounts = CreateMasterEditionV3 {
+        edition: nft_master_edition.to_account_info(),
+        mint: nft_mint.to_account_info(),
+        update_authority: network_state.to_account_info(),
+        mint_authority: network_state.to_account_info(),
+        payer: payer.to_account_info(),
+        metadata: nft_metadata.to_account_info(),
+        token_program: token_program.to_account_info(),
+        system_program: system_program.to_account_info(),
+        rent: rent.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(token_metadata_program.to_account_info(), cpi_accounts, signer_seeds);
+    create_master_edition_v3(cpi_ctx, Some(0))
+}
+
+/// Burn an asset's NFT, returning the rent of its token, metadata and edition accounts to the holder
+pub fn burn_asset_nft<'info>(
+    owner: &Signer<'info>,
+    nft_mint: &Account<'info, Mint>,
+    owner_nft: &Account<'info, TokenAccount>,
+    nft_metadata: &UncheckedAccount<'info>,
+    nft_master_edition: &UncheckedAccount<'info>,
+    token_program: &Program<'info, Token>,
+    token_metadata_program: &Program<'info, Metadata>,
+) -> Result<()> {
+    let cpi_accounts = BurnNft {
+        metadata: nft_metadata.to_account_info(),
+        owner: owner.to_account_info(),
+        mint: nft_mint.to_account_info(),
+        token: owner_nft.to_account_info(),
+        edition: nft_master_edition.to_account_info(),
+        spl_token: token_program.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(token_metadata_program.to_account_info(), cpi_accounts);
+    burn_nft(cpi_ctx, None)
+}
+
+/// Set up a profile the first time its owner operates or receives an asset, and
+/// record their activity
+pub fn init_user_profile_if_needed(user_profile: &mut UserProfile, owner: Pubkey, bump: u8) -> Result<()> {
+    if user_profile.owner == Pubkey::default() {
+        user_profile.owner = owner;
+        user_profile.total_earned = 0;
+        user_profile.total_staked = 0;
+        user_profile.reputation_score = 100;
+        user_profile.wifi_hotspots = 0;
+        user_profile.logistics_partners = 0;
+        user_profile.farms = 0;
+        user_profile.governance_votes = 0;
+        user_profile.healthcare_providers = 0;
+        user_profile.tax_points = 0;
+        user_profile.vote_checkpoints = Vec::new();
+        user_profile.delegate = Pubkey::default();
+        user_profile.delegation_changed_at = 0;
+        user_profile.ev_chargers = 0;
+        user_profile.bump = bump;
+    }
+    user_profile.last_activity = Clock::get()?.unix_timestamp;
+    Ok(())
+}
+
+/// Accrue an operator's contribution for a sector to the current reward epoch.
+/// The contribution account is initialized on the operator's first submission of the epoch.
+pub fn record_epoch_contribution(
+    reward_epoch: &mut RewardEpoch,
+    epoch_contribution: &mut EpochContribution,
+    owner: Pubkey,
+    bump: u8,
+    sector: Sector,
+    amount: u64,
+) -> Result<()> {
+    require!(!reward_epoch.finalized, DePINError::EpochAlreadyFinalized);
+
+    if epoch_contribution.owner == Pubkey::default() {
+        epoch_contribution.owner = owner;
+        epoch_contribution.epoch = reward_epoch.id;
+        epoch_contribution.contributions = [0; SECTOR_COUNT];
+        epoch_contribution.bump = bump;
+    }
+
+    let index = sector as usize;
+    epoch_contribution.contributions[index] = epoch_contribution.contributions[index].safe_add(amount)?;
+    reward_epoch.sector_contributions[index] = reward_epoch.sector_contributions[index].safe_add(amount)?;
+
+    Ok(())
+}
+
+/// Build the message a device signs for a telemetry report:
+/// domain tag, asset address, counter, timestamp and the Borsh-encoded metrics
+pub fn telemetry_message(asset: &Pubkey, nonce: u64, timestamp: i64, metrics: &[u8]) -> Vec<u8> {
+    let mut message = Vec::with_capacity(TELEMETRY_DOMAIN.len() + 32 + 8 + 8 + metrics.len());
+    message.extend_from_slice(TELEMETRY_DOMAIN);
+    message.extend_from_slice(asset.as_ref());
+    message.extend_from_slice(&nonce.to_le_bytes());
+    message.extend_from_slice(&timestamp.to_le_bytes());
+    message.extend_from_slice(metrics);
+    message
+}
+
+/// Check that the instruction immediately before this one is an ed25519 precompile
+/// verification of `message` signed by `device_pubkey`
+pub fn verify_device_signature(
+    instructions_sysvar: &AccountInfo,
+    device_pubkey: &Pubkey,
+    message: &[u8],
+) -> Result<()> {
+    let current_index = load_current_index_checked(instructions_sysvar)?;
+    require!(current_index > 0, DePINError::MissingDeviceSignature);
+
+    let ed25519_ix = load_instruction_at_checked((current_index - 1) as usize, instructions_sysvar)?;
+    require!(ed25519_ix.program_id == ed25519_program::ID, DePINError::MissingDeviceSignature);
+    require!(ed25519_ix.accounts.is_empty(), DePINError::MissingDeviceSignature);
+
+    // Layout: [num_signatures: u8, padding: u8, offsets: 7 x u16, ...data]
+    let data = &ed25519_ix.data;
+    require!(data.len() >= 16 && data[0] == 1, DePINError::MissingDeviceSignature);
+
+    let read_u16 = |at: usize| u16::from_le_bytes([data[at], data[at + 1]]);
+    let signature_ix_index = read_u16(4);
+    let public_key_offset = read_u16(6) as usize;
+    let public_key_ix_index = read_u16(8);
+    let message_offset = read_u16(10) as usize;
+    let message_size = read_u16(12) as usize;
+    let message_ix_index = read_u16(14);
+
+    // Signature, key and message must all live in the precompile instruction itself
+    require!(
+        signature_ix_index == u16::MAX && public_key_ix_index == u16::MAX && message_ix_index == u16::MAX,
+        DePINError::MissingDeviceSignature
+    );
+
+    let signed_key = data
+        .get(public_key_offset..public_key_offset + 32)
+        .ok_or(DePINError::MissingDeviceSignature)?;
+    let signed_message = data
+        .get(message_offset..message_offset + message_size)
+        .ok_or(DePINError::MissingDeviceSignature)?;
+
+    require!(signed_key == device_pubkey.as_ref(), DePINError::InvalidDeviceSignature);
+    require!(signed_message == message, DePINError::InvalidDeviceSignature);
+
+    Ok(())
+}
+
+/// Check a telemetry report's counter is monotonic and its timestamp is fresh
+pub fn validate_telemetry_freshness(
+    nonce: u64,
+    last_nonce: u64,
+    timestamp: i64,
+    current_time: i64,
+) -> Result<()> {
+    require!(nonce > last_nonce, DePINError::InvalidDeviceNonce);
+    require!(
+        current_time.safe_sub(timestamp)?.unsigned_abs() <= TELEMETRY_MAX_AGE as u64,
+        DePINError::StaleTelemetry
     );
     Ok(())
 }
@@ -24,15 +393,237 @@ pub fn calculate_performance_multiplier(
     }
 }
 
-/// Convert APY basis points to per-second rate
-pub fn apy_to_per_second_rate(apy_basis_points: u64) -> u64 {
-    // APY in basis points (100 = 1%) to per-second rate
-    apy_basis_points / (365 * 24 * 3600 * 100)
+/// Reward-per-token accrued over `elapsed_seconds` at an APY in basis points,
+/// scaled by `REWARD_PRECISION`
+pub fn apy_to_reward_per_token(apy_basis_points: u64, elapsed_seconds: i64) -> Result<u128> {
+    if elapsed_seconds <= 0 {
+        return Ok(0);
+    }
+    let accrued = (elapsed_seconds as u128)
+        .safe_mul(apy_basis_points as u128)?
+        .safe_mul(REWARD_PRECISION)?;
+    accrued.safe_div(BASIS_POINTS as u128 * SECONDS_PER_YEAR as u128)
+}
+
+/// Advance a staking pool's reward-per-token accumulator to `current_time`
+pub fn update_pool_rewards(staking_pool: &mut StakingPool, current_time: i64) -> Result<()> {
+    let elapsed = current_time.safe_sub(staking_pool.last_update_time)?;
+    if elapsed <= 0 {
+        return Ok(());
+    }
+    let accrued = apy_to_reward_per_token(staking_pool.reward_rate, elapsed)?;
+    staking_pool.reward_per_token_stored = staking_pool.reward_per_token_stored.safe_add(accrued)?;
+    staking_pool.last_update_time = current_time;
+    Ok(())
+}
+
+/// Rewards a stake has accrued at the pool's current reward-per-token, before its reward debt
+fn accumulated_rewards(amount: u64, reward_per_token: u128) -> Result<u128> {
+    (amount as u128).safe_mul(reward_per_token)?.safe_div(REWARD_PRECISION)
+}
+
+/// Move a stake's accrued rewards into `pending_rewards`. The pool must already be
+/// updated, and the caller must `reset_reward_debt` after changing the staked amount.
+pub fn settle_stake_rewards(user_stake: &mut UserStake, staking_pool: &StakingPool) -> Result<()> {
+    let accrued = accumulated_rewards(user_stake.amount, staking_pool.reward_per_token_stored)?;
+    let earned = accrued.saturating_sub(user_stake.reward_debt);
+    user_stake.pending_rewards = user_stake.pending_rewards.safe_add(to_u64(earned)?)?;
+    user_stake.reward_debt = accrued;
+    Ok(())
+}
+
+/// Re-base a stake's reward debt on its current amount
+pub fn reset_reward_debt(user_stake: &mut UserStake, staking_pool: &StakingPool) -> Result<()> {
+    user_stake.reward_debt = accumulated_rewards(user_stake.amount, staking_pool.reward_per_token_stored)?;
+    Ok(())
+}
+
+/// Append a checkpoint to a voting power history.
+///
+/// A proposal reads the last checkpoint before its start, so the latest checkpoint is
+/// overwritten while no proposal has opened since it was written. When the history is
+/// full, the oldest checkpoint is dropped once the next one predates every open proposal.
+/// Otherwise the change is merged into the latest checkpoint, keeping the lower of the
+/// two balances, so a full history never blocks staking and never overstates a vote.
+fn push_checkpoint(
+    checkpoints: &mut Vec<VoteCheckpoint>,
+    max_checkpoints: usize,
+    checkpoint: VoteCheckpoint,
+    last_proposal_time: i64,
+) -> Result<()> {
+    if let Some(last) = checkpoints.last_mut() {
+        if last.timestamp >= last_proposal_time {
+            *last = checkpoint;
+            return Ok(());
+        }
+    }
+    if checkpoints.len() >= max_checkpoints {
+        // Open proposals started within the last voting period
+        let oldest_open_start = checkpoint.timestamp.safe_sub(VOTING_PERIOD)?;
+        if checkpoints[1].timestamp >= oldest_open_start {
+            if let Some(last) = checkpoints.last_mut() {
+                last.voting_power = last.voting_power.min(checkpoint.voting_power);
+                last.delegated_stake = last.delegated_stake.min(checkpoint.delegated_stake);
+            }
+            return Ok(());
+        }
+        checkpoints.remove(0);
+    }
+    checkpoints.push(checkpoint);
+    Ok(())
+}
+
+/// Record a user's own voting power from `timestamp`: their stake unless it is delegated.
+/// Stake delegated to the user is tracked separately in their `DelegateRecord`.
+pub fn write_vote_checkpoint(user_profile: &mut UserProfile, timestamp: i64, last_proposal_time: i64) -> Result<()> {
+    let delegated_stake = if user_profile.delegate == Pubkey::default() {
+        0
+    } else {
+        user_profile.total_staked
+    };
+    let voting_power = user_profile.total_staked.safe_sub(delegated_stake)?;
+
+    push_checkpoint(
+        &mut user_profile.vote_checkpoints,
+        UserProfile::MAX_VOTE_CHECKPOINTS,
+        VoteCheckpoint { timestamp, voting_power, delegated_stake },
+        last_proposal_time,
+    )
+}
+
+/// Record the stake delegated to a user from `timestamp`
+pub fn write_delegate_checkpoint(delegate_record: &mut DelegateRecord, timestamp: i64, last_proposal_time: i64) -> Result<()> {
+    let voting_power = delegate_record.delegated_power;
+    push_checkpoint(
+        &mut delegate_record.checkpoints,
+        DelegateRecord::MAX_CHECKPOINTS,
+        VoteCheckpoint { timestamp, voting_power, delegated_stake: 0 },
+        last_proposal_time,
+    )
+}
+
+/// Own voting power a user held strictly before `timestamp`. Zero if no checkpoint that old
+/// is retained, so stake moved in at or after a proposal's start never counts towards it.
+pub fn voting_power_at(user_profile: &UserProfile, timestamp: i64) -> u64 {
+    user_profile
+        .vote_checkpoints
+        .iter()
+        .rev()
+        .find(|checkpoint| checkpoint.timestamp < timestamp)
+        .map_or(0, |checkpoint| checkpoint.voting_power)
+}
+
+/// Own stake a user had delegated strictly before `timestamp`
+pub fn delegated_stake_at(user_profile: &UserProfile, timestamp: i64) -> u64 {
+    user_profile
+        .vote_checkpoints
+        .iter()
+        .rev()
+        .find(|checkpoint| checkpoint.timestamp < timestamp)
+        .map_or(0, |checkpoint| checkpoint.delegated_stake)
+}
+
+/// Stake delegated to a user strictly before `timestamp`
+pub fn delegated_power_at(delegate_record: &DelegateRecord, timestamp: i64) -> u64 {
+    delegate_record
+        .checkpoints
+        .iter()
+        .rev()
+        .find(|checkpoint| checkpoint.timestamp < timestamp)
+        .map_or(0, |checkpoint| checkpoint.voting_power)
+}
+
+/// Check a quorum and per-type approval thresholds against the limits governance allows
+pub fn validate_voting_rules(quorum_bps: u16, approval_threshold_bps: &[u16; PROPOSAL_TYPE_COUNT]) -> bool {
+    quorum_bps as u64 <= BASIS_POINTS
+        && approval_threshold_bps
+            .iter()
+            .all(|bps| *bps >= MIN_APPROVAL_THRESHOLD_BPS && (*bps as u64) < BASIS_POINTS)
+}
+
+/// Tally a closed proposal, returning `(quorum_reached, approved)`. All votes, including
+/// abstains, count towards quorum; only yes and no votes count towards approval.
+pub fn tally_proposal(proposal: &Proposal) -> Result<(bool, bool)> {
+    let decisive_votes = proposal.yes_votes.safe_add(proposal.no_votes)?;
+    let participating_votes = decisive_votes.safe_add(proposal.abstain_votes)?;
+
+    let quorum_reached = (participating_votes as u128).safe_mul(BASIS_POINTS as u128)?
+        >= (proposal.total_staked_snapshot as u128).safe_mul(proposal.quorum_bps as u128)?;
+    let approval_reached = decisive_votes > 0
+        && (proposal.yes_votes as u128).safe_mul(BASIS_POINTS as u128)?
+            > (decisive_votes as u128).safe_mul(proposal.approval_threshold_bps as u128)?;
+
+    Ok((quorum_reached, quorum_reached && approval_reached))
+}
+
+/// Convert fixed-point micro-degrees to degrees
+pub fn micro_degrees_to_degrees(micro_degrees: i32) -> f64 {
+    micro_degrees as f64 / 1_000_000.0
+}
+
+/// Distance in meters between two hotspots' fixed-point coordinates
+pub fn hotspot_distance_meters(a: &WiFiHotspot, b: &WiFiHotspot) -> f64 {
+    calculate_distance_km(
+        micro_degrees_to_degrees(a.latitude),
+        micro_degrees_to_degrees(a.longitude),
+        micro_degrees_to_degrees(b.latitude),
+        micro_degrees_to_degrees(b.longitude),
+    ) * 1000.0
+}
+
+/// Outcome of a coverage challenge as (verified, penalized, bond kept by the treasury).
+/// A hotspot that fails is only penalised once enough receipts came in to show it was
+/// witnessable. The treasury takes the whole bond if the hotspot proved its coverage and
+/// the challenge fee otherwise, so issuing challenges is never free.
+pub fn poc_outcome(valid_witnesses: u8, receipts: usize, bond: u64) -> (bool, bool, u64) {
+    let verified = valid_witnesses >= MIN_POC_WITNESSES;
+    let penalized = !verified && receipts >= MIN_POC_RECEIPTS as usize;
+    let to_treasury = if verified { bond } else { bond.min(POC_CHALLENGE_FEE) };
+
+    (verified, penalized, to_treasury)
+}
+
+/// Validate fixed-point geographic coordinates given in micro-degrees
+pub fn validate_micro_degree_coordinates(latitude: i32, longitude: i32) -> bool {
+    (-90_000_000..=90_000_000).contains(&latitude) && (-180_000_000..=180_000_000).contains(&longitude)
+}
+
+/// Whether a point in micro-degrees lies within plot (row, col) of a city grid
+pub fn plot_contains(plot_city: &PlotCity, row: u16, col: u16, latitude: i32, longitude: i32) -> bool {
+    let cell = plot_city.cell_size as i64;
+    let south = plot_city.origin_latitude as i64 + row as i64 * cell;
+    let west = plot_city.origin_longitude as i64 + col as i64 * cell;
+    let (latitude, longitude) = (latitude as i64, longitude as i64);
+
+    latitude >= south && latitude < south + cell && longitude >= west && longitude < west + cell
+}
+
+/// Whether `h3_index` is the H3 cell containing a point in micro-degrees, at the
+/// given resolution. The cell is recomputed on-chain, so a hotspot cannot claim
+/// a hex it is not in.
+pub fn validate_h3_cell(h3_index: u64, latitude: i32, longitude: i32, resolution: u8) -> bool {
+    let Ok(resolution) = Resolution::try_from(resolution) else {
+        return false;
+    };
+    let Ok(point) = LatLng::new(micro_degrees_to_degrees(latitude), micro_degrees_to_degrees(longitude)) else {
+        return false;
+    };
+
+    u64::from(point.to_cell(resolution)) == h3_index
+}
+
+/// Reward multiplier for each hotspot in a hex holding `hotspots_in_cell` active hotspots
+pub fn hex_density_multiplier_bps(hotspots_in_cell: u32, density_target: u32) -> u16 {
+    if hotspots_in_cell <= density_target {
+        BASIS_POINTS as u16
+    } else {
+        (BASIS_POINTS * density_target as u64 / hotspots_in_cell as u64) as u16
+    }
 }
 
 /// Validate geographic coordinates (basic validation)
 pub fn validate_coordinates(latitude: f64, longitude: f64) -> bool {
-    latitude >= -90.0 && latitude <= 90.0 && longitude >= -180.0 && longitude <= 180.0
+    (-90.0..=90.0).contains(&latitude) && (-180.0..=180.0).contains(&longitude)
 }
 
 /// Calculate distance between two points (simplified Haversine formula)
@@ -48,9 +639,9 @@ pub fn calculate_distance_km(lat1: f64, lon1: f64, lat2: f64, lon2: f64) -> f64
 }
 
 /// Check if a timestamp is within a certain period
-pub fn is_within_period(timestamp: i64, period_seconds: i64) -> bool {
-    let current_time = Clock::get().unwrap().unix_timestamp;
-    (current_time - timestamp) <= period_seconds
+pub fn is_within_period(timestamp: i64, period_seconds: i64) -> Result<bool> {
+    let current_time = Clock::get()?.unix_timestamp;
+    Ok(current_time.safe_sub(timestamp)? <= period_seconds)
 }
 
 /// Calculate reputation score update
@@ -61,7 +652,7 @@ pub fn update_reputation_score(
     poor_threshold: u8,
 ) -> u8 {
     if performance_score >= excellent_threshold {
-        std::cmp::min(100, current_score + 1)
+        std::cmp::min(100, current_score.saturating_add(1))
     } else if performance_score < poor_threshold {
         std::cmp::max(0, current_score.saturating_sub(2))
     } else {
@@ -77,18 +668,429 @@ pub fn validate_sensor_data(
     ph_level: u8,
 ) -> bool {
     soil_moisture <= 100 &&
-    temperature >= -50 && temperature <= 70 &&
+    (-50..=70).contains(&temperature) &&
     humidity <= 100 &&
     ph_level <= 14
 }
 
-/// Calculate staking rewards based on time and rate
+/// Calculate staking rewards based on time and rate (fixed-point, for quotes and display)
 pub fn calculate_staking_rewards(
     staked_amount: u64,
     apy_basis_points: u64,
     time_staked_seconds: i64,
-) -> u64 {
-    let annual_rate = apy_basis_points as f64 / 10000.0; // Convert basis points to decimal
-    let time_factor = time_staked_seconds as f64 / (365.25 * 24.0 * 3600.0); // Years
-    (staked_amount as f64 * annual_rate * time_factor) as u64
-}
\ No newline at end of file
+) -> Result<u64> {
+    let reward_per_token = apy_to_reward_per_token(apy_basis_points, time_staked_seconds)?;
+    to_u64(accumulated_rewards(staked_amount, reward_per_token)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::solana_program::sysvar;
+    use anchor_lang::solana_program::sysvar::instructions::{
+        construct_instructions_data, store_current_index, BorrowedInstruction,
+    };
+
+    const DAY: i64 = SECONDS_PER_DAY;
+    const TOKEN_UNIT: u64 = 10_u64.pow(PKN_DECIMALS as u32);
+
+    fn overflow() -> Error {
+        DePINError::ArithmeticOverflow.into()
+    }
+
+    #[test]
+    fn safe_math_fails_instead_of_wrapping() {
+        assert_eq!(u64::MAX.safe_add(1).unwrap_err(), overflow());
+        assert_eq!(0u64.safe_sub(1).unwrap_err(), overflow());
+        assert_eq!(u32::MAX.safe_mul(2).unwrap_err(), overflow());
+        assert_eq!(7u64.safe_div(0).unwrap_err(), overflow());
+        assert_eq!(i64::MIN.safe_sub(1).unwrap_err(), overflow());
+        assert_eq!(40u64.safe_add(2).unwrap(), 42);
+    }
+
+    #[test]
+    fn mul_div_uses_a_wide_intermediate() {
+        // u64::MAX * 3 overflows u64 but the quotient fits
+        assert_eq!(mul_div(u64::MAX, 3, 4).unwrap(), u64::MAX / 4 * 3 + 2);
+        assert_eq!(mul_div(1_000, 2_500, BASIS_POINTS).unwrap(), 250);
+        assert_eq!(mul_div(u64::MAX, 2, 1).unwrap_err(), overflow());
+        assert_eq!(mul_div(1, 1, 0).unwrap_err(), overflow());
+    }
+
+    #[test]
+    fn calculate_reward_scales_by_the_multiplier() {
+        assert_eq!(calculate_reward(10, TOKEN_UNIT, 120, 100).unwrap(), 12 * TOKEN_UNIT);
+        assert_eq!(calculate_reward(10, TOKEN_UNIT, 80, 100).unwrap(), 8 * TOKEN_UNIT);
+        // quantity * rate exceeds u64 before the divisor brings it back in range
+        assert_eq!(calculate_reward(u64::MAX, 2, 1, 4).unwrap(), u64::MAX / 2);
+        assert_eq!(calculate_reward(u64::MAX, 2, 1, 1).unwrap_err(), overflow());
+        assert_eq!(calculate_reward(1, 1, 1, 0).unwrap_err(), overflow());
+    }
+
+    fn staking_pool(reward_rate: u64) -> StakingPool {
+        StakingPool {
+            pool_type: PoolType::Governance,
+            vault: Pubkey::default(),
+            reward_vault: Pubkey::default(),
+            total_staked: 0,
+            reward_rate,
+            min_stake_amount: 0,
+            max_capacity: u64::MAX,
+            lock_period: 0,
+            total_rewards_paid: 0,
+            reward_per_token_stored: 0,
+            last_update_time: 0,
+            is_active: true,
+            bump: 0,
+        }
+    }
+
+    fn user_stake(amount: u64, staking_pool: &StakingPool) -> UserStake {
+        let mut user_stake = UserStake {
+            user: Pubkey::default(),
+            pool: Pubkey::default(),
+            amount,
+            start_time: 0,
+            last_reward_claim: 0,
+            pending_rewards: 0,
+            reward_debt: 0,
+            bump: 0,
+        };
+        reset_reward_debt(&mut user_stake, staking_pool).unwrap();
+        user_stake
+    }
+
+    #[test]
+    fn accumulator_pays_a_year_of_apy() {
+        let mut pool = staking_pool(1_000); // 10% APY
+        let mut stake = user_stake(1_000 * TOKEN_UNIT, &pool);
+
+        update_pool_rewards(&mut pool, SECONDS_PER_YEAR).unwrap();
+        settle_stake_rewards(&mut stake, &pool).unwrap();
+        assert_eq!(stake.pending_rewards, 100 * TOKEN_UNIT);
+
+        // Settling again at the same accumulator pays nothing more
+        settle_stake_rewards(&mut stake, &pool).unwrap();
+        assert_eq!(stake.pending_rewards, 100 * TOKEN_UNIT);
+    }
+
+    #[test]
+    fn accumulator_ignores_time_going_backwards() {
+        let mut pool = staking_pool(1_000);
+        pool.last_update_time = 100;
+        update_pool_rewards(&mut pool, 50).unwrap();
+        assert_eq!(pool.reward_per_token_stored, 0);
+        assert_eq!(pool.last_update_time, 100);
+        assert_eq!(apy_to_reward_per_token(1_000, -1).unwrap(), 0);
+    }
+
+    #[test]
+    fn accumulator_does_not_pay_late_stakers_retroactively() {
+        let mut pool = staking_pool(1_000);
+        let mut early = user_stake(1_000 * TOKEN_UNIT, &pool);
+
+        update_pool_rewards(&mut pool, SECONDS_PER_YEAR / 2).unwrap();
+        let mut late = user_stake(1_000 * TOKEN_UNIT, &pool);
+
+        update_pool_rewards(&mut pool, SECONDS_PER_YEAR).unwrap();
+        settle_stake_rewards(&mut early, &pool).unwrap();
+        settle_stake_rewards(&mut late, &pool).unwrap();
+
+        assert_eq!(early.pending_rewards, 100 * TOKEN_UNIT);
+        assert_eq!(late.pending_rewards, 50 * TOKEN_UNIT);
+    }
+
+    #[test]
+    fn accumulator_rebases_debt_when_a_stake_grows() {
+        let mut pool = staking_pool(1_000);
+        let mut stake = user_stake(1_000 * TOKEN_UNIT, &pool);
+
+        // Stake more half way through: settle first, then re-base on the new amount
+        update_pool_rewards(&mut pool, SECONDS_PER_YEAR / 2).unwrap();
+        settle_stake_rewards(&mut stake, &pool).unwrap();
+        stake.amount += 1_000 * TOKEN_UNIT;
+        reset_reward_debt(&mut stake, &pool).unwrap();
+
+        update_pool_rewards(&mut pool, SECONDS_PER_YEAR).unwrap();
+        settle_stake_rewards(&mut stake, &pool).unwrap();
+        assert_eq!(stake.pending_rewards, 50 * TOKEN_UNIT + 100 * TOKEN_UNIT);
+    }
+
+    fn checkpoint(timestamp: i64, voting_power: u64) -> VoteCheckpoint {
+        VoteCheckpoint { timestamp, voting_power, delegated_stake: voting_power }
+    }
+
+    #[test]
+    fn checkpoint_overwrites_until_a_proposal_opens() {
+        let mut history = vec![checkpoint(10, 100)];
+
+        push_checkpoint(&mut history, 4, checkpoint(20, 200), 5).unwrap();
+        assert_eq!(history, vec![checkpoint(20, 200)]);
+
+        // A proposal opened at 30 reads the checkpoint at 20, so it is kept
+        push_checkpoint(&mut history, 4, checkpoint(40, 300), 30).unwrap();
+        assert_eq!(history, vec![checkpoint(20, 200), checkpoint(40, 300)]);
+    }
+
+    #[test]
+    fn full_checkpoint_history_drops_the_oldest_once_no_proposal_needs_it() {
+        let mut history = vec![checkpoint(0, 100), checkpoint(DAY, 200), checkpoint(2 * DAY, 300)];
+
+        push_checkpoint(&mut history, 3, checkpoint(DAY + VOTING_PERIOD + 1, 400), 2 * DAY + 1).unwrap();
+        assert_eq!(
+            history,
+            vec![checkpoint(DAY, 200), checkpoint(2 * DAY, 300), checkpoint(DAY + VOTING_PERIOD + 1, 400)]
+        );
+    }
+
+    #[test]
+    fn full_checkpoint_history_merges_into_the_latest_at_the_lower_balance() {
+        let full = vec![checkpoint(0, 100), checkpoint(DAY, 200), checkpoint(2 * DAY, 300)];
+
+        let mut history = full.clone();
+        push_checkpoint(&mut history, 3, checkpoint(3 * DAY, 50), 2 * DAY + 1).unwrap();
+        assert_eq!(history, vec![checkpoint(0, 100), checkpoint(DAY, 200), checkpoint(2 * DAY, 50)]);
+
+        // An increase is not credited to proposals that may still read the latest checkpoint
+        let mut history = full.clone();
+        push_checkpoint(&mut history, 3, checkpoint(3 * DAY, 900), 2 * DAY + 1).unwrap();
+        assert_eq!(history, full);
+    }
+
+    fn proposal(yes_votes: u64, no_votes: u64, abstain_votes: u64) -> Proposal {
+        Proposal {
+            id: 0,
+            proposer: Pubkey::default(),
+            title: String::new(),
+            description: String::new(),
+            proposal_type: ProposalType::ParameterChange,
+            action: ProposalAction::Signal,
+            yes_votes,
+            no_votes,
+            total_votes: 0,
+            start_time: 0,
+            end_time: 0,
+            status: ProposalStatus::Active,
+            bump: 0,
+            abstain_votes,
+            total_staked_snapshot: 1_000,
+            quorum_bps: 1_000,
+            approval_threshold_bps: 6_666,
+            eta: 0,
+            expires_at: 0,
+            deposit: 0,
+        }
+    }
+
+    #[test]
+    fn tally_requires_quorum_of_all_votes() {
+        assert_eq!(tally_proposal(&proposal(99, 0, 0)).unwrap(), (false, false));
+        assert_eq!(tally_proposal(&proposal(100, 0, 0)).unwrap(), (true, true));
+        // Abstains count towards quorum but not approval
+        assert_eq!(tally_proposal(&proposal(10, 0, 90)).unwrap(), (true, true));
+        assert_eq!(tally_proposal(&proposal(0, 0, 100)).unwrap(), (true, false));
+    }
+
+    #[test]
+    fn tally_requires_strictly_more_than_the_threshold() {
+        let mut at_threshold = proposal(6_666, 3_334, 0);
+        at_threshold.approval_threshold_bps = 6_666;
+        assert_eq!(tally_proposal(&at_threshold).unwrap(), (true, false));
+
+        let above_threshold = proposal(6_667, 3_333, 0);
+        assert_eq!(tally_proposal(&above_threshold).unwrap(), (true, true));
+
+        let mut tie = proposal(500, 500, 0);
+        tie.approval_threshold_bps = MIN_APPROVAL_THRESHOLD_BPS;
+        assert_eq!(tally_proposal(&tie).unwrap(), (true, false));
+    }
+
+    #[test]
+    fn tally_does_not_overflow_on_large_stakes() {
+        let mut whale = proposal(u64::MAX / 2, 0, 0);
+        whale.total_staked_snapshot = u64::MAX;
+        whale.quorum_bps = 5_000;
+        assert_eq!(tally_proposal(&whale).unwrap(), (false, false));
+    }
+
+    #[test]
+    fn voting_rules_keep_thresholds_between_majority_and_unanimity() {
+        assert!(validate_voting_rules(1_000, &DEFAULT_APPROVAL_THRESHOLD_BPS));
+        assert!(!validate_voting_rules(10_001, &DEFAULT_APPROVAL_THRESHOLD_BPS));
+        assert!(!validate_voting_rules(1_000, &[4_999, 5_000, 5_000, 5_000, 5_000]));
+        assert!(!validate_voting_rules(1_000, &[5_000, 5_000, 5_000, 5_000, 10_000]));
+    }
+
+    #[test]
+    fn poc_outcome_splits_the_bond() {
+        let bond = 3 * POC_CHALLENGE_FEE;
+
+        // Coverage proven: the whole bond goes to the treasury
+        assert_eq!(poc_outcome(MIN_POC_WITNESSES, 5, bond), (true, false, bond));
+
+        // Not enough receipts to judge the hotspot: no penalty, but the fee is kept
+        let few_receipts = MIN_POC_RECEIPTS as usize - 1;
+        assert_eq!(poc_outcome(0, few_receipts, bond), (false, false, POC_CHALLENGE_FEE));
+
+        // Witnessable but unseen: penalised, fee kept, the rest refunded by the caller
+        assert_eq!(poc_outcome(1, MIN_POC_RECEIPTS as usize, bond), (false, true, POC_CHALLENGE_FEE));
+
+        // A bond smaller than the fee is taken whole
+        assert_eq!(poc_outcome(0, 0, 1), (false, false, 1));
+    }
+
+    fn plot_city() -> PlotCity {
+        PlotCity {
+            name: String::from("Karachi"),
+            origin_latitude: 24_800_000,
+            origin_longitude: 66_900_000,
+            cell_size: 1_000,
+            rows: 10,
+            cols: 10,
+            plot_price: 0,
+            plots_sold: 0,
+            bump: 0,
+        }
+    }
+
+    #[test]
+    fn plot_contains_its_south_west_edge_only() {
+        let city = plot_city();
+        assert!(plot_contains(&city, 0, 0, 24_800_000, 66_900_000));
+        assert!(plot_contains(&city, 2, 3, 24_802_999, 66_903_999));
+        assert!(!plot_contains(&city, 2, 3, 24_803_000, 66_903_500));
+        assert!(!plot_contains(&city, 2, 3, 24_802_500, 66_904_000));
+        assert!(!plot_contains(&city, 2, 3, 24_801_999, 66_903_500));
+    }
+
+    #[test]
+    fn plot_contains_handles_negative_origins() {
+        let mut city = plot_city();
+        city.origin_latitude = -1_000;
+        city.origin_longitude = -180_000_000;
+        assert!(plot_contains(&city, 0, 0, -1, -179_999_001));
+        assert!(!plot_contains(&city, 0, 0, 0, -179_999_001));
+        assert!(plot_contains(&city, u16::MAX, 0, -1_000 + u16::MAX as i32 * 1_000, -180_000_000));
+    }
+
+    #[test]
+    fn h3_cell_must_match_the_location() {
+        let (latitude, longitude) = (24_860_700, 67_001_100);
+        let point = LatLng::new(24.8607, 67.0011).unwrap();
+        let cell = point.to_cell(Resolution::Eight);
+
+        assert!(validate_h3_cell(u64::from(cell), latitude, longitude, DEFAULT_H3_RESOLUTION));
+        // A neighbouring hex, or the right hex at another resolution, is rejected
+        let neighbour = cell.grid_disk::<Vec<_>>(1).into_iter().find(|c| *c != cell).unwrap();
+        assert!(!validate_h3_cell(u64::from(neighbour), latitude, longitude, DEFAULT_H3_RESOLUTION));
+        assert!(!validate_h3_cell(u64::from(cell), latitude, longitude, 9));
+        assert!(!validate_h3_cell(u64::from(cell), latitude, longitude, MAX_H3_RESOLUTION + 1));
+        assert!(!validate_h3_cell(u64::from(cell), 90_000_001, longitude, DEFAULT_H3_RESOLUTION));
+    }
+
+    const DEVICE: Pubkey = Pubkey::new_from_array([7; 32]);
+
+    /// Precompile data verifying one signature whose key and message are stored inline
+    fn ed25519_data(public_key: &Pubkey, message: &[u8]) -> Vec<u8> {
+        let public_key_offset = 16u16;
+        let signature_offset = public_key_offset + 32;
+        let message_offset = signature_offset + 64;
+
+        let mut data = vec![1, 0];
+        for value in [
+            signature_offset,
+            u16::MAX,
+            public_key_offset,
+            u16::MAX,
+            message_offset,
+            message.len() as u16,
+            u16::MAX,
+        ] {
+            data.extend_from_slice(&value.to_le_bytes());
+        }
+        data.extend_from_slice(public_key.as_ref());
+        data.extend_from_slice(&[0; 64]);
+        data.extend_from_slice(message);
+        data
+    }
+
+    /// Check `message` against an instructions sysvar holding `previous` followed by
+    /// the program's own instruction
+    fn verify_after(previous: Option<(&Pubkey, &[u8])>, message: &[u8]) -> Result<()> {
+        let mut instructions = Vec::new();
+        if let Some((program_id, data)) = previous {
+            instructions.push(BorrowedInstruction { program_id, accounts: vec![], data });
+        }
+        instructions.push(BorrowedInstruction { program_id: &crate::ID, accounts: vec![], data: &[] });
+
+        let mut data = construct_instructions_data(&instructions);
+        store_current_index(&mut data, instructions.len() as u16 - 1);
+        let mut lamports = 0;
+        let sysvar_info = AccountInfo::new(
+            &sysvar::instructions::ID,
+            false,
+            false,
+            &mut lamports,
+            &mut data,
+            &sysvar::ID,
+            false,
+            0,
+        );
+
+        verify_device_signature(&sysvar_info, &DEVICE, message)
+    }
+
+    #[test]
+    fn device_signature_accepts_the_matching_precompile() {
+        let message = telemetry_message(&Pubkey::default(), 1, 100, &[1, 2, 3]);
+        let data = ed25519_data(&DEVICE, &message);
+        assert!(verify_after(Some((&ed25519_program::ID, &data)), &message).is_ok());
+    }
+
+    #[test]
+    fn device_signature_rejects_another_key_or_message() {
+        let message = telemetry_message(&Pubkey::default(), 1, 100, &[1, 2, 3]);
+        let invalid: Error = DePINError::InvalidDeviceSignature.into();
+
+        let other_key = ed25519_data(&Pubkey::new_unique(), &message);
+        assert_eq!(verify_after(Some((&ed25519_program::ID, &other_key)), &message).unwrap_err(), invalid);
+
+        let replayed = telemetry_message(&Pubkey::default(), 2, 100, &[1, 2, 3]);
+        let data = ed25519_data(&DEVICE, &message);
+        assert_eq!(verify_after(Some((&ed25519_program::ID, &data)), &replayed).unwrap_err(), invalid);
+    }
+
+    #[test]
+    fn device_signature_requires_an_inline_precompile_just_before() {
+        let message = telemetry_message(&Pubkey::default(), 1, 100, &[]);
+        let missing: Error = DePINError::MissingDeviceSignature.into();
+        let data = ed25519_data(&DEVICE, &message);
+
+        assert_eq!(verify_after(None, &message).unwrap_err(), missing);
+        assert_eq!(verify_after(Some((&crate::ID, &data)), &message).unwrap_err(), missing);
+
+        // Key or message read from another instruction
+        let mut external = data.clone();
+        external[8..10].copy_from_slice(&0u16.to_le_bytes());
+        assert_eq!(verify_after(Some((&ed25519_program::ID, &external)), &message).unwrap_err(), missing);
+
+        // Offsets past the end of the instruction
+        let mut truncated = data.clone();
+        truncated.truncate(data.len() - 1);
+        assert_eq!(verify_after(Some((&ed25519_program::ID, &truncated)), &message).unwrap_err(), missing);
+    }
+
+    #[test]
+    fn telemetry_must_be_new_and_fresh() {
+        assert!(validate_telemetry_freshness(2, 1, 1_000, 1_000 + TELEMETRY_MAX_AGE).is_ok());
+        assert!(validate_telemetry_freshness(2, 1, 1_000 + TELEMETRY_MAX_AGE, 1_000).is_ok());
+        assert_eq!(
+            validate_telemetry_freshness(1, 1, 1_000, 1_000).unwrap_err(),
+            DePINError::InvalidDeviceNonce.into()
+        );
+        assert_eq!(
+            validate_telemetry_freshness(2, 1, 1_000, 1_001 + TELEMETRY_MAX_AGE).unwrap_err(),
+            DePINError::StaleTelemetry.into()
+        );
+    }
+}
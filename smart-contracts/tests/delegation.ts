@@ -0,0 +1,139 @@
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  KARACHI,
+  LAHORE,
+  TestEnv,
+  createGovernancePool,
+  createProposal,
+  delegateRecordPda,
+  eventCpi,
+  expectError,
+  fundPkn,
+  networkStatePda,
+  newUser,
+  pda,
+  pkn,
+  registerHotspot,
+  setup,
+  stake,
+  userProfilePda,
+  voteProposal,
+  votePda,
+  warp,
+} from "./helpers";
+
+describe("vote delegation", () => {
+  let env: TestEnv;
+  let delegate: Keypair;
+  let delegator: Keypair;
+
+  const DELEGATE_STAKE = pkn(1_500);
+  const DELEGATOR_STAKE = pkn(400);
+
+  before(async () => {
+    env = await setup();
+    await createGovernancePool(env);
+
+    delegate = newUser(env);
+    delegator = newUser(env);
+
+    // Registering an asset is what sets up a user's profile
+    await registerHotspot(env, delegate, KARACHI);
+    await registerHotspot(env, delegator, LAHORE);
+    await fundPkn(env, delegate.publicKey, pkn(2_000));
+    await fundPkn(env, delegator.publicKey, pkn(500));
+    await stake(env, delegate, DELEGATE_STAKE);
+    await stake(env, delegator, DELEGATOR_STAKE);
+
+    await env.program.methods
+      .acceptDelegations()
+      .accounts({
+        delegateRecord: delegateRecordPda(delegate.publicKey),
+        delegate: delegate.publicKey,
+        systemProgram: SystemProgram.programId,
+        ...eventCpi(),
+      })
+      .signers([delegate])
+      .rpc();
+
+    await env.program.methods
+      .delegateVotes()
+      .accounts({
+        delegation: delegationPda(delegator.publicKey),
+        delegatorProfile: userProfilePda(delegator.publicKey),
+        delegateRecord: delegateRecordPda(delegate.publicKey),
+        networkState: networkStatePda,
+        delegator: delegator.publicKey,
+        systemProgram: SystemProgram.programId,
+        ...eventCpi(),
+      })
+      .signers([delegator])
+      .rpc();
+
+    // Voting power counts stake held strictly before a proposal opens
+    await warp(env, 60);
+  });
+
+  function delegationPda(delegator: PublicKey) {
+    return pda(Buffer.from("delegation"), delegator.toBuffer());
+  }
+
+  function overrideDelegateVote(proposal: PublicKey, vote: { yes: {} } | { no: {} }) {
+    return env.program.methods
+      .overrideDelegateVote(vote)
+      .accounts({
+        proposal,
+        userProfile: userProfilePda(delegator.publicKey),
+        delegateRecord: null,
+        delegation: delegationPda(delegator.publicKey),
+        userVote: votePda(proposal, delegator.publicKey),
+        delegateVote: votePda(proposal, delegate.publicKey),
+        voter: delegator.publicKey,
+        systemProgram: SystemProgram.programId,
+        ...eventCpi(),
+      })
+      .signers([delegator])
+      .rpc();
+  }
+
+  it("takes delegated stake back from a delegate who already voted", async () => {
+    const proposal = await createProposal(env, delegate, "Expand coverage to Hyderabad");
+
+    await voteProposal(env, delegate, proposal, { yes: {} }, delegateRecordPda(delegate.publicKey));
+    let tally = await env.program.account.proposal.fetch(proposal);
+    expect(tally.yesVotes.toString()).to.equal(DELEGATE_STAKE.add(DELEGATOR_STAKE).toString());
+
+    await overrideDelegateVote(proposal, { no: {} });
+
+    tally = await env.program.account.proposal.fetch(proposal);
+    expect(tally.yesVotes.toString()).to.equal(DELEGATE_STAKE.toString());
+    expect(tally.noVotes.toString()).to.equal(DELEGATOR_STAKE.toString());
+    expect(tally.totalVotes).to.equal(2);
+
+    const delegateVote = await env.program.account.userVote.fetch(votePda(proposal, delegate.publicKey));
+    expect(delegateVote.votingPower.toString()).to.equal(DELEGATE_STAKE.toString());
+    expect(delegateVote.overriddenPower.toString()).to.equal(DELEGATOR_STAKE.toString());
+
+    const delegatorVote = await env.program.account.userVote.fetch(votePda(proposal, delegator.publicKey));
+    expect(delegatorVote.votingPower.toString()).to.equal(DELEGATOR_STAKE.toString());
+
+    // The override is the delegator's one vote on this proposal
+    await warp(env, 1);
+    await expectError(overrideDelegateVote(proposal, { yes: {} }), "AlreadyVoted");
+  });
+
+  it("leaves a delegate who votes after the override only their own stake", async () => {
+    const proposal = await createProposal(env, delegate, "Expand coverage to Multan");
+
+    await overrideDelegateVote(proposal, { no: {} });
+    await voteProposal(env, delegate, proposal, { yes: {} }, delegateRecordPda(delegate.publicKey));
+
+    const tally = await env.program.account.proposal.fetch(proposal);
+    expect(tally.yesVotes.toString()).to.equal(DELEGATE_STAKE.toString());
+    expect(tally.noVotes.toString()).to.equal(DELEGATOR_STAKE.toString());
+
+    const delegateVote = await env.program.account.userVote.fetch(votePda(proposal, delegate.publicKey));
+    expect(delegateVote.votingPower.toString()).to.equal(DELEGATE_STAKE.toString());
+  });
+});
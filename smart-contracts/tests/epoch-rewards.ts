@@ -0,0 +1,154 @@
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import { TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import { expect } from "chai";
+import {
+  EPOCH_DURATION,
+  KARACHI,
+  LAHORE,
+  TestEnv,
+  WIFI_SECTOR,
+  createTokenAccount,
+  epochPda,
+  eventCpi,
+  expectError,
+  mintPda,
+  networkStatePda,
+  newUser,
+  pda,
+  registerHotspot,
+  setup,
+  submitWifiData,
+  tokenBalance,
+  treasuryPda,
+  userProfilePda,
+  warp,
+} from "./helpers";
+
+describe("epoch rewards", () => {
+  let env: TestEnv;
+
+  beforeEach(async () => {
+    env = await setup();
+  });
+
+  async function distributeRewards() {
+    const networkState = await env.program.account.networkState.fetch(networkStatePda);
+    await env.program.methods
+      .distributeRewards()
+      .accounts({
+        networkState: networkStatePda,
+        rewardEpoch: epochPda(networkState.currentEpoch),
+        nextEpoch: epochPda(networkState.currentEpoch.addn(1)),
+        payer: env.authority.publicKey,
+        systemProgram: SystemProgram.programId,
+        ...eventCpi(),
+      })
+      .rpc();
+  }
+
+  function operatorRewardsPda(owner: PublicKey) {
+    return pda(Buffer.from("operator_rewards"), owner.toBuffer());
+  }
+
+  async function settle(epoch: number, owner: PublicKey) {
+    const rewardEpoch = epochPda(epoch);
+    await env.program.methods
+      .settleEpochRewards()
+      .accounts({
+        rewardEpoch,
+        epochContribution: pda(Buffer.from("epoch_contribution"), rewardEpoch.toBuffer(), owner.toBuffer()),
+        operatorRewards: operatorRewardsPda(owner),
+        owner,
+        payer: env.authority.publicKey,
+        systemProgram: SystemProgram.programId,
+        ...eventCpi(),
+      })
+      .rpc();
+  }
+
+  function claim(owner: Keypair, userTokenAccount: PublicKey) {
+    return env.program.methods
+      .claimOperatorRewards()
+      .accounts({
+        operatorRewards: operatorRewardsPda(owner.publicKey),
+        userProfile: userProfilePda(owner.publicKey),
+        userTokenAccount,
+        mint: mintPda,
+        treasury: treasuryPda,
+        networkState: networkStatePda,
+        owner: owner.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        ...eventCpi(),
+      })
+      .signers([owner])
+      .rpc();
+  }
+
+  it("pays operators pro rata without exceeding the epoch's budget", async () => {
+    const alice = newUser(env);
+    const bob = newUser(env);
+    const aliceHotspot = await registerHotspot(env, alice, KARACHI);
+    const bobHotspot = await registerHotspot(env, bob, LAHORE);
+
+    await submitWifiData(env, aliceHotspot, alice, { dataGb: 7, nonce: 1 });
+    await submitWifiData(env, bobHotspot, bob, { dataGb: 3, nonce: 1 });
+
+    // Contributions can't be settled until the epoch is finalized
+    await expectError(settle(0, alice.publicKey), "EpochNotFinalized");
+
+    await warp(env, EPOCH_DURATION);
+    await distributeRewards();
+
+    const epoch = await env.program.account.rewardEpoch.fetch(epochPda(0));
+    expect(epoch.finalized).to.be.true;
+    const budget = BigInt(epoch.sectorBudgets[WIFI_SECTOR].toString());
+    expect(budget > 0n).to.be.true;
+    expect(epoch.emissionBudget.toString()).to.equal(budget.toString());
+    epoch.sectorBudgets.forEach((sectorBudget, sector) => {
+      if (sector !== WIFI_SECTOR) {
+        expect(sectorBudget.isZero(), `sector ${sector} had no contributions`).to.be.true;
+      }
+    });
+
+    const aliceTokens = await createTokenAccount(env, alice.publicKey);
+    const bobTokens = await createTokenAccount(env, bob.publicKey);
+    const treasuryBefore = await tokenBalance(env, treasuryPda);
+
+    await settle(0, alice.publicKey);
+    await settle(0, bob.publicKey);
+    await claim(alice, aliceTokens);
+    await claim(bob, bobTokens);
+
+    const alicePaid = await tokenBalance(env, aliceTokens);
+    const bobPaid = await tokenBalance(env, bobTokens);
+    const totalPaid = alicePaid + bobPaid;
+
+    // Shares round down, so at most one base unit per operator is left behind
+    expect(totalPaid <= budget, `paid ${totalPaid} of a ${budget} budget`).to.be.true;
+    expect(budget - totalPaid <= 2n).to.be.true;
+    expect(alicePaid > bobPaid).to.be.true;
+    expect(treasuryBefore - (await tokenBalance(env, treasuryPda))).to.equal(totalPaid);
+
+    // Each contribution is settled once and each claim pays out once. Warp first so the
+    // retries are new transactions rather than duplicates of the ones above.
+    await warp(env, 1);
+    await expectError(claim(alice, aliceTokens), "NoRewardsToClaim");
+    // Settling closes the contribution account, so Anchor rejects it as uninitialized
+    let settledTwice = true;
+    try {
+      await settle(0, alice.publicKey);
+    } catch (err) {
+      settledTwice = false;
+      expect(`${err}`).to.match(/AccountNotInitialized|0xbc4/);
+    }
+    expect(settledTwice, "a closed contribution was settled again").to.be.false;
+  });
+
+  it("keeps the budget of sectors nobody contributed to in the treasury", async () => {
+    await warp(env, EPOCH_DURATION);
+    await distributeRewards();
+
+    const epoch = await env.program.account.rewardEpoch.fetch(epochPda(0));
+    expect(epoch.emissionBudget.isZero()).to.be.true;
+  });
+});
@@ -0,0 +1,442 @@
+// Shared setup for the program tests. Tests run against bankrun so they can move the
+// clock past epochs, voting periods and timelocks without waiting on a validator.
+//
+// The asset NFTs are created through Metaplex, so the token metadata program must be
+// dumped to tests/fixtures first: `yarn fixtures`.
+
+import { BN, Program } from "@coral-xyz/anchor";
+import {
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  TOKEN_PROGRAM_ID,
+  createAssociatedTokenAccountIdempotentInstruction,
+  getAccount,
+  getAssociatedTokenAddressSync,
+} from "@solana/spl-token";
+import {
+  ComputeBudgetProgram,
+  Ed25519Program,
+  Keypair,
+  LAMPORTS_PER_SOL,
+  PublicKey,
+  SYSVAR_INSTRUCTIONS_PUBKEY,
+  SYSVAR_RENT_PUBKEY,
+  SystemProgram,
+  Transaction,
+} from "@solana/web3.js";
+import { BankrunProvider, startAnchor } from "anchor-bankrun";
+import { expect } from "chai";
+import { latLngToCell } from "h3-js";
+import { Clock, ProgramTestContext } from "solana-bankrun";
+import { DepinNetwork, IDL } from "../target/types/depin_network";
+
+export const PROGRAM_ID = new PublicKey("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+export const TOKEN_METADATA_PROGRAM_ID = new PublicKey("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s");
+
+// Mirrors constants.rs
+export const PKN_DECIMALS = 9;
+export const INITIAL_SUPPLY = pkn(1_000_000_000);
+export const H3_RESOLUTION = 8;
+export const SECONDS_PER_DAY = 86_400;
+export const EPOCH_DURATION = SECONDS_PER_DAY;
+export const VOTING_PERIOD = 7 * SECONDS_PER_DAY;
+export const TIMELOCK_DELAY = 2 * SECONDS_PER_DAY;
+export const EXECUTION_WINDOW = 7 * SECONDS_PER_DAY;
+export const WIFI_SECTOR = 0;
+
+export const KARACHI = { latitude: 24.8607, longitude: 67.0011 };
+export const LAHORE = { latitude: 31.5204, longitude: 74.3587 };
+
+export function pkn(amount: number): BN {
+  return new BN(amount).mul(new BN(10).pow(new BN(PKN_DECIMALS)));
+}
+
+export function u16le(value: number): Buffer {
+  return new BN(value).toArrayLike(Buffer, "le", 2);
+}
+
+export function u64le(value: number | BN): Buffer {
+  return new BN(value).toArrayLike(Buffer, "le", 8);
+}
+
+export function pda(...seeds: (Buffer | Uint8Array)[]): PublicKey {
+  return PublicKey.findProgramAddressSync(seeds, PROGRAM_ID)[0];
+}
+
+export const networkStatePda = pda(Buffer.from("network_state"));
+export const mintPda = pda(Buffer.from("mint"));
+export const treasuryPda = pda(Buffer.from("network_state"), Buffer.from("treasury"));
+
+export function epochPda(id: number | BN): PublicKey {
+  return pda(Buffer.from("reward_epoch"), u64le(id));
+}
+
+export function userProfilePda(owner: PublicKey): PublicKey {
+  return pda(Buffer.from("user_profile"), owner.toBuffer());
+}
+
+export function stakingPoolPda(poolTypeIndex: number): PublicKey {
+  return pda(Buffer.from("staking_pool"), Buffer.from([poolTypeIndex]));
+}
+
+export function proposalPda(id: number | BN): PublicKey {
+  return pda(Buffer.from("proposal"), u64le(id));
+}
+
+export function votePda(proposal: PublicKey, voter: PublicKey): PublicKey {
+  return pda(Buffer.from("vote"), proposal.toBuffer(), voter.toBuffer());
+}
+
+export function delegateRecordPda(delegate: PublicKey): PublicKey {
+  return pda(Buffer.from("delegate_record"), delegate.toBuffer());
+}
+
+// Accounts `#[event_cpi]` adds to an instruction. Instructions without it ignore them.
+export function eventCpi() {
+  return {
+    eventAuthority: pda(Buffer.from("__event_authority")),
+    program: PROGRAM_ID,
+  };
+}
+
+export interface TestEnv {
+  context: ProgramTestContext;
+  provider: BankrunProvider;
+  program: Program<DepinNetwork>;
+  authority: Keypair;
+}
+
+// Start a fresh bank with the program and token metadata loaded, and initialize the network
+export async function setup(): Promise<TestEnv> {
+  const context = await startAnchor(
+    ".",
+    [{ name: "mpl_token_metadata", programId: TOKEN_METADATA_PROGRAM_ID }],
+    [],
+  );
+  const provider = new BankrunProvider(context);
+  const program = new Program<DepinNetwork>(IDL, PROGRAM_ID, provider);
+  const authority = context.payer;
+
+  await program.methods
+    .initialize(INITIAL_SUPPLY, PKN_DECIMALS)
+    .accounts({
+      networkState: networkStatePda,
+      mint: mintPda,
+      treasury: treasuryPda,
+      rewardEpoch: epochPda(0),
+      authority: authority.publicKey,
+      systemProgram: SystemProgram.programId,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      rent: SYSVAR_RENT_PUBKEY,
+      ...eventCpi(),
+    })
+    .rpc();
+
+  return { context, provider, program, authority };
+}
+
+export async function now(env: TestEnv): Promise<number> {
+  const clock = await env.context.banksClient.getClock();
+  return Number(clock.unixTimestamp);
+}
+
+// Move the clock forward. Also advances the slot so repeated transactions get a new blockhash.
+export async function warp(env: TestEnv, seconds: number): Promise<void> {
+  const clock = await env.context.banksClient.getClock();
+  const slot = clock.slot + 1n;
+  env.context.warpToSlot(slot);
+  env.context.setClock(
+    new Clock(
+      slot,
+      clock.epochStartTimestamp,
+      clock.epoch,
+      clock.leaderScheduleEpoch,
+      clock.unixTimestamp + BigInt(seconds),
+    ),
+  );
+}
+
+// A new keypair with SOL for rent and fees
+export function newUser(env: TestEnv): Keypair {
+  const user = Keypair.generate();
+  env.context.setAccount(user.publicKey, {
+    lamports: 100 * LAMPORTS_PER_SOL,
+    data: Buffer.alloc(0),
+    owner: SystemProgram.programId,
+    executable: false,
+  });
+  return user;
+}
+
+export async function createTokenAccount(env: TestEnv, owner: PublicKey, mint = mintPda): Promise<PublicKey> {
+  const address = getAssociatedTokenAddressSync(mint, owner);
+  await env.provider.sendAndConfirm(
+    new Transaction().add(
+      createAssociatedTokenAccountIdempotentInstruction(env.authority.publicKey, address, owner, mint),
+    ),
+  );
+  return address;
+}
+
+export async function tokenBalance(env: TestEnv, address: PublicKey): Promise<bigint> {
+  return (await getAccount(env.provider.connection, address)).amount;
+}
+
+// Give a user PKN from the treasury
+export async function fundPkn(env: TestEnv, owner: PublicKey, amount: BN): Promise<PublicKey> {
+  const recipientTokenAccount = await createTokenAccount(env, owner);
+  await env.program.methods
+    .withdrawTreasury(amount)
+    .accounts({
+      treasury: treasuryPda,
+      recipientTokenAccount,
+      mint: mintPda,
+      networkState: networkStatePda,
+      authority: env.authority.publicKey,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      ...eventCpi(),
+    })
+    .rpc();
+  return recipientTokenAccount;
+}
+
+export interface Hotspot {
+  address: PublicKey;
+  nftMint: PublicKey;
+  device: Keypair;
+  h3Index: BN;
+  hexCell: PublicKey;
+}
+
+export function h3Cell(latitude: number, longitude: number): BN {
+  return new BN(latLngToCell(latitude, longitude, H3_RESOLUTION), 16);
+}
+
+export function hexCellPda(h3Index: BN): PublicKey {
+  return pda(Buffer.from("hex_cell"), h3Index.toArrayLike(Buffer, "le", 8));
+}
+
+export function microDegrees(degrees: number): number {
+  return Math.round(degrees * 1_000_000);
+}
+
+// Register a WiFi hotspot, which also mints its asset NFT to the owner and sets up
+// the owner's profile
+export async function registerHotspot(
+  env: TestEnv,
+  owner: Keypair,
+  position = KARACHI,
+): Promise<Hotspot> {
+  const networkState = await env.program.account.networkState.fetch(networkStatePda);
+  const address = pda(Buffer.from("wifi_hotspot"), u64le(networkState.nextAssetId));
+  const nftMint = pda(Buffer.from("asset_mint"), address.toBuffer());
+  const [nftMetadata] = PublicKey.findProgramAddressSync(
+    [Buffer.from("metadata"), TOKEN_METADATA_PROGRAM_ID.toBuffer(), nftMint.toBuffer()],
+    TOKEN_METADATA_PROGRAM_ID,
+  );
+  const [nftMasterEdition] = PublicKey.findProgramAddressSync(
+    [Buffer.from("metadata"), TOKEN_METADATA_PROGRAM_ID.toBuffer(), nftMint.toBuffer(), Buffer.from("edition")],
+    TOKEN_METADATA_PROGRAM_ID,
+  );
+  const device = Keypair.generate();
+  const h3Index = h3Cell(position.latitude, position.longitude);
+  const hexCell = hexCellPda(h3Index);
+
+  await env.program.methods
+    .registerWifiHotspot(
+      device.publicKey,
+      "Saddar, Karachi",
+      microDegrees(position.latitude),
+      microDegrees(position.longitude),
+      h3Index,
+      500,
+      100,
+      "https://buildpk.network/assets/hotspot.json",
+    )
+    .accounts({
+      wifiHotspot: address,
+      hexCell,
+      networkState: networkStatePda,
+      userProfile: userProfilePda(owner.publicKey),
+      owner: owner.publicKey,
+      nftMint,
+      ownerNft: getAssociatedTokenAddressSync(nftMint, owner.publicKey),
+      nftMetadata,
+      nftMasterEdition,
+      systemProgram: SystemProgram.programId,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
+      rent: SYSVAR_RENT_PUBKEY,
+      ...eventCpi(),
+    })
+    .preInstructions([ComputeBudgetProgram.setComputeUnitLimit({ units: 400_000 })])
+    .signers([owner])
+    .rpc();
+
+  return { address, nftMint, device, h3Index, hexCell };
+}
+
+// Submit a WiFi report signed by the hotspot's device, from whoever holds its NFT
+export async function submitWifiData(
+  env: TestEnv,
+  hotspot: Hotspot,
+  operator: Keypair,
+  { dataGb, nonce, usersConnected = 25, uptime = 99 }: {
+    dataGb: number;
+    nonce: number;
+    usersConnected?: number;
+    uptime?: number;
+  },
+): Promise<string> {
+  const timestamp = new BN(await now(env));
+  const metrics = Buffer.alloc(4 + 8 + 1);
+  metrics.writeUInt32LE(usersConnected, 0);
+  metrics.writeBigUInt64LE(BigInt(dataGb), 4);
+  metrics.writeUInt8(uptime, 12);
+  const message = Buffer.concat([
+    Buffer.from("pkn-telemetry-v1"),
+    hotspot.address.toBuffer(),
+    u64le(nonce),
+    timestamp.toTwos(64).toArrayLike(Buffer, "le", 8),
+    metrics,
+  ]);
+  const deviceSignature = Ed25519Program.createInstructionWithPrivateKey({
+    privateKey: hotspot.device.secretKey,
+    message,
+  });
+
+  const networkState = await env.program.account.networkState.fetch(networkStatePda);
+  const rewardEpoch = epochPda(networkState.currentEpoch);
+
+  return env.program.methods
+    .submitWifiData(usersConnected, new BN(dataGb), uptime, new BN(nonce), timestamp)
+    .accounts({
+      wifiHotspot: hotspot.address,
+      ownerNft: getAssociatedTokenAddressSync(hotspot.nftMint, operator.publicKey),
+      hexCell: hotspot.hexCell,
+      userProfile: userProfilePda(operator.publicKey),
+      networkState: networkStatePda,
+      rewardEpoch,
+      epochContribution: pda(Buffer.from("epoch_contribution"), rewardEpoch.toBuffer(), operator.publicKey.toBuffer()),
+      instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+      owner: operator.publicKey,
+      systemProgram: SystemProgram.programId,
+      ...eventCpi(),
+    })
+    .preInstructions([deviceSignature])
+    .signers([operator])
+    .rpc();
+}
+
+// Create the governance staking pool and stake `amount` for each user. Users need a
+// profile, so register a hotspot for them first.
+export async function createGovernancePool(env: TestEnv): Promise<PublicKey> {
+  const stakingPool = stakingPoolPda(3);
+  await env.program.methods
+    .createStakingPool({ governance: {} }, new BN(500), pkn(100), pkn(1_000_000), new BN(0), true)
+    .accounts({
+      stakingPool,
+      poolVault: pda(Buffer.from("pool_vault"), stakingPool.toBuffer()),
+      rewardVault: pda(Buffer.from("reward_vault"), stakingPool.toBuffer()),
+      mint: mintPda,
+      networkState: networkStatePda,
+      authority: env.authority.publicKey,
+      systemProgram: SystemProgram.programId,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      ...eventCpi(),
+    })
+    .rpc();
+  return stakingPool;
+}
+
+export async function stake(env: TestEnv, user: Keypair, amount: BN): Promise<void> {
+  const stakingPool = stakingPoolPda(3);
+  const userProfile = await env.program.account.userProfile.fetch(userProfilePda(user.publicKey));
+  const delegating = !userProfile.delegate.equals(PublicKey.default);
+
+  await env.program.methods
+    .stakeTokens(amount, { governance: {} })
+    .accounts({
+      stakingPool,
+      userStake: pda(Buffer.from("user_stake"), user.publicKey.toBuffer(), stakingPool.toBuffer()),
+      userTokenAccount: getAssociatedTokenAddressSync(mintPda, user.publicKey),
+      poolTokenAccount: pda(Buffer.from("pool_vault"), stakingPool.toBuffer()),
+      mint: mintPda,
+      networkState: networkStatePda,
+      userProfile: userProfilePda(user.publicKey),
+      delegateRecord: delegating ? delegateRecordPda(userProfile.delegate) : null,
+      user: user.publicKey,
+      systemProgram: SystemProgram.programId,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      ...eventCpi(),
+    })
+    .signers([user])
+    .rpc();
+}
+
+// Open a signalling proposal from `proposer` and return its address
+export async function createProposal(env: TestEnv, proposer: Keypair, title: string): Promise<PublicKey> {
+  const networkState = await env.program.account.networkState.fetch(networkStatePda);
+  const proposal = proposalPda(networkState.proposalsCount);
+
+  await env.program.methods
+    .createProposal(title, "Signalling proposal used by the tests", { protocolUpgrade: {} }, { signal: {} })
+    .accounts({
+      proposal,
+      depositVault: pda(Buffer.from("proposal_deposit"), proposal.toBuffer()),
+      proposerTokenAccount: getAssociatedTokenAddressSync(mintPda, proposer.publicKey),
+      mint: mintPda,
+      networkState: networkStatePda,
+      userProfile: userProfilePda(proposer.publicKey),
+      proposer: proposer.publicKey,
+      systemProgram: SystemProgram.programId,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      ...eventCpi(),
+    })
+    .signers([proposer])
+    .rpc();
+  return proposal;
+}
+
+export async function voteProposal(
+  env: TestEnv,
+  voter: Keypair,
+  proposal: PublicKey,
+  vote: { yes: {} } | { no: {} } | { abstain: {} },
+  delegateRecord: PublicKey | null = null,
+): Promise<void> {
+  await env.program.methods
+    .voteProposal(vote)
+    .accounts({
+      proposal,
+      userProfile: userProfilePda(voter.publicKey),
+      delegateRecord,
+      userVote: votePda(proposal, voter.publicKey),
+      voter: voter.publicKey,
+      systemProgram: SystemProgram.programId,
+      ...eventCpi(),
+    })
+    .signers([voter])
+    .rpc();
+}
+
+// Expect a transaction to fail with one of the program's errors
+export async function expectError(promise: Promise<unknown>, name: string): Promise<void> {
+  const idlError = IDL.errors.find((error) => error.name === name);
+  expect(idlError, `${name} is not a program error`).to.not.be.undefined;
+
+  let failed = false;
+  try {
+    await promise;
+  } catch (err) {
+    failed = true;
+    const logs: string[] = (err as { logs?: string[] }).logs ?? [];
+    const text = `${err}\n${logs.join("\n")}`;
+    const hexCode = `0x${idlError!.code.toString(16)}`;
+    expect(
+      text.includes(name) || text.includes(hexCode),
+      `expected ${name} (${hexCode}), got: ${text}`,
+    ).to.be.true;
+  }
+  expect(failed, `expected ${name}, but the transaction succeeded`).to.be.true;
+}
@@ -0,0 +1,120 @@
+import { createTransferInstruction, getAssociatedTokenAddressSync } from "@solana/spl-token";
+import { Keypair, SystemProgram, Transaction } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  Hotspot,
+  KARACHI,
+  LAHORE,
+  TestEnv,
+  createTokenAccount,
+  eventCpi,
+  expectError,
+  h3Cell,
+  hexCellPda,
+  microDegrees,
+  networkStatePda,
+  newUser,
+  registerHotspot,
+  setup,
+  submitWifiData,
+  tokenBalance,
+  userProfilePda,
+  warp,
+} from "./helpers";
+
+describe("asset NFT transfers", () => {
+  let env: TestEnv;
+  let seller: Keypair;
+  let buyer: Keypair;
+  let hotspot: Hotspot;
+
+  beforeEach(async () => {
+    env = await setup();
+    seller = newUser(env);
+    buyer = newUser(env);
+    hotspot = await registerHotspot(env, seller, KARACHI);
+
+    // Hand the NFT over with a plain token transfer, as a marketplace would
+    const buyerNft = await createTokenAccount(env, buyer.publicKey, hotspot.nftMint);
+    await env.provider.sendAndConfirm(
+      new Transaction().add(
+        createTransferInstruction(
+          getAssociatedTokenAddressSync(hotspot.nftMint, seller.publicKey),
+          buyerNft,
+          seller.publicKey,
+          1,
+        ),
+      ),
+      [seller],
+    );
+  });
+
+  function moveToLahore(operator: Keypair) {
+    const h3Index = h3Cell(LAHORE.latitude, LAHORE.longitude);
+    return env.program.methods
+      .updateWifiHotspot(
+        "Gulberg, Lahore",
+        microDegrees(LAHORE.latitude),
+        microDegrees(LAHORE.longitude),
+        h3Index,
+        500,
+        100,
+      )
+      .accounts({
+        wifiHotspot: hotspot.address,
+        ownerNft: getAssociatedTokenAddressSync(hotspot.nftMint, operator.publicKey),
+        currentHexCell: hotspot.hexCell,
+        newHexCell: hexCellPda(h3Index),
+        networkState: networkStatePda,
+        owner: operator.publicKey,
+        systemProgram: SystemProgram.programId,
+        ...eventCpi(),
+      })
+      .signers([operator])
+      .rpc();
+  }
+
+  it("lets the new holder operate the asset and locks out the previous one", async () => {
+    expect(await tokenBalance(env, getAssociatedTokenAddressSync(hotspot.nftMint, buyer.publicKey))).to.equal(1n);
+
+    // The previous owner can no longer report or reconfigure it
+    await expectError(submitWifiData(env, hotspot, seller, { dataGb: 5, nonce: 1 }), "NotAssetHolder");
+    await expectError(moveToLahore(seller), "NotAssetHolder");
+
+    // The holder can, without claiming first; their profile is set up on the first report
+    await submitWifiData(env, hotspot, buyer, { dataGb: 5, nonce: 1 });
+    const buyerProfile = await env.program.account.userProfile.fetch(userProfilePda(buyer.publicKey));
+    expect(buyerProfile.owner.equals(buyer.publicKey)).to.be.true;
+
+    await moveToLahore(buyer);
+    const moved = await env.program.account.wiFiHotspot.fetch(hotspot.address);
+    expect(moved.location).to.equal("Gulberg, Lahore");
+    expect(moved.h3Index.eq(h3Cell(LAHORE.latitude, LAHORE.longitude))).to.be.true;
+    hotspot.hexCell = hexCellPda(moved.h3Index);
+
+    // Claiming moves the owner of record and the per-owner counts
+    expect(moved.owner.equals(seller.publicKey)).to.be.true;
+    await env.program.methods
+      .claimWifiHotspotOwnership()
+      .accounts({
+        wifiHotspot: hotspot.address,
+        newOwnerNft: getAssociatedTokenAddressSync(hotspot.nftMint, buyer.publicKey),
+        previousOwnerProfile: userProfilePda(seller.publicKey),
+        newOwnerProfile: userProfilePda(buyer.publicKey),
+        newOwner: buyer.publicKey,
+        systemProgram: SystemProgram.programId,
+        ...eventCpi(),
+      })
+      .signers([buyer])
+      .rpc();
+
+    const claimed = await env.program.account.wiFiHotspot.fetch(hotspot.address);
+    expect(claimed.owner.equals(buyer.publicKey)).to.be.true;
+    const sellerProfile = await env.program.account.userProfile.fetch(userProfilePda(seller.publicKey));
+    expect(sellerProfile.wifiHotspots).to.equal(0);
+
+    // Reports are still rate limited for the new holder
+    await warp(env, 60);
+    await expectError(submitWifiData(env, hotspot, buyer, { dataGb: 5, nonce: 2 }), "SubmissionTooFrequent");
+  });
+});
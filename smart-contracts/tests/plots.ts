@@ -0,0 +1,178 @@
+import { TOKEN_PROGRAM_ID, getAssociatedTokenAddressSync } from "@solana/spl-token";
+import { BN } from "@coral-xyz/anchor";
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  KARACHI,
+  TestEnv,
+  eventCpi,
+  expectError,
+  fundPkn,
+  microDegrees,
+  mintPda,
+  networkStatePda,
+  newUser,
+  pda,
+  pkn,
+  setup,
+  tokenBalance,
+  treasuryPda,
+  u16le,
+} from "./helpers";
+
+describe("charging plots", () => {
+  let env: TestEnv;
+  let seller: Keypair;
+  let buyer: Keypair;
+  let plotCity: PublicKey;
+  let chargingPlot: PublicKey;
+  let plotListing: PublicKey;
+
+  const PLOT_PRICE = pkn(50);
+
+  beforeEach(async () => {
+    env = await setup();
+    plotCity = pda(Buffer.from("plot_city"), Buffer.from("Karachi"));
+
+    await env.program.methods
+      .createPlotCity("Karachi", microDegrees(KARACHI.latitude), microDegrees(KARACHI.longitude), 1_000, 10, 10, PLOT_PRICE)
+      .accounts({
+        plotCity,
+        networkState: networkStatePda,
+        authority: env.authority.publicKey,
+        systemProgram: SystemProgram.programId,
+        ...eventCpi(),
+      })
+      .rpc();
+
+    seller = newUser(env);
+    buyer = newUser(env);
+    await fundPkn(env, seller.publicKey, pkn(200));
+    await fundPkn(env, buyer.publicKey, pkn(200));
+
+    chargingPlot = pda(Buffer.from("charging_plot"), plotCity.toBuffer(), u16le(2), u16le(3));
+    plotListing = pda(Buffer.from("plot_listing"), chargingPlot.toBuffer());
+
+    await purchase(seller, 2, 3, PLOT_PRICE);
+  });
+
+  function tokenAccount(owner: Keypair) {
+    return getAssociatedTokenAddressSync(mintPda, owner.publicKey);
+  }
+
+  function purchase(owner: Keypair, row: number, col: number, maxPrice: BN) {
+    return env.program.methods
+      .purchasePlot(row, col, maxPrice)
+      .accounts({
+        plotCity,
+        chargingPlot: pda(Buffer.from("charging_plot"), plotCity.toBuffer(), u16le(row), u16le(col)),
+        buyerTokenAccount: tokenAccount(owner),
+        mint: mintPda,
+        treasury: treasuryPda,
+        networkState: networkStatePda,
+        buyer: owner.publicKey,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        ...eventCpi(),
+      })
+      .signers([owner])
+      .rpc();
+  }
+
+  function list(owner: Keypair, price: number) {
+    return env.program.methods
+      .listPlot(pkn(price))
+      .accounts({
+        chargingPlot,
+        plotListing,
+        seller: owner.publicKey,
+        systemProgram: SystemProgram.programId,
+        ...eventCpi(),
+      })
+      .signers([owner])
+      .rpc();
+  }
+
+  function cancel() {
+    return env.program.methods
+      .cancelListing()
+      .accounts({
+        chargingPlot,
+        plotListing,
+        seller: seller.publicKey,
+        ...eventCpi(),
+      })
+      .signers([seller])
+      .rpc();
+  }
+
+  function buy(maxPrice: number) {
+    return env.program.methods
+      .buyListedPlot(pkn(maxPrice))
+      .accounts({
+        chargingPlot,
+        plotListing,
+        buyerTokenAccount: tokenAccount(buyer),
+        sellerTokenAccount: tokenAccount(seller),
+        mint: mintPda,
+        seller: seller.publicKey,
+        buyer: buyer.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        ...eventCpi(),
+      })
+      .signers([buyer])
+      .rpc();
+  }
+
+  async function plotOwner() {
+    return (await env.program.account.chargingPlot.fetch(chargingPlot)).owner;
+  }
+
+  it("lists, cancels and sells a plot", async () => {
+    expect((await plotOwner()).equals(seller.publicKey)).to.be.true;
+    expect(await tokenBalance(env, tokenAccount(seller))).to.equal(BigInt(pkn(150).toString()));
+
+    // Only the owner can list
+    await expectError(list(buyer, 90), "NotPlotOwner");
+
+    // While listed the listing holds the plot
+    await list(seller, 90);
+    expect((await plotOwner()).equals(plotListing)).to.be.true;
+
+    // Cancelling hands it back and closes the listing
+    await cancel();
+    expect((await plotOwner()).equals(seller.publicKey)).to.be.true;
+    expect(await env.program.account.plotListing.fetchNullable(plotListing)).to.be.null;
+
+    // Relist and sell
+    await list(seller, 80);
+    await expectError(buy(70), "PriceAboveMax");
+    await buy(80);
+
+    const sold = await env.program.account.chargingPlot.fetch(chargingPlot);
+    expect(sold.owner.equals(buyer.publicKey)).to.be.true;
+    expect(sold.lastPrice.toString()).to.equal(pkn(80).toString());
+    expect(await env.program.account.plotListing.fetchNullable(plotListing)).to.be.null;
+    expect(await tokenBalance(env, tokenAccount(seller))).to.equal(BigInt(pkn(230).toString()));
+    expect(await tokenBalance(env, tokenAccount(buyer))).to.equal(BigInt(pkn(120).toString()));
+
+    // The previous owner can no longer list it
+    await expectError(list(seller, 100), "NotPlotOwner");
+  });
+
+  it("won't sell an unclaimed plot above the buyer's maximum", async () => {
+    await env.program.methods
+      .updatePlotPrice(pkn(60))
+      .accounts({
+        plotCity,
+        networkState: networkStatePda,
+        authority: env.authority.publicKey,
+        ...eventCpi(),
+      })
+      .rpc();
+
+    await expectError(purchase(buyer, 4, 5, PLOT_PRICE), "PriceAboveMax");
+    await purchase(buyer, 4, 5, pkn(60));
+    expect(await tokenBalance(env, tokenAccount(buyer))).to.equal(BigInt(pkn(140).toString()));
+  });
+});
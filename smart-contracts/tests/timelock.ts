@@ -0,0 +1,110 @@
+import { TOKEN_PROGRAM_ID, getAssociatedTokenAddressSync } from "@solana/spl-token";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  EXECUTION_WINDOW,
+  TIMELOCK_DELAY,
+  TestEnv,
+  VOTING_PERIOD,
+  createGovernancePool,
+  createProposal,
+  eventCpi,
+  expectError,
+  fundPkn,
+  mintPda,
+  networkStatePda,
+  newUser,
+  pda,
+  pkn,
+  registerHotspot,
+  setup,
+  stake,
+  treasuryPda,
+  voteProposal,
+  warp,
+} from "./helpers";
+
+describe("governance timelock", () => {
+  let env: TestEnv;
+  let proposer: Keypair;
+
+  beforeEach(async () => {
+    env = await setup();
+    await createGovernancePool(env);
+
+    proposer = newUser(env);
+    await registerHotspot(env, proposer);
+    await fundPkn(env, proposer.publicKey, pkn(2_000));
+    await stake(env, proposer, pkn(1_500));
+    await warp(env, 60);
+  });
+
+  // Open a proposal with enough yes votes to be queued once voting ends
+  async function passingProposal(title: string): Promise<PublicKey> {
+    const proposal = await createProposal(env, proposer, title);
+    await voteProposal(env, proposer, proposal, { yes: {} });
+    return proposal;
+  }
+
+  async function finalize(proposal: PublicKey) {
+    await env.program.methods
+      .finalizeProposal()
+      .accounts({
+        proposal,
+        networkState: networkStatePda,
+        depositVault: pda(Buffer.from("proposal_deposit"), proposal.toBuffer()),
+        treasury: treasuryPda,
+        proposerTokenAccount: getAssociatedTokenAddressSync(mintPda, proposer.publicKey),
+        finalizer: env.authority.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        ...eventCpi(),
+      })
+      .rpc();
+  }
+
+  function execute(proposal: PublicKey) {
+    return env.program.methods
+      .executeProposal()
+      .accounts({
+        proposal,
+        networkState: networkStatePda,
+        stakingPool: null,
+        treasury: null,
+        recipientTokenAccount: null,
+        tokenProgram: null,
+        executor: env.authority.publicKey,
+        ...eventCpi(),
+      })
+      .rpc();
+  }
+
+  it("only executes a queued proposal between its eta and expiry", async () => {
+    const onTime = await passingProposal("Open a second data centre in Islamabad");
+    const late = await passingProposal("Open a second data centre in Peshawar");
+
+    await warp(env, VOTING_PERIOD + 1);
+    await finalize(onTime);
+    await finalize(late);
+
+    const queued = await env.program.account.proposal.fetch(onTime);
+    expect(queued.status).to.deep.equal({ queued: {} });
+    expect(queued.expiresAt.sub(queued.eta).toNumber()).to.equal(EXECUTION_WINDOW);
+
+    // Still inside the timelock delay
+    await expectError(execute(onTime), "TimelockNotElapsed");
+    await warp(env, TIMELOCK_DELAY - 60);
+    await expectError(execute(onTime), "TimelockNotElapsed");
+
+    // At the eta it executes
+    await warp(env, 60);
+    await execute(onTime);
+    const executed = await env.program.account.proposal.fetch(onTime);
+    expect(executed.status).to.deep.equal({ executed: {} });
+
+    // Past the execution window it has expired
+    await warp(env, EXECUTION_WINDOW + 1);
+    await expectError(execute(late), "ProposalExpired");
+    const expired = await env.program.account.proposal.fetch(late);
+    expect(expired.status).to.deep.equal({ queued: {} });
+  });
+});